@@ -6,6 +6,10 @@
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use tauri::State;
+
+use crate::git::OperationRegistry;
+use crate::git::network::{NetworkSettings, create_certificate_check};
 
 /// Result of fetching an extension manifest from a Git URL.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -19,9 +23,17 @@ pub struct ExtensionFetchResult {
 ///
 /// Clones the repo to a temp directory, reads the manifest file,
 /// and returns both the manifest JSON and the temp path for subsequent install.
+/// The clone can be stopped with `cancel_operation(operation_id)`.
 #[tauri::command]
 #[specta::specta]
-pub async fn extension_fetch_manifest(git_url: String) -> Result<ExtensionFetchResult, String> {
+pub async fn extension_fetch_manifest(
+    git_url: String,
+    operation_id: Option<String>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<ExtensionFetchResult, String> {
+    let operation = registry.register(operation_id).map_err(|e| e.to_string())?;
+    let cancel = operation.token();
+
     let temp_base = std::env::temp_dir().join("flowforge-ext-install");
     tokio::fs::create_dir_all(&temp_base)
         .await
@@ -38,12 +50,36 @@ pub async fn extension_fetch_manifest(git_url: String) -> Result<ExtensionFetchR
     let path_clone = temp_path.clone();
 
     // Clone the repository (blocking git2 operation)
-    tokio::task::spawn_blocking(move || {
-        git2::Repository::clone(&url_clone, &path_clone)
-            .map_err(|e| format!("Failed to clone repository '{}': {}", url_clone, e))
+    let cloned = tokio::task::spawn_blocking(move || {
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.transfer_progress(|_| !cancel.is_cancelled());
+        callbacks.certificate_check(create_certificate_check(&url_clone));
+        let network = NetworkSettings::load(None);
+        network.apply_ca_info().map_err(|e| e.to_string())?;
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+        fetch_options.proxy_options(network.proxy_options());
+
+        git2::build::RepoBuilder::new()
+            .fetch_options(fetch_options)
+            .clone(&url_clone, &path_clone)
+            .map(|_| ())
+            .map_err(|e| {
+                if cancel.is_cancelled() {
+                    "Extension download cancelled".to_string()
+                } else {
+                    format!("Failed to clone repository '{}': {}", url_clone, e)
+                }
+            })
     })
     .await
-    .map_err(|e| format!("Clone task failed: {}", e))??;
+    .map_err(|e| format!("Clone task failed: {}", e))?;
+
+    if let Err(e) = cloned {
+        // Don't leave a partial clone behind
+        tokio::fs::remove_dir_all(&temp_path).await.ok();
+        return Err(e);
+    }
 
     // Check for manifest file
     let manifest_path = temp_path.join("flowforge.extension.json");
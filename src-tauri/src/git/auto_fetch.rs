@@ -0,0 +1,425 @@
+//! Background auto-fetch.
+//!
+//! Auto-fetch is off until the user enables it; the setting is kept in the
+//! app config directory. While it is enabled and a repository is open, a
+//! scheduler task fetches all of its remotes every `interval_secs` and emits
+//! `remote-updated` with the current branch's ahead/behind counts when
+//! something changed. Network errors (offline, metered or captive
+//! connections) pause fetching until a slow probe succeeds again;
+//! authentication failures back off exponentially so a bad credential does
+//! not hammer the server.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::git::RepositoryState;
+use crate::git::branch::{AheadBehind, branch_ahead_behind};
+use crate::git::cancellation::CancellationToken;
+use crate::git::credentials::without_prompts;
+use crate::git::error::GitError;
+use crate::git::remote::{FetchOptions, fetch_remote};
+
+/// Settings file in the app config directory.
+const CONFIG_FILE: &str = "auto-fetch.json";
+/// Shortest interval accepted from the frontend.
+const MIN_INTERVAL_SECS: u32 = 60;
+/// Upper bound for authentication backoff.
+const MAX_AUTH_BACKOFF: Duration = Duration::from_secs(60 * 60);
+/// How often connectivity is probed while paused.
+const PAUSED_PROBE: Duration = Duration::from_secs(15 * 60);
+
+/// User-facing auto-fetch settings.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoFetchConfig {
+    pub enabled: bool,
+    /// Seconds between fetches (at least 60)
+    pub interval_secs: u32,
+}
+
+impl Default for AutoFetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 300,
+        }
+    }
+}
+
+impl AutoFetchConfig {
+    /// Saved settings, or the defaults if none were saved or they are unreadable.
+    fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> Result<(), GitError> {
+        let write = || -> std::io::Result<()> {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+            fs::write(path, json)
+        };
+        write()
+            .map_err(|e| GitError::Internal(format!("Failed to write auto-fetch settings: {}", e)))
+    }
+}
+
+/// Current scheduler state, for display in the UI.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoFetchStatus {
+    pub config: AutoFetchConfig,
+    /// Why fetching is paused (network unavailable), if it is
+    pub paused_reason: Option<String>,
+    /// Consecutive authentication failures driving the backoff
+    pub auth_failures: u32,
+    /// Seconds until the next attempt
+    pub next_fetch_secs: u32,
+}
+
+/// Event emitted after a background fetch changed the current branch's position.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteUpdatedEvent {
+    pub branch_name: String,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+/// Global auto-fetch state - one scheduler per app instance
+pub struct AutoFetchState {
+    config_path: PathBuf,
+    status: Arc<Mutex<AutoFetchStatus>>,
+    task: Option<tauri::async_runtime::JoinHandle<()>>,
+}
+
+impl AutoFetchState {
+    /// Load the settings saved in `dir`, normally the app config directory.
+    pub fn new(dir: PathBuf) -> Self {
+        let config_path = dir.join(CONFIG_FILE);
+        let status = AutoFetchStatus {
+            config: AutoFetchConfig::load(&config_path),
+            ..Default::default()
+        };
+        Self {
+            config_path,
+            status: Arc::new(Mutex::new(status)),
+            task: None,
+        }
+    }
+}
+
+/// How a scheduled fetch went, as far as scheduling is concerned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FetchAttempt {
+    Fetched,
+    Offline(String),
+    AuthFailed,
+}
+
+impl FetchAttempt {
+    fn from_error(error: &GitError) -> Option<Self> {
+        match error {
+            GitError::NetworkError(message) => Some(Self::Offline(message.clone())),
+            GitError::AuthenticationFailed(_) => Some(Self::AuthFailed),
+            _ => None,
+        }
+    }
+}
+
+/// Record an attempt in the status and return the delay before the next one.
+fn schedule_next(status: &mut AutoFetchStatus, attempt: FetchAttempt) -> Duration {
+    let interval = Duration::from_secs(status.config.interval_secs.max(MIN_INTERVAL_SECS) as u64);
+
+    let delay = match attempt {
+        FetchAttempt::Fetched => {
+            status.paused_reason = None;
+            status.auth_failures = 0;
+            interval
+        }
+        FetchAttempt::Offline(reason) => {
+            status.paused_reason = Some(reason);
+            interval.max(PAUSED_PROBE)
+        }
+        FetchAttempt::AuthFailed => {
+            status.paused_reason = None;
+            status.auth_failures = status.auth_failures.saturating_add(1);
+            let factor = 1u32 << status.auth_failures.min(6);
+            (interval * factor).min(MAX_AUTH_BACKOFF.max(interval))
+        }
+    };
+
+    status.next_fetch_secs = delay.as_secs() as u32;
+    delay
+}
+
+/// Fetch every remote of the repository once, without prompting or progress.
+///
+/// An authentication failure on any remote triggers the backoff. Network
+/// errors only pause fetching when no remote could be reached at all, so a
+/// single dead remote does not stop the others from being fetched.
+fn fetch_once(repo_path: &Path) -> Result<(FetchAttempt, u32), GitError> {
+    without_prompts(|| fetch_each_remote(repo_path))
+}
+
+fn fetch_each_remote(repo_path: &Path) -> Result<(FetchAttempt, u32), GitError> {
+    let repo = git2::Repository::open(repo_path)?;
+    let mut reached_any = false;
+    let mut offline = None;
+    let mut auth_failed = false;
+    let mut received = 0;
+
+    for name in repo.remotes()?.iter().flatten() {
+        match fetch_remote(
+            &repo,
+            name,
+            &FetchOptions::default(),
+            &|_| {},
+            &CancellationToken::default(),
+        ) {
+            Ok(outcome) => {
+                reached_any = true;
+                received += outcome.objects_received + outcome.pruned_refs.len() as u32;
+            }
+            Err(e) => match FetchAttempt::from_error(&e) {
+                Some(FetchAttempt::AuthFailed) => auth_failed = true,
+                Some(attempt) => offline = Some(attempt),
+                None => {}
+            },
+        }
+    }
+
+    let attempt = match (auth_failed, reached_any, offline) {
+        (true, _, _) => FetchAttempt::AuthFailed,
+        (false, false, Some(offline)) => offline,
+        _ => FetchAttempt::Fetched,
+    };
+    Ok((attempt, received))
+}
+
+/// Ahead/behind of the checked-out branch, if HEAD is on a branch.
+fn current_ahead_behind(repo_path: &Path) -> Option<(String, AheadBehind)> {
+    let repo = git2::Repository::open(repo_path).ok()?;
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    let name = head.shorthand()?.to_string();
+    let counts = branch_ahead_behind(&repo, &name).ok()?;
+    Some((name, counts))
+}
+
+/// Start the scheduler for a repository, replacing any running one.
+pub fn start_auto_fetch(state: &mut AutoFetchState, repo_path: PathBuf, app_handle: AppHandle) {
+    stop_auto_fetch(state);
+
+    let status = state.status.clone();
+    let first_delay = {
+        let Ok(mut status) = status.lock() else {
+            return;
+        };
+        if !status.config.enabled {
+            return;
+        }
+        status.paused_reason = None;
+        status.auth_failures = 0;
+        schedule_next(&mut status, FetchAttempt::Fetched)
+    };
+
+    state.task = Some(tauri::async_runtime::spawn(async move {
+        let mut delay = first_delay;
+        let mut last_counts = current_ahead_behind(&repo_path).map(|(_, c)| (c.ahead, c.behind));
+
+        loop {
+            tokio::time::sleep(delay).await;
+
+            let path = repo_path.clone();
+            let fetched = tokio::task::spawn_blocking(move || {
+                let result = fetch_once(&path);
+                (result, current_ahead_behind(&path))
+            })
+            .await;
+
+            let Ok((result, position)) = fetched else {
+                break;
+            };
+            let attempt = match result {
+                Ok((attempt, received)) => {
+                    if let Some((branch_name, counts)) = position {
+                        let changed = last_counts != Some((counts.ahead, counts.behind));
+                        if changed || received > 0 {
+                            last_counts = Some((counts.ahead, counts.behind));
+                            let _ = app_handle.emit(
+                                "remote-updated",
+                                RemoteUpdatedEvent {
+                                    branch_name,
+                                    ahead: counts.ahead,
+                                    behind: counts.behind,
+                                },
+                            );
+                        }
+                    }
+                    attempt
+                }
+                // The repository went away or is unreadable; try again later
+                Err(_) => FetchAttempt::Fetched,
+            };
+
+            delay = match status.lock() {
+                Ok(mut status) => schedule_next(&mut status, attempt),
+                Err(_) => break,
+            };
+        }
+    }));
+}
+
+/// Stop the scheduler, if running.
+pub fn stop_auto_fetch(state: &mut AutoFetchState) {
+    if let Some(task) = state.task.take() {
+        task.abort();
+    }
+}
+
+/// Get the auto-fetch settings and scheduler state.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_auto_fetch_status(
+    auto_fetch_state: State<'_, Mutex<AutoFetchState>>,
+) -> Result<AutoFetchStatus, GitError> {
+    let state = auto_fetch_state
+        .lock()
+        .map_err(|_| GitError::Internal("Auto-fetch state poisoned".to_string()))?;
+    let status = state
+        .status
+        .lock()
+        .map_err(|_| GitError::Internal("Auto-fetch state poisoned".to_string()))?;
+    Ok(status.clone())
+}
+
+/// Change the auto-fetch settings and restart the scheduler for the open repository.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_auto_fetch_config(
+    config: AutoFetchConfig,
+    state: State<'_, RepositoryState>,
+    auto_fetch_state: State<'_, Mutex<AutoFetchState>>,
+    app_handle: AppHandle,
+) -> Result<AutoFetchStatus, GitError> {
+    if config.interval_secs < MIN_INTERVAL_SECS {
+        return Err(GitError::OperationFailed(format!(
+            "Auto-fetch interval must be at least {} seconds",
+            MIN_INTERVAL_SECS
+        )));
+    }
+
+    let repo_path = state.get_path().await;
+
+    let mut auto_fetch = auto_fetch_state
+        .lock()
+        .map_err(|_| GitError::Internal("Auto-fetch state poisoned".to_string()))?;
+    config.save(&auto_fetch.config_path)?;
+    if let Ok(mut status) = auto_fetch.status.lock() {
+        status.config = config;
+    }
+
+    match repo_path {
+        Some(path) => start_auto_fetch(&mut auto_fetch, path, app_handle),
+        None => stop_auto_fetch(&mut auto_fetch),
+    }
+
+    let status = auto_fetch
+        .status
+        .lock()
+        .map_err(|_| GitError::Internal("Auto-fetch state poisoned".to_string()))?;
+    Ok(status.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(interval_secs: u32) -> AutoFetchStatus {
+        AutoFetchStatus {
+            config: AutoFetchConfig {
+                enabled: true,
+                interval_secs,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_config_is_off_by_default_and_persisted() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let state = AutoFetchState::new(dir.path().to_path_buf());
+        assert!(!state.status.lock().unwrap().config.enabled);
+
+        let config = AutoFetchConfig {
+            enabled: true,
+            interval_secs: 600,
+        };
+        config.save(&state.config_path).unwrap();
+
+        let reloaded = AutoFetchState::new(dir.path().to_path_buf());
+        let status = reloaded.status.lock().unwrap();
+        assert!(status.config.enabled);
+        assert_eq!(status.config.interval_secs, 600);
+    }
+
+    #[test]
+    fn test_auth_failures_back_off_and_reset() {
+        let mut status = status(300);
+
+        let delays: Vec<u64> = (0..8)
+            .map(|_| schedule_next(&mut status, FetchAttempt::AuthFailed).as_secs())
+            .collect();
+        assert_eq!(delays, vec![600, 1200, 2400, 3600, 3600, 3600, 3600, 3600]);
+        assert_eq!(status.auth_failures, 8);
+
+        assert_eq!(
+            schedule_next(&mut status, FetchAttempt::Fetched).as_secs(),
+            300
+        );
+        assert_eq!(status.auth_failures, 0);
+    }
+
+    #[test]
+    fn test_network_errors_pause_until_a_fetch_succeeds() {
+        let mut status = status(120);
+
+        let delay = schedule_next(&mut status, FetchAttempt::Offline("no route".to_string()));
+        assert_eq!(delay, PAUSED_PROBE);
+        assert_eq!(status.paused_reason.as_deref(), Some("no route"));
+
+        schedule_next(&mut status, FetchAttempt::Fetched);
+        assert_eq!(status.paused_reason, None);
+        assert_eq!(status.next_fetch_secs, 120);
+    }
+
+    #[test]
+    fn test_fetch_once_pauses_only_when_no_remote_is_reachable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path().join("work")).unwrap();
+        repo.remote("gone", dir.path().join("missing.git").to_str().unwrap())
+            .unwrap();
+
+        let (attempt, _) = fetch_once(repo.path()).unwrap();
+        assert!(matches!(attempt, FetchAttempt::Offline(_)));
+
+        git2::Repository::init_bare(dir.path().join("origin.git")).unwrap();
+        repo.remote("origin", dir.path().join("origin.git").to_str().unwrap())
+            .unwrap();
+        let (attempt, received) = fetch_once(repo.path()).unwrap();
+        assert_eq!(attempt, FetchAttempt::Fetched);
+        assert_eq!(received, 0);
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -6,6 +7,8 @@ use tauri::State;
 
 use crate::git::error::GitError;
 use crate::git::repository::RepositoryState;
+use crate::git::stash::{stash_apply_internal, stash_save_internal};
+use crate::git::undo::record_operation;
 
 /// How many commits a local branch is ahead/behind its upstream.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -35,6 +38,17 @@ pub struct BranchInfo {
     pub is_remote: bool,
     /// Remote name (e.g., "origin") for remote branches, None for local
     pub remote_name: Option<String>,
+    /// `branch.<name>.description` for local branches
+    pub description: Option<String>,
+}
+
+/// Read `branch.<name>.description`, treating a blank value as unset.
+fn branch_description(config: &git2::Config, name: &str) -> Option<String> {
+    config
+        .get_string(&format!("branch.{}.description", name))
+        .ok()
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
 }
 
 /// List all local branches in the repository.
@@ -56,6 +70,7 @@ pub async fn list_branches(state: State<'_, RepositoryState>) -> Result<Vec<Bran
             Err(e) => return Err(e.into()),
         };
 
+        let config = repo.config()?.snapshot()?;
         let mut branches = Vec::new();
 
         for branch_result in repo.branches(Some(git2::BranchType::Local))? {
@@ -82,6 +97,7 @@ pub async fn list_branches(state: State<'_, RepositoryState>) -> Result<Vec<Bran
             };
 
             branches.push(BranchInfo {
+                description: branch_description(&config, &name),
                 name,
                 is_head,
                 last_commit_oid,
@@ -120,82 +136,262 @@ pub async fn create_branch(
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
+    record_operation(
+        repo_path.clone(),
+        format!("Create branch '{}'", name),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
 
-        // Validate branch name
-        if !git2::Branch::name_is_valid(&name)? {
-            return Err(GitError::InvalidBranchName(name));
-        }
+            // Validate branch name
+            if !git2::Branch::name_is_valid(&name)? {
+                return Err(GitError::InvalidBranchName(name));
+            }
 
-        // Check if branch already exists
-        if repo.find_branch(&name, git2::BranchType::Local).is_ok() {
-            return Err(GitError::BranchAlreadyExists(name));
-        }
+            // Check if branch already exists
+            if repo.find_branch(&name, git2::BranchType::Local).is_ok() {
+                return Err(GitError::BranchAlreadyExists(name));
+            }
 
-        // Get HEAD commit
-        let head_commit = repo.head()?.peel_to_commit()?;
+            // Get HEAD commit
+            let head_commit = repo.head()?.peel_to_commit()?;
 
-        // Create branch
-        let branch = repo.branch(&name, &head_commit, false)?;
+            // Create branch
+            let branch = repo.branch(&name, &head_commit, false)?;
 
-        // Optionally checkout the new branch
-        if checkout {
-            repo.set_head(&format!("refs/heads/{}", name))?;
-            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
-        }
+            // Optionally checkout the new branch
+            if checkout {
+                repo.set_head(&format!("refs/heads/{}", name))?;
+                repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
+            }
 
-        let commit = branch.get().peel_to_commit()?;
+            let commit = branch.get().peel_to_commit()?;
 
-        Ok(BranchInfo {
-            name: name.clone(),
-            is_head: checkout,
-            last_commit_oid: format!("{:.7}", commit.id()),
-            last_commit_message: commit.summary().unwrap_or("").to_string(),
-            is_merged: if checkout { None } else { Some(true) },
-            is_remote: false,
-            remote_name: None,
-        })
-    })
+            Ok(BranchInfo {
+                name: name.clone(),
+                is_head: checkout,
+                last_commit_oid: format!("{:.7}", commit.id()),
+                last_commit_message: commit.summary().unwrap_or("").to_string(),
+                is_merged: if checkout { None } else { Some(true) },
+                is_remote: false,
+                remote_name: None,
+                description: None,
+            })
+        },
+    )
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// How `checkout_branch` treats uncommitted changes that the target
+/// branch would overwrite.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckoutMode {
+    /// Refuse to switch when changes would be overwritten
+    #[default]
+    Safe,
+    /// Only report what would conflict; never switch
+    Preview,
+    /// Stash conflicting changes, switch, then re-apply them
+    Autostash,
+}
+
+/// Outcome of `checkout_branch`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutResult {
+    /// Whether HEAD now points at the target branch
+    pub switched: bool,
+    /// Uncommitted files (including untracked) before the checkout
+    pub dirty_files: Vec<String>,
+    /// Dirty files the target branch would overwrite
+    pub conflicts: Vec<String>,
+    /// Whether local changes were stashed and re-applied
+    pub autostashed: bool,
+    /// Files left conflicted by re-applying the autostash; the stash is kept
+    /// (as stash@{0}) when this is not empty
+    pub reapply_conflicts: Vec<String>,
+}
+
+/// Uncommitted paths in the working tree and index, ignoring ignored files.
+fn dirty_files(repo: &git2::Repository) -> Result<Vec<String>, GitError> {
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    Ok(repo
+        .statuses(Some(&mut opts))?
+        .iter()
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .collect())
+}
+
+/// Dirty files a safe checkout of `target` would refuse to overwrite: those
+/// that differ between HEAD and the target.
+///
+/// libgit2 skips conflict notifications on a dry-run checkout, so this
+/// compares the trees instead.
+pub fn checkout_conflicts(
+    repo: &git2::Repository,
+    target: &git2::Commit,
+    dirty_files: &[String],
+) -> Result<Vec<String>, GitError> {
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+        Err(e) => return Err(e.into()),
+    };
+    let diff = repo.diff_tree_to_tree(head_tree.as_ref(), Some(&target.tree()?), None)?;
+    let changed: HashSet<&Path> = diff
+        .deltas()
+        .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+        .flatten()
+        .collect();
+
+    Ok(dirty_files
+        .iter()
+        .filter(|path| changed.contains(Path::new(path.as_str())))
+        .cloned()
+        .collect())
+}
+
+/// Check out a local branch's tree, then point HEAD at it, so a refused
+/// checkout leaves HEAD where it was.
+fn switch_to_branch(repo: &git2::Repository, branch_name: &str) -> Result<(), GitError> {
+    let ref_name = format!("refs/heads/{}", branch_name);
+    let commit = repo.find_reference(&ref_name)?.peel_to_commit()?;
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(git2::build::CheckoutBuilder::new().safe()),
+    )
+    .map_err(|e| {
+        if e.code() == git2::ErrorCode::Conflict
+            || e.message().contains("conflict")
+            || e.message().contains("overwrite")
+        {
+            GitError::DirtyWorkingDirectory
+        } else {
+            GitError::from(e)
+        }
+    })?;
+    repo.set_head(&ref_name)?;
+    Ok(())
+}
+
+/// Paths with conflicts in the index.
+fn index_conflicts(repo: &git2::Repository) -> Result<Vec<String>, GitError> {
+    let index = repo.index()?;
+    if !index.has_conflicts() {
+        return Ok(Vec::new());
+    }
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+            paths.push(String::from_utf8_lossy(&entry.path).to_string());
+        }
+    }
+    Ok(paths)
+}
+
+pub fn checkout_branch_internal(
+    repo: &mut git2::Repository,
+    branch_name: &str,
+    mode: CheckoutMode,
+) -> Result<CheckoutResult, GitError> {
+    let target = repo
+        .find_branch(branch_name, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(branch_name.to_string()))?
+        .get()
+        .peel_to_commit()?;
+    let dirty_files = dirty_files(repo)?;
+    let conflicts = checkout_conflicts(repo, &target, &dirty_files)?;
+    let mut result = CheckoutResult {
+        switched: false,
+        dirty_files,
+        conflicts,
+        autostashed: false,
+        reapply_conflicts: Vec::new(),
+    };
+
+    match mode {
+        CheckoutMode::Preview => return Ok(result),
+        CheckoutMode::Safe if !result.conflicts.is_empty() => {
+            return Err(GitError::DirtyWorkingDirectory);
+        }
+        // Non-conflicting changes carry over to the target as with `git switch`
+        CheckoutMode::Safe | CheckoutMode::Autostash if result.conflicts.is_empty() => {
+            drop(target);
+            switch_to_branch(repo, branch_name)?;
+            result.switched = true;
+            return Ok(result);
+        }
+        _ => {}
+    }
+
+    // Untracked files only need stashing if the target would overwrite them
+    let workdir_statuses = repo.statuses(None)?;
+    let include_untracked = result.conflicts.iter().any(|path| {
+        workdir_statuses
+            .iter()
+            .any(|entry| entry.path() == Some(path.as_str()) && entry.status().is_wt_new())
+    });
+    drop(workdir_statuses);
+    drop(target);
+
+    let message = format!("autostash before checkout of {}", branch_name);
+    stash_save_internal(repo, Some(&message), include_untracked)?;
+    if let Err(e) = switch_to_branch(repo, branch_name) {
+        // Put the changes back where they were
+        stash_apply_internal(repo, 0, true)?;
+        repo.stash_drop(0)?;
+        return Err(e);
+    }
+    result.switched = true;
+    result.autostashed = true;
+
+    // Like `git rebase --autostash`, a stash that does not apply cleanly is kept
+    match stash_apply_internal(repo, 0, false) {
+        Ok(()) => {}
+        Err(GitError::OperationFailed(message)) if message.contains("conflict") => {
+            // Refused outright (nothing applied); the stash still holds everything
+            result.reapply_conflicts = result.conflicts.clone();
+            return Ok(result);
+        }
+        Err(e) => return Err(e),
+    }
+    result.reapply_conflicts = index_conflicts(repo)?;
+    if result.reapply_conflicts.is_empty() {
+        repo.stash_drop(0)?;
+    }
+    Ok(result)
 }
 
 /// Switch to an existing branch.
+///
+/// `mode` (default `Safe`) decides what happens when uncommitted changes
+/// would be overwritten by the target: refuse, only report them, or
+/// stash and re-apply them around the switch.
 #[tauri::command]
 #[specta::specta]
 pub async fn checkout_branch(
     branch_name: String,
+    mode: Option<CheckoutMode>,
     state: State<'_, RepositoryState>,
-) -> Result<(), GitError> {
+) -> Result<CheckoutResult, GitError> {
     let repo_path = state
         .get_path()
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-
-        // Check if branch exists
-        repo.find_branch(&branch_name, git2::BranchType::Local)
-            .map_err(|_| GitError::BranchNotFound(branch_name.clone()))?;
-
-        // Two-step checkout process
-        repo.set_head(&format!("refs/heads/{}", branch_name))?;
-
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))
-            .map_err(|e| {
-                if e.message().contains("conflict") || e.message().contains("overwrite") {
-                    GitError::DirtyWorkingDirectory
-                } else {
-                    GitError::from(e)
-                }
-            })?;
-
-        Ok(())
-    })
+    record_operation(
+        repo_path.clone(),
+        format!("Checkout '{}'", branch_name),
+        move || {
+            let mut repo = git2::Repository::open(&repo_path)?;
+            checkout_branch_internal(&mut repo, &branch_name, mode.unwrap_or_default())
+        },
+    )
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// Delete a local branch.
@@ -211,33 +407,36 @@ pub async fn delete_branch(
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
+    record_operation(
+        repo_path.clone(),
+        format!("Delete branch '{}'", branch_name),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
 
-        let mut branch = repo
-            .find_branch(&branch_name, git2::BranchType::Local)
-            .map_err(|_| GitError::BranchNotFound(branch_name.clone()))?;
+            let mut branch = repo
+                .find_branch(&branch_name, git2::BranchType::Local)
+                .map_err(|_| GitError::BranchNotFound(branch_name.clone()))?;
 
-        if branch.is_head() {
-            return Err(GitError::CannotDeleteCurrentBranch);
-        }
+            if branch.is_head() {
+                return Err(GitError::CannotDeleteCurrentBranch);
+            }
 
-        if !force {
-            let head_commit = repo.head()?.peel_to_commit()?;
-            let branch_commit = branch.get().peel_to_commit()?;
-            let merge_base = repo.merge_base(head_commit.id(), branch_commit.id())?;
+            if !force {
+                let head_commit = repo.head()?.peel_to_commit()?;
+                let branch_commit = branch.get().peel_to_commit()?;
+                let merge_base = repo.merge_base(head_commit.id(), branch_commit.id())?;
 
-            if merge_base != branch_commit.id() {
-                return Err(GitError::BranchNotMerged(branch_name));
+                if merge_base != branch_commit.id() {
+                    return Err(GitError::BranchNotMerged(branch_name));
+                }
             }
-        }
 
-        branch.delete()?;
+            branch.delete()?;
 
-        Ok(())
-    })
+            Ok(())
+        },
+    )
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// List all branches (local and optionally remote) in the repository.
@@ -261,6 +460,7 @@ pub async fn list_all_branches(
             Err(e) => return Err(e.into()),
         };
 
+        let config = repo.config()?.snapshot()?;
         let mut branches = Vec::new();
 
         // Always include local branches
@@ -288,6 +488,7 @@ pub async fn list_all_branches(
             };
 
             branches.push(BranchInfo {
+                description: branch_description(&config, &name),
                 name,
                 is_head,
                 last_commit_oid,
@@ -339,6 +540,7 @@ pub async fn list_all_branches(
                     is_merged,
                     is_remote: true,
                     remote_name,
+                    description: None,
                 });
             }
         }
@@ -372,65 +574,49 @@ pub async fn checkout_remote_branch(
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-
-        // Parse local name from remote branch (e.g., "origin/feature/x" -> "feature/x")
-        let local_name = remote_branch
-            .split_once('/')
-            .map(|(_, rest)| rest.to_string())
-            .ok_or_else(|| {
-                GitError::InvalidBranchName(format!(
-                    "Invalid remote branch format: {}",
-                    remote_branch
-                ))
-            })?;
-
-        // Check if a local branch with that name already exists
-        if repo
-            .find_branch(&local_name, git2::BranchType::Local)
-            .is_ok()
-        {
-            repo.set_head(&format!("refs/heads/{}", local_name))?;
-            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))
-                .map_err(|e| {
-                    if e.message().contains("conflict") || e.message().contains("overwrite") {
-                        GitError::DirtyWorkingDirectory
-                    } else {
-                        GitError::from(e)
-                    }
+    record_operation(
+        repo_path.clone(),
+        format!("Checkout '{}'", remote_branch),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+
+            // Parse local name from remote branch (e.g., "origin/feature/x" -> "feature/x")
+            let local_name = remote_branch
+                .split_once('/')
+                .map(|(_, rest)| rest.to_string())
+                .ok_or_else(|| {
+                    GitError::InvalidBranchName(format!(
+                        "Invalid remote branch format: {}",
+                        remote_branch
+                    ))
                 })?;
-            return Ok(());
-        }
 
-        // Find the remote reference
-        let remote_ref = repo
-            .find_branch(&remote_branch, git2::BranchType::Remote)
-            .map_err(|_| GitError::BranchNotFound(remote_branch.clone()))?;
+            // Check if a local branch with that name already exists
+            if repo
+                .find_branch(&local_name, git2::BranchType::Local)
+                .is_ok()
+            {
+                return switch_to_branch(&repo, &local_name);
+            }
 
-        let commit = remote_ref.get().peel_to_commit()?;
+            // Find the remote reference
+            let remote_ref = repo
+                .find_branch(&remote_branch, git2::BranchType::Remote)
+                .map_err(|_| GitError::BranchNotFound(remote_branch.clone()))?;
 
-        // Create a local branch from that commit
-        let mut local_branch = repo.branch(&local_name, &commit, false)?;
+            let commit = remote_ref.get().peel_to_commit()?;
 
-        // Set the upstream tracking
-        local_branch.set_upstream(Some(&remote_branch))?;
+            // Create a local branch from that commit
+            let mut local_branch = repo.branch(&local_name, &commit, false)?;
 
-        // Checkout the new local branch
-        repo.set_head(&format!("refs/heads/{}", local_name))?;
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))
-            .map_err(|e| {
-                if e.message().contains("conflict") || e.message().contains("overwrite") {
-                    GitError::DirtyWorkingDirectory
-                } else {
-                    GitError::from(e)
-                }
-            })?;
+            // Set the upstream tracking
+            local_branch.set_upstream(Some(&remote_branch))?;
 
-        Ok(())
-    })
+            // Checkout the new local branch
+            switch_to_branch(&repo, &local_name)
+        },
+    )
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// A recently checked-out branch extracted from the reflog.
@@ -532,76 +718,79 @@ pub async fn batch_delete_branches(
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
+    record_operation(
+        repo_path.clone(),
+        format!("Delete {} branches", branch_names.len()),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
 
-        let head_commit = match repo.head() {
-            Ok(head) => Some(head.peel_to_commit()?),
-            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
-            Err(e) => return Err(e.into()),
-        };
+            let head_commit = match repo.head() {
+                Ok(head) => Some(head.peel_to_commit()?),
+                Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+                Err(e) => return Err(e.into()),
+            };
 
-        let mut results = Vec::with_capacity(branch_names.len());
-        let mut total_deleted = 0u32;
-        let mut total_failed = 0u32;
+            let mut results = Vec::with_capacity(branch_names.len());
+            let mut total_deleted = 0u32;
+            let mut total_failed = 0u32;
 
-        for name in &branch_names {
-            let result: Result<(), String> = (|| {
-                let mut branch = repo
-                    .find_branch(name, git2::BranchType::Local)
-                    .map_err(|_| format!("Branch '{}' not found", name))?;
+            for name in &branch_names {
+                let result: Result<(), String> = (|| {
+                    let mut branch = repo
+                        .find_branch(name, git2::BranchType::Local)
+                        .map_err(|_| format!("Branch '{}' not found", name))?;
 
-                if branch.is_head() {
-                    return Err("Cannot delete the current branch".to_string());
-                }
+                    if branch.is_head() {
+                        return Err("Cannot delete the current branch".to_string());
+                    }
 
-                if !force {
-                    if let Some(ref head) = head_commit {
-                        let branch_commit = branch
-                            .get()
-                            .peel_to_commit()
-                            .map_err(|e| e.message().to_string())?;
-                        let merge_base = repo
-                            .merge_base(head.id(), branch_commit.id())
-                            .map_err(|e| e.message().to_string())?;
-                        if merge_base != branch_commit.id() {
-                            return Err(format!("Branch '{}' is not fully merged", name));
+                    if !force {
+                        if let Some(ref head) = head_commit {
+                            let branch_commit = branch
+                                .get()
+                                .peel_to_commit()
+                                .map_err(|e| e.message().to_string())?;
+                            let merge_base = repo
+                                .merge_base(head.id(), branch_commit.id())
+                                .map_err(|e| e.message().to_string())?;
+                            if merge_base != branch_commit.id() {
+                                return Err(format!("Branch '{}' is not fully merged", name));
+                            }
                         }
                     }
-                }
 
-                branch.delete().map_err(|e| e.message().to_string())?;
-                Ok(())
-            })();
-
-            match result {
-                Ok(()) => {
-                    total_deleted += 1;
-                    results.push(BranchDeleteResult {
-                        name: name.clone(),
-                        deleted: true,
-                        error: None,
-                    });
-                }
-                Err(e) => {
-                    total_failed += 1;
-                    results.push(BranchDeleteResult {
-                        name: name.clone(),
-                        deleted: false,
-                        error: Some(e),
-                    });
+                    branch.delete().map_err(|e| e.message().to_string())?;
+                    Ok(())
+                })();
+
+                match result {
+                    Ok(()) => {
+                        total_deleted += 1;
+                        results.push(BranchDeleteResult {
+                            name: name.clone(),
+                            deleted: true,
+                            error: None,
+                        });
+                    }
+                    Err(e) => {
+                        total_failed += 1;
+                        results.push(BranchDeleteResult {
+                            name: name.clone(),
+                            deleted: false,
+                            error: Some(e),
+                        });
+                    }
                 }
             }
-        }
 
-        Ok(BatchDeleteResult {
-            results,
-            total_deleted,
-            total_failed,
-        })
-    })
+            Ok(BatchDeleteResult {
+                results,
+                total_deleted,
+                total_failed,
+            })
+        },
+    )
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// Get ahead/behind counts for a local branch relative to its upstream.
@@ -620,36 +809,534 @@ pub async fn get_branch_ahead_behind(
 
     tokio::task::spawn_blocking(move || {
         let repo = git2::Repository::open(&repo_path)?;
+        branch_ahead_behind(&repo, &branch_name)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
 
-        let local_branch = repo
-            .find_branch(&branch_name, git2::BranchType::Local)
-            .map_err(|_| GitError::BranchNotFound(branch_name.clone()))?;
+/// Ahead/behind counts of a local branch against its upstream (0/0 without one).
+pub(crate) fn branch_ahead_behind(
+    repo: &git2::Repository,
+    branch_name: &str,
+) -> Result<AheadBehind, GitError> {
+    let local_branch = repo
+        .find_branch(branch_name, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(branch_name.to_string()))?;
+
+    let local_oid = local_branch
+        .get()
+        .peel_to_commit()
+        .map(|c| c.id())
+        .map_err(|e| GitError::OperationFailed(e.message().to_string()))?;
+
+    // Try to get the upstream; if none exists, return 0/0
+    let upstream = match local_branch.upstream() {
+        Ok(up) => up,
+        Err(_) => {
+            return Ok(AheadBehind {
+                ahead: 0,
+                behind: 0,
+            });
+        }
+    };
 
-        let local_oid = local_branch
-            .get()
-            .peel_to_commit()
-            .map(|c| c.id())
-            .map_err(|e| GitError::OperationFailed(e.message().to_string()))?;
+    let upstream_oid = upstream
+        .get()
+        .peel_to_commit()
+        .map(|c| c.id())
+        .map_err(|e| GitError::OperationFailed(e.message().to_string()))?;
 
-        // Try to get the upstream; if none exists, return 0/0
-        let upstream = match local_branch.upstream() {
-            Ok(up) => up,
-            Err(_) => return Ok(AheadBehind { ahead: 0, behind: 0 }),
+    let (ahead, behind) = repo.graph_ahead_behind(local_oid, upstream_oid)?;
+
+    Ok(AheadBehind {
+        ahead: ahead as u32,
+        behind: behind as u32,
+    })
+}
+
+/// Local and remote-tracking branches whose history contains a commit.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchesContaining {
+    /// Local branch names, alphabetically
+    pub local: Vec<String>,
+    /// Remote branch names (e.g., "origin/main"), alphabetically
+    pub remote: Vec<String>,
+}
+
+/// Rename a local branch (`git branch -m`).
+///
+/// libgit2 moves the branch's reflog and its `branch.<name>.*` config
+/// section and repoints HEAD if the branch is checked out. Other local
+/// branches tracking the renamed one are repointed here.
+pub fn rename_branch_internal(
+    repo: &git2::Repository,
+    old_name: &str,
+    new_name: &str,
+    force: bool,
+) -> Result<BranchInfo, GitError> {
+    if !git2::Branch::name_is_valid(new_name)? {
+        return Err(GitError::InvalidBranchName(new_name.to_string()));
+    }
+    let mut branch = repo
+        .find_branch(old_name, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(old_name.to_string()))?;
+    if !force && repo.find_branch(new_name, git2::BranchType::Local).is_ok() {
+        return Err(GitError::BranchAlreadyExists(new_name.to_string()));
+    }
+
+    let renamed = branch.rename(new_name, force)?;
+
+    // Local branches tracking this one have `remote = .` and `merge = refs/heads/<old>`
+    let mut config = repo.config()?;
+    let old_ref = format!("refs/heads/{}", old_name);
+    let mut tracking = Vec::new();
+    {
+        let snapshot = config.snapshot()?;
+        let mut entries = snapshot.entries(Some(r"^branch\..*\.merge$"))?;
+        while let Some(entry) = entries.next() {
+            let entry = entry?;
+            if let (Some(key), Some(value)) = (entry.name(), entry.value())
+                && value == old_ref
+                && let Some(branch) = key
+                    .strip_prefix("branch.")
+                    .and_then(|k| k.strip_suffix(".merge"))
+                && snapshot
+                    .get_string(&format!("branch.{}.remote", branch))
+                    .is_ok_and(|remote| remote == ".")
+            {
+                tracking.push(key.to_string());
+            }
+        }
+    }
+    for key in tracking {
+        config.set_str(&key, &format!("refs/heads/{}", new_name))?;
+    }
+
+    let commit = renamed.get().peel_to_commit()?;
+    let is_head = renamed.is_head();
+    let is_merged = if is_head {
+        None
+    } else {
+        match repo.head() {
+            Ok(head) => {
+                let head_oid = head.peel_to_commit()?.id();
+                Some(repo.merge_base(head_oid, commit.id())? == commit.id())
+            }
+            Err(_) => Some(false),
+        }
+    };
+    Ok(BranchInfo {
+        name: new_name.to_string(),
+        is_head,
+        last_commit_oid: format!("{:.7}", commit.id()),
+        last_commit_message: commit.summary().unwrap_or("").to_string(),
+        is_merged,
+        is_remote: false,
+        remote_name: None,
+        description: branch_description(&config.snapshot()?, new_name),
+    })
+}
+
+/// Set (`Some`) or unset (`None`) the upstream of a local branch.
+///
+/// `upstream` is a remote-tracking branch ("origin/main") or another local
+/// branch. Unsetting a branch without an upstream is a no-op.
+pub fn set_branch_upstream_internal(
+    repo: &git2::Repository,
+    branch_name: &str,
+    upstream: Option<&str>,
+) -> Result<(), GitError> {
+    let mut branch = repo
+        .find_branch(branch_name, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(branch_name.to_string()))?;
+
+    match upstream {
+        Some(upstream) => {
+            if repo
+                .find_branch(upstream, git2::BranchType::Remote)
+                .is_err()
+                && repo.find_branch(upstream, git2::BranchType::Local).is_err()
+            {
+                return Err(GitError::BranchNotFound(upstream.to_string()));
+            }
+            branch.set_upstream(Some(upstream))?;
+        }
+        None => {
+            if branch.upstream().is_ok() {
+                branch.set_upstream(None)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Set or clear `branch.<name>.description`; a blank description clears it.
+pub fn set_branch_description_internal(
+    repo: &git2::Repository,
+    branch_name: &str,
+    description: Option<&str>,
+) -> Result<(), GitError> {
+    repo.find_branch(branch_name, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(branch_name.to_string()))?;
+
+    let mut config = repo.config()?;
+    let key = format!("branch.{}.description", branch_name);
+    match description.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(description) => config.set_str(&key, description)?,
+        None => match config.remove(&key) {
+            Err(e) if e.code() != git2::ErrorCode::NotFound => return Err(e.into()),
+            _ => {}
+        },
+    }
+    Ok(())
+}
+
+/// Branches whose tip is `oid` or a descendant of it (`git branch -a --contains`).
+pub fn branches_containing_internal(
+    repo: &git2::Repository,
+    oid: git2::Oid,
+) -> Result<BranchesContaining, GitError> {
+    let mut local = Vec::new();
+    let mut remote = Vec::new();
+
+    for branch_result in repo.branches(None)? {
+        let (branch, branch_type) = branch_result?;
+        let name = match branch.name()? {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        // Skip symbolic refs such as "origin/HEAD"
+        if branch.get().kind() != Some(git2::ReferenceType::Direct) {
+            continue;
+        }
+        let tip = match branch.get().peel_to_commit() {
+            Ok(c) => c.id(),
+            Err(_) => continue,
         };
+        if tip != oid && !repo.graph_descendant_of(tip, oid)? {
+            continue;
+        }
+        match branch_type {
+            git2::BranchType::Local => local.push(name),
+            git2::BranchType::Remote => remote.push(name),
+        }
+    }
 
-        let upstream_oid = upstream
-            .get()
-            .peel_to_commit()
-            .map(|c| c.id())
-            .map_err(|e| GitError::OperationFailed(e.message().to_string()))?;
+    local.sort();
+    remote.sort();
+    Ok(BranchesContaining { local, remote })
+}
 
-        let (ahead, behind) = repo.graph_ahead_behind(local_oid, upstream_oid)?;
+/// Rename a local branch, carrying its upstream, description and reflog.
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_branch(
+    old_name: String,
+    new_name: String,
+    force: Option<bool>,
+    state: State<'_, RepositoryState>,
+) -> Result<BranchInfo, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-        Ok(AheadBehind {
-            ahead: ahead as u32,
-            behind: behind as u32,
-        })
+    record_operation(
+        repo_path.clone(),
+        format!("Rename branch '{}' to '{}'", old_name, new_name),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            rename_branch_internal(&repo, &old_name, &new_name, force.unwrap_or(false))
+        },
+    )
+    .await
+}
+
+/// Track `upstream` (e.g., "origin/main") from a local branch.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_branch_upstream(
+    branch_name: String,
+    upstream: String,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        set_branch_upstream_internal(&repo, &branch_name, Some(&upstream))
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
+
+/// Stop tracking an upstream from a local branch.
+#[tauri::command]
+#[specta::specta]
+pub async fn unset_branch_upstream(
+    branch_name: String,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        set_branch_upstream_internal(&repo, &branch_name, None)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Set or clear a local branch's description.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_branch_description(
+    branch_name: String,
+    description: Option<String>,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        set_branch_description_internal(&repo, &branch_name, description.as_deref())
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// List local and remote branches that contain a commit.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_branches_containing(
+    oid: String,
+    state: State<'_, RepositoryState>,
+) -> Result<BranchesContaining, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        let oid = git2::Oid::from_str(&oid)
+            .map_err(|e| GitError::OperationFailed(format!("Invalid OID '{}': {}", oid, e)))?;
+        branches_containing_internal(&repo, oid)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_file, commit_index, setup_repo};
+
+    #[test]
+    fn test_rename_carries_upstream_description_and_reflog() {
+        let (_dir, repo) = setup_repo();
+        let first = commit_index(&repo, "One");
+        let head_name = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/feature", first, false, "fetch")
+            .unwrap();
+        repo.branch("feature", &repo.find_commit(first).unwrap(), false)
+            .unwrap();
+        set_branch_upstream_internal(&repo, "feature", Some("origin/feature")).unwrap();
+        set_branch_description_internal(&repo, "feature", Some("  Login work \n")).unwrap();
+        // A local branch stacked on top of the one being renamed
+        repo.branch("stacked", &repo.find_commit(first).unwrap(), false)
+            .unwrap();
+        set_branch_upstream_internal(&repo, "stacked", Some("feature")).unwrap();
+
+        assert!(matches!(
+            rename_branch_internal(&repo, "feature", &head_name, false),
+            Err(GitError::BranchAlreadyExists(_))
+        ));
+        assert!(matches!(
+            rename_branch_internal(&repo, "feature", "bad..name", false),
+            Err(GitError::InvalidBranchName(_))
+        ));
+
+        let info = rename_branch_internal(&repo, "feature", "login", false).unwrap();
+        assert_eq!(info.description.as_deref(), Some("Login work"));
+        assert!(
+            repo.find_branch("feature", git2::BranchType::Local)
+                .is_err()
+        );
+        let login = repo.find_branch("login", git2::BranchType::Local).unwrap();
+        assert_eq!(
+            login.upstream().unwrap().name().unwrap(),
+            Some("origin/feature")
+        );
+        let reflog = repo.reflog("refs/heads/login").unwrap();
+        assert!(reflog.len() >= 2);
+        let stacked = repo
+            .find_branch("stacked", git2::BranchType::Local)
+            .unwrap();
+        assert_eq!(stacked.upstream().unwrap().name().unwrap(), Some("login"));
+
+        // Renaming the checked-out branch moves HEAD with it
+        rename_branch_internal(&repo, &head_name, "trunk", false).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("trunk"));
+    }
+
+    #[test]
+    fn test_upstream_and_description_can_be_cleared() {
+        let (_dir, repo) = setup_repo();
+        let oid = commit_index(&repo, "One");
+        let head_name = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/main", oid, false, "fetch")
+            .unwrap();
+
+        assert!(matches!(
+            set_branch_upstream_internal(&repo, &head_name, Some("origin/missing")),
+            Err(GitError::BranchNotFound(_))
+        ));
+        set_branch_upstream_internal(&repo, &head_name, Some("origin/main")).unwrap();
+        let branch = repo
+            .find_branch(&head_name, git2::BranchType::Local)
+            .unwrap();
+        assert!(branch.upstream().is_ok());
+        set_branch_upstream_internal(&repo, &head_name, None).unwrap();
+        let branch = repo
+            .find_branch(&head_name, git2::BranchType::Local)
+            .unwrap();
+        assert!(branch.upstream().is_err());
+        // Unsetting twice is fine
+        set_branch_upstream_internal(&repo, &head_name, None).unwrap();
+
+        set_branch_description_internal(&repo, &head_name, Some("Main line")).unwrap();
+        set_branch_description_internal(&repo, &head_name, Some("   ")).unwrap();
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(branch_description(&config, &head_name), None);
+        set_branch_description_internal(&repo, &head_name, None).unwrap();
+    }
+
+    #[test]
+    fn test_branches_containing_commit() {
+        let (_dir, repo) = setup_repo();
+        let first = commit_index(&repo, "One");
+        let head_name = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.branch("old", &repo.find_commit(first).unwrap(), false)
+            .unwrap();
+        let second = commit_index(&repo, "Two");
+        repo.reference("refs/remotes/origin/main", second, false, "fetch")
+            .unwrap();
+        repo.reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/main",
+            false,
+            "clone",
+        )
+        .unwrap();
+
+        let containing = branches_containing_internal(&repo, first).unwrap();
+        let mut expected = vec![head_name.clone(), "old".to_string()];
+        expected.sort();
+        assert_eq!(containing.local, expected);
+        assert_eq!(containing.remote, vec!["origin/main"]);
+
+        let containing = branches_containing_internal(&repo, second).unwrap();
+        assert_eq!(containing.local, vec![head_name]);
+        assert_eq!(containing.remote, vec!["origin/main"]);
+    }
+
+    /// HEAD branch with a.txt "1 2 3 4 5" and `other` changing line 1;
+    /// returns the HEAD branch name.
+    fn setup_diverged(repo: &git2::Repository) -> String {
+        commit_file(repo, "a.txt", "1\n2\n3\n4\n5\n", "Base");
+        let head_name = repo.head().unwrap().shorthand().unwrap().to_string();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("other", &base, false).unwrap();
+        repo.set_head("refs/heads/other").unwrap();
+        commit_file(repo, "a.txt", "one\n2\n3\n4\n5\n", "Other");
+        repo.set_head(&format!("refs/heads/{}", head_name)).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        head_name
+    }
+
+    fn read(repo: &git2::Repository, path: &str) -> String {
+        std::fs::read_to_string(repo.workdir().unwrap().join(path)).unwrap()
+    }
+
+    #[test]
+    fn test_checkout_preview_and_safe_mode_keep_head() {
+        let (dir, _) = setup_repo();
+        let mut repo = git2::Repository::open(dir.path()).unwrap();
+        let head_name = setup_diverged(&repo);
+        std::fs::write(dir.path().join("a.txt"), "1\n2\n3\n4\nfive\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "scratch").unwrap();
+
+        let preview = checkout_branch_internal(&mut repo, "other", CheckoutMode::Preview).unwrap();
+        assert!(!preview.switched);
+        assert_eq!(preview.conflicts, vec!["a.txt"]);
+        assert_eq!(preview.dirty_files, vec!["a.txt", "notes.txt"]);
+
+        assert!(matches!(
+            checkout_branch_internal(&mut repo, "other", CheckoutMode::Safe),
+            Err(GitError::DirtyWorkingDirectory)
+        ));
+        assert_eq!(repo.head().unwrap().shorthand(), Some(head_name.as_str()));
+        assert_eq!(read(&repo, "a.txt"), "1\n2\n3\n4\nfive\n");
+
+        // Changes the target does not touch carry over
+        std::fs::write(dir.path().join("a.txt"), "1\n2\n3\n4\n5\n").unwrap();
+        let result = checkout_branch_internal(&mut repo, "other", CheckoutMode::Safe).unwrap();
+        assert!(result.switched && !result.autostashed);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("other"));
+        assert_eq!(read(&repo, "notes.txt"), "scratch");
+    }
+
+    #[test]
+    fn test_checkout_autostash_reapplies_changes() {
+        let (dir, _) = setup_repo();
+        let mut repo = git2::Repository::open(dir.path()).unwrap();
+        setup_diverged(&repo);
+        std::fs::write(dir.path().join("a.txt"), "1\n2\n3\n4\nfive\n").unwrap();
+
+        let result = checkout_branch_internal(&mut repo, "other", CheckoutMode::Autostash).unwrap();
+        assert!(result.switched && result.autostashed);
+        assert_eq!(result.conflicts, vec!["a.txt"]);
+        assert!(result.reapply_conflicts.is_empty());
+        assert_eq!(repo.head().unwrap().shorthand(), Some("other"));
+        assert_eq!(read(&repo, "a.txt"), "one\n2\n3\n4\nfive\n");
+        assert!(repo.find_reference("refs/stash").is_err());
+    }
+
+    #[test]
+    fn test_checkout_autostash_keeps_stash_on_conflict() {
+        let (dir, _) = setup_repo();
+        let mut repo = git2::Repository::open(dir.path()).unwrap();
+        setup_diverged(&repo);
+        std::fs::write(dir.path().join("a.txt"), "uno\n2\n3\n4\n5\n").unwrap();
+
+        let result = checkout_branch_internal(&mut repo, "other", CheckoutMode::Autostash).unwrap();
+        assert!(result.switched && result.autostashed);
+        assert_eq!(result.reapply_conflicts, vec!["a.txt"]);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("other"));
+        let mut stashes = 0;
+        repo.stash_foreach(|_, message, _| {
+            assert!(message.contains("autostash before checkout of other"));
+            stashes += 1;
+            true
+        })
+        .unwrap();
+        assert_eq!(stashes, 1);
+    }
+}
@@ -0,0 +1,158 @@
+//! Cancellation of long-running network operations.
+//!
+//! Clone, fetch, pull, push and submodule update commands accept an
+//! optional `operation_id`. While the operation runs its id is registered
+//! here with a [`CancellationToken`], which the libgit2 progress callbacks
+//! poll; `cancel_operation` trips the token and the operation stops at the
+//! next callback with [`GitError::Cancelled`].
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+use crate::git::error::GitError;
+
+/// Shared flag checked by progress callbacks.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Return `Cancelled` if the token was tripped.
+    pub fn check(&self) -> Result<(), GitError> {
+        if self.is_cancelled() {
+            Err(GitError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Running operations by id - one registry per app instance
+pub struct OperationRegistry {
+    operations: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl OperationRegistry {
+    pub fn new() -> Self {
+        Self {
+            operations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register an operation and return a guard that unregisters it on drop.
+    ///
+    /// Without an id the operation simply cannot be cancelled.
+    pub fn register(&self, id: Option<String>) -> Result<OperationGuard<'_>, GitError> {
+        let token = CancellationToken::new();
+        if let Some(id) = &id {
+            let mut operations = self.lock()?;
+            if operations.contains_key(id) {
+                return Err(GitError::OperationInProgress(id.clone()));
+            }
+            operations.insert(id.clone(), token.clone());
+        }
+        Ok(OperationGuard {
+            registry: self,
+            id,
+            token,
+        })
+    }
+
+    /// Cancel a running operation. Returns false if no such operation is running.
+    pub fn cancel(&self, id: &str) -> Result<bool, GitError> {
+        Ok(match self.lock()?.get(id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        })
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, HashMap<String, CancellationToken>>, GitError> {
+        self.operations
+            .lock()
+            .map_err(|_| GitError::Internal("Operation registry poisoned".to_string()))
+    }
+}
+
+impl Default for OperationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps an operation registered for as long as it runs.
+pub struct OperationGuard<'a> {
+    registry: &'a OperationRegistry,
+    id: Option<String>,
+    token: CancellationToken,
+}
+
+impl OperationGuard<'_> {
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for OperationGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id
+            && let Ok(mut operations) = self.registry.operations.lock()
+        {
+            operations.remove(id);
+        }
+    }
+}
+
+/// Cancel a running clone, fetch, pull, push or submodule update by its id.
+///
+/// Returns false if the operation already finished.
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_operation(
+    id: String,
+    registry: State<'_, OperationRegistry>,
+) -> Result<bool, GitError> {
+    registry.cancel(&id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_cancels_running_operations_only() {
+        let registry = OperationRegistry::new();
+
+        let guard = registry.register(Some("clone-1".to_string())).unwrap();
+        assert!(matches!(
+            registry.register(Some("clone-1".to_string())),
+            Err(GitError::OperationInProgress(_))
+        ));
+        assert!(!guard.token().is_cancelled());
+
+        assert!(registry.cancel("clone-1").unwrap());
+        assert!(guard.token().is_cancelled());
+        assert!(matches!(guard.token().check(), Err(GitError::Cancelled)));
+
+        drop(guard);
+        assert!(!registry.cancel("clone-1").unwrap());
+        assert!(registry.register(Some("clone-1".to_string())).is_ok());
+    }
+}
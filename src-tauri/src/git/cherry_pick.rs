@@ -0,0 +1,907 @@
+//! Cherry-pick and revert operations.
+//!
+//! Both apply (or undo) the change introduced by a commit on top of HEAD.
+//! On conflicts the repository is left in git's `CHERRY_PICK_HEAD` /
+//! `REVERT_HEAD` state, so the conflict commands can resolve files before
+//! the operation is continued. Multi-commit cherry-picks persist the
+//! remaining commits in git's `sequencer` directory.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+use crate::git::conflict::collect_conflicted_paths;
+use crate::git::error::GitError;
+use crate::git::rebase::{committer_signature, has_uncommitted_changes};
+use crate::git::repository::RepositoryState;
+use crate::git::signing::create_commit_signed;
+use crate::git::undo::record_operation;
+
+/// Directory (inside `.git`) holding the remaining commits of a multi-commit cherry-pick.
+pub(crate) const SEQUENCER_DIR: &str = "sequencer";
+
+/// Result of a cherry-pick operation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CherryPickResult {
+    /// Whether every requested commit was applied
+    pub success: bool,
+    /// OIDs of the commits created, in order
+    pub created_oids: Vec<String>,
+    /// OIDs of commits skipped because their changes were already present
+    pub skipped_oids: Vec<String>,
+    /// True if the operation stopped on conflicts
+    pub has_conflicts: bool,
+    /// List of conflicted file paths
+    pub conflicted_files: Vec<String>,
+    /// OID of the commit that could not be applied cleanly
+    pub stopped_oid: Option<String>,
+    /// Number of commits still waiting to be applied after the stopped one
+    pub remaining: u32,
+}
+
+impl CherryPickResult {
+    fn empty() -> Self {
+        Self {
+            success: false,
+            created_oids: vec![],
+            skipped_oids: vec![],
+            has_conflicts: false,
+            conflicted_files: vec![],
+            stopped_oid: None,
+            remaining: 0,
+        }
+    }
+}
+
+/// Result of a revert operation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertResult {
+    /// Whether the revert commit was created
+    pub success: bool,
+    /// OID of the revert commit (if created)
+    pub commit_oid: Option<String>,
+    /// True if conflicts remain
+    pub has_conflicts: bool,
+    /// List of conflicted file paths
+    pub conflicted_files: Vec<String>,
+}
+
+/// Which of the two operations is being applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PickKind {
+    CherryPick,
+    Revert,
+}
+
+impl PickKind {
+    /// State file git writes while the operation is stopped on conflicts.
+    fn head_file(&self) -> &'static str {
+        match self {
+            PickKind::CherryPick => "CHERRY_PICK_HEAD",
+            PickKind::Revert => "REVERT_HEAD",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PickKind::CherryPick => "cherry-pick",
+            PickKind::Revert => "revert",
+        }
+    }
+}
+
+/// Options shared by every commit of a cherry-pick sequence.
+#[derive(Debug, Clone, Copy, Default)]
+struct PickOptions {
+    /// Append a "(cherry picked from commit ...)" line (`-x`)
+    record_origin: bool,
+    /// 1-based parent to diff against for merge commits (`-m`)
+    mainline: Option<u32>,
+}
+
+/// Outcome of applying a single commit.
+enum StepOutcome {
+    Committed(git2::Oid),
+    Empty,
+    Conflicts(Vec<String>),
+}
+
+/// Mainline argument for git2: 0 for regular commits, the parent number for merges.
+fn mainline_for(commit: &git2::Commit, mainline: Option<u32>) -> Result<u32, GitError> {
+    if commit.parent_count() <= 1 {
+        return Ok(0);
+    }
+
+    match mainline {
+        Some(m) if m >= 1 && (m as usize) <= commit.parent_count() => Ok(m),
+        _ => Err(GitError::MainlineRequired(format!("{:.7}", commit.id()))),
+    }
+}
+
+/// Build the commit message for a cherry-picked or reverted commit.
+fn pick_message(kind: PickKind, commit: &git2::Commit, opts: PickOptions, mainline: u32) -> String {
+    match kind {
+        PickKind::CherryPick => {
+            let message = commit.message().unwrap_or("").trim_end().to_string();
+            if opts.record_origin {
+                format!(
+                    "{}\n\n(cherry picked from commit {})\n",
+                    message,
+                    commit.id()
+                )
+            } else {
+                format!("{}\n", message)
+            }
+        }
+        PickKind::Revert => {
+            let mut message = format!(
+                "Revert \"{}\"\n\nThis reverts commit {}",
+                commit.summary().unwrap_or(""),
+                commit.id()
+            );
+            let parent = (mainline > 0)
+                .then(|| commit.parent_id(mainline as usize - 1).ok())
+                .flatten();
+            if let Some(parent) = parent {
+                message.push_str(&format!(", reversing\nchanges made to {}", parent));
+            }
+            message.push_str(".\n");
+            message
+        }
+    }
+}
+
+/// Strip the comment lines git and libgit2 append to `MERGE_MSG`.
+pub(crate) fn clean_message(raw: &str) -> String {
+    let lines: Vec<&str> = raw.lines().filter(|l| !l.starts_with('#')).collect();
+    format!("{}\n", lines.join("\n").trim_end())
+}
+
+fn remove_state_file(repo: &git2::Repository, name: &str) {
+    let _ = fs::remove_file(repo.path().join(name));
+}
+
+fn write_git_file(path: &Path, content: &str) -> Result<(), GitError> {
+    fs::write(path, content).map_err(|e| {
+        GitError::OperationFailed(format!("Failed to write {}: {}", path.display(), e))
+    })
+}
+
+/// Apply (or revert) a single commit on top of HEAD and commit the result.
+///
+/// On conflicts, writes conflict markers to the working tree and leaves the
+/// repository in the matching git state with the pending message in `MERGE_MSG`.
+fn apply_commit(
+    repo: &git2::Repository,
+    kind: PickKind,
+    commit: &git2::Commit,
+    opts: PickOptions,
+) -> Result<StepOutcome, GitError> {
+    let head = match repo.head() {
+        Ok(head) => head.peel_to_commit()?,
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+            return Err(GitError::EmptyRepository);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mainline = mainline_for(commit, opts.mainline)?;
+    let message = pick_message(kind, commit, opts, mainline);
+
+    let mut index = match kind {
+        PickKind::CherryPick => repo.cherrypick_commit(commit, &head, mainline, None)?,
+        PickKind::Revert => repo.revert_commit(commit, &head, mainline, None)?,
+    };
+
+    if index.has_conflicts() {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout
+            .safe()
+            .allow_conflicts(true)
+            .conflict_style_merge(true);
+        repo.checkout_index(Some(&mut index), Some(&mut checkout))?;
+
+        write_git_file(
+            &repo.path().join(kind.head_file()),
+            &format!("{}\n", commit.id()),
+        )?;
+        write_git_file(&repo.path().join("MERGE_MSG"), &message)?;
+
+        return Ok(StepOutcome::Conflicts(collect_conflicted_paths(
+            &repo.index()?,
+        )?));
+    }
+
+    let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+    if tree.id() == head.tree_id() {
+        return Ok(StepOutcome::Empty);
+    }
+
+    repo.checkout_tree(
+        tree.as_object(),
+        Some(git2::build::CheckoutBuilder::new().safe()),
+    )?;
+    let oid = commit_pick(repo, kind, commit, &head, &tree, &message)?;
+
+    Ok(StepOutcome::Committed(oid))
+}
+
+/// Create the commit for a picked change on the current branch.
+///
+/// Cherry-picks keep the original author; reverts are authored by the user.
+fn commit_pick(
+    repo: &git2::Repository,
+    kind: PickKind,
+    original: &git2::Commit,
+    head: &git2::Commit,
+    tree: &git2::Tree,
+    message: &str,
+) -> Result<git2::Oid, GitError> {
+    let committer = committer_signature(repo)?;
+    let author = match kind {
+        PickKind::CherryPick => original.author().to_owned(),
+        PickKind::Revert => committer.clone(),
+    };
+
+    create_commit_signed(
+        repo,
+        Some("HEAD"),
+        &author,
+        &committer,
+        message,
+        tree,
+        &[head],
+    )
+}
+
+/// Expand revision arguments into commit OIDs, oldest first for ranges.
+///
+/// Each entry may be a single revision (`abc1234`, `main~2`) or a range (`A..B`).
+fn resolve_revisions(
+    repo: &git2::Repository,
+    revisions: &[String],
+) -> Result<Vec<git2::Oid>, GitError> {
+    let mut oids = Vec::new();
+
+    for spec in revisions {
+        let revspec = repo
+            .revparse(spec)
+            .map_err(|_| GitError::NotFound(format!("Revision not found: {}", spec)))?;
+
+        if revspec.mode().contains(git2::RevparseMode::RANGE) {
+            let (Some(from), Some(to)) = (revspec.from(), revspec.to()) else {
+                return Err(GitError::NotFound(format!("Invalid range: {}", spec)));
+            };
+
+            let mut revwalk = repo.revwalk()?;
+            revwalk.push(to.peel_to_commit()?.id())?;
+            revwalk.hide(from.peel_to_commit()?.id())?;
+            revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+            for oid in revwalk {
+                oids.push(oid?);
+            }
+        } else {
+            let object = revspec
+                .from()
+                .ok_or_else(|| GitError::NotFound(format!("Revision not found: {}", spec)))?;
+            oids.push(object.peel_to_commit()?.id());
+        }
+    }
+
+    Ok(oids)
+}
+
+/// Persist the commits still to be picked after a conflict.
+fn write_sequencer(
+    repo: &git2::Repository,
+    orig_head: git2::Oid,
+    remaining: &[git2::Oid],
+    opts: PickOptions,
+) -> Result<(), GitError> {
+    let dir = repo.path().join(SEQUENCER_DIR);
+    fs::create_dir_all(&dir).map_err(|e| {
+        GitError::OperationFailed(format!("Failed to create sequencer state: {}", e))
+    })?;
+
+    let mut todo = String::new();
+    for oid in remaining {
+        let summary = repo
+            .find_commit(*oid)
+            .ok()
+            .and_then(|c| c.summary().map(|s| s.to_string()))
+            .unwrap_or_default();
+        todo.push_str(&format!("pick {} {}\n", oid, summary));
+    }
+
+    let mut options = format!("[options]\n\trecord-origin = {}\n", opts.record_origin);
+    if let Some(mainline) = opts.mainline {
+        options.push_str(&format!("\tmainline = {}\n", mainline));
+    }
+
+    write_git_file(&dir.join("head"), &format!("{}\n", orig_head))?;
+    write_git_file(&dir.join("todo"), &todo)?;
+    write_git_file(&dir.join("opts"), &options)?;
+
+    Ok(())
+}
+
+/// Read the sequencer state: original HEAD, remaining commits and options.
+fn read_sequencer(
+    repo: &git2::Repository,
+) -> Result<Option<(git2::Oid, Vec<git2::Oid>, PickOptions)>, GitError> {
+    let dir = repo.path().join(SEQUENCER_DIR);
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+
+    let head = fs::read_to_string(dir.join("head")).unwrap_or_default();
+    let orig_head = git2::Oid::from_str(head.trim())
+        .map_err(|e| GitError::Internal(format!("Invalid sequencer head: {}", e)))?;
+
+    let mut remaining = Vec::new();
+    for line in fs::read_to_string(dir.join("todo"))
+        .unwrap_or_default()
+        .lines()
+    {
+        let mut parts = line.split_whitespace();
+        if let (Some("pick" | "p"), Some(rev)) = (parts.next(), parts.next()) {
+            remaining.push(repo.revparse_single(rev)?.peel_to_commit()?.id());
+        }
+    }
+
+    let mut opts = PickOptions::default();
+    for line in fs::read_to_string(dir.join("opts"))
+        .unwrap_or_default()
+        .lines()
+    {
+        if let Some((key, value)) = line.split_once('=') {
+            match key.trim() {
+                "record-origin" => opts.record_origin = value.trim() == "true",
+                "mainline" => opts.mainline = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Some((orig_head, remaining, opts)))
+}
+
+fn remove_sequencer(repo: &git2::Repository) -> Result<(), GitError> {
+    let dir = repo.path().join(SEQUENCER_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| {
+            GitError::OperationFailed(format!("Failed to remove sequencer state: {}", e))
+        })?;
+    }
+    Ok(())
+}
+
+/// Cherry-pick `oids` in order, stopping (and saving the rest) on the first conflict.
+///
+/// When `sequence` is set the picks belong to a multi-commit cherry-pick, and
+/// the original HEAD is saved on a conflict even if nothing is left to pick,
+/// so an abort can undo the commits the sequence already created.
+fn run_pick_sequence(
+    repo: &git2::Repository,
+    orig_head: git2::Oid,
+    oids: &[git2::Oid],
+    opts: PickOptions,
+    sequence: bool,
+    mut result: CherryPickResult,
+) -> Result<CherryPickResult, GitError> {
+    for (i, oid) in oids.iter().enumerate() {
+        let commit = repo.find_commit(*oid)?;
+
+        match apply_commit(repo, PickKind::CherryPick, &commit, opts)? {
+            StepOutcome::Committed(new_oid) => result.created_oids.push(new_oid.to_string()),
+            StepOutcome::Empty => result.skipped_oids.push(oid.to_string()),
+            StepOutcome::Conflicts(conflicted_files) => {
+                let remaining = &oids[i + 1..];
+                if sequence || !remaining.is_empty() {
+                    write_sequencer(repo, orig_head, remaining, opts)?;
+                }
+
+                result.success = false;
+                result.has_conflicts = true;
+                result.conflicted_files = conflicted_files;
+                result.stopped_oid = Some(oid.to_string());
+                result.remaining = remaining.len() as u32;
+                return Ok(result);
+            }
+        }
+    }
+
+    result.success = true;
+    Ok(result)
+}
+
+pub fn cherry_pick_internal(
+    repo: &git2::Repository,
+    revisions: &[String],
+    record_origin: bool,
+    mainline: Option<u32>,
+) -> Result<CherryPickResult, GitError> {
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(GitError::OperationInProgress(format!("{:?}", repo.state())));
+    }
+    if has_uncommitted_changes(repo)? {
+        return Err(GitError::DirtyWorkingDirectory);
+    }
+
+    let oids = resolve_revisions(repo, revisions)?;
+    let orig_head = repo
+        .head()
+        .map_err(|_| GitError::EmptyRepository)?
+        .peel_to_commit()?
+        .id();
+
+    let opts = PickOptions {
+        record_origin,
+        mainline,
+    };
+
+    run_pick_sequence(
+        repo,
+        orig_head,
+        &oids,
+        opts,
+        oids.len() > 1,
+        CherryPickResult::empty(),
+    )
+}
+
+/// Commit the resolved stopped commit of a cherry-pick or revert.
+///
+/// Returns the new commit OID, or None if the resolution left nothing to commit.
+fn commit_resolution(
+    repo: &git2::Repository,
+    kind: PickKind,
+) -> Result<Option<git2::Oid>, GitError> {
+    let head_file = repo.path().join(kind.head_file());
+    let stopped = fs::read_to_string(&head_file)
+        .map_err(|_| GitError::NoOperationInProgress(kind.name().to_string()))?;
+
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        let files = collect_conflicted_paths(&index)?;
+        return Err(GitError::UnresolvedConflicts(files.join(", ")));
+    }
+
+    let original = repo.revparse_single(stopped.trim())?.peel_to_commit()?;
+    let message = fs::read_to_string(repo.path().join("MERGE_MSG"))
+        .map(|m| clean_message(&m))
+        .unwrap_or_else(|_| original.message().unwrap_or("").to_string());
+
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let oid = if tree.id() != head.tree_id() {
+        Some(commit_pick(repo, kind, &original, &head, &tree, &message)?)
+    } else {
+        None
+    };
+
+    remove_state_file(repo, kind.head_file());
+    remove_state_file(repo, "MERGE_MSG");
+
+    Ok(oid)
+}
+
+pub fn continue_cherry_pick_internal(
+    repo: &git2::Repository,
+) -> Result<CherryPickResult, GitError> {
+    let has_pick_head = repo.path().join("CHERRY_PICK_HEAD").exists();
+    let sequence = read_sequencer(repo)?;
+    if !has_pick_head && sequence.is_none() {
+        return Err(GitError::NoOperationInProgress("cherry-pick".to_string()));
+    }
+
+    let mut result = CherryPickResult::empty();
+
+    let resolved = if has_pick_head {
+        commit_resolution(repo, PickKind::CherryPick)?
+    } else {
+        None
+    };
+    if let Some(oid) = resolved {
+        result.created_oids.push(oid.to_string());
+    }
+
+    match sequence {
+        Some((orig_head, remaining, opts)) => {
+            remove_sequencer(repo)?;
+            run_pick_sequence(repo, orig_head, &remaining, opts, true, result)
+        }
+        None => {
+            result.success = true;
+            Ok(result)
+        }
+    }
+}
+
+/// Drop the stopped commit and apply any remaining commits of the sequence.
+pub fn skip_cherry_pick_internal(repo: &git2::Repository) -> Result<CherryPickResult, GitError> {
+    let stopped = fs::read_to_string(repo.path().join("CHERRY_PICK_HEAD"))
+        .map_err(|_| GitError::NoOperationInProgress("cherry-pick".to_string()))?;
+    let sequence = read_sequencer(repo)?;
+
+    // A hard reset also removes the state files, so the sequence is read first
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), git2::ResetType::Hard, None)?;
+    remove_state_file(repo, "CHERRY_PICK_HEAD");
+    remove_state_file(repo, "MERGE_MSG");
+    remove_sequencer(repo)?;
+
+    let mut result = CherryPickResult::empty();
+    result.skipped_oids.push(stopped.trim().to_string());
+
+    match sequence {
+        Some((orig_head, remaining, opts)) => {
+            run_pick_sequence(repo, orig_head, &remaining, opts, true, result)
+        }
+        None => {
+            result.success = true;
+            Ok(result)
+        }
+    }
+}
+
+pub fn abort_cherry_pick_internal(repo: &git2::Repository) -> Result<(), GitError> {
+    let has_pick_head = repo.path().join("CHERRY_PICK_HEAD").exists();
+    let sequence = read_sequencer(repo)?;
+    if !has_pick_head && sequence.is_none() {
+        return Err(GitError::NoOperationInProgress("cherry-pick".to_string()));
+    }
+
+    // Undo commits already created by the sequence, not just the stopped one
+    let target = match &sequence {
+        Some((orig_head, _, _)) => repo.find_commit(*orig_head)?,
+        None => repo.head()?.peel_to_commit()?,
+    };
+    repo.reset(target.as_object(), git2::ResetType::Hard, None)?;
+
+    remove_state_file(repo, "CHERRY_PICK_HEAD");
+    remove_state_file(repo, "MERGE_MSG");
+    remove_sequencer(repo)
+}
+
+pub fn revert_internal(
+    repo: &git2::Repository,
+    oid: &str,
+    mainline: Option<u32>,
+) -> Result<RevertResult, GitError> {
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(GitError::OperationInProgress(format!("{:?}", repo.state())));
+    }
+    if has_uncommitted_changes(repo)? {
+        return Err(GitError::DirtyWorkingDirectory);
+    }
+
+    let commit = repo.revparse_single(oid)?.peel_to_commit()?;
+    let opts = PickOptions {
+        record_origin: false,
+        mainline,
+    };
+
+    Ok(match apply_commit(repo, PickKind::Revert, &commit, opts)? {
+        StepOutcome::Committed(new_oid) => RevertResult {
+            success: true,
+            commit_oid: Some(new_oid.to_string()),
+            has_conflicts: false,
+            conflicted_files: vec![],
+        },
+        StepOutcome::Empty => RevertResult {
+            success: true,
+            commit_oid: None,
+            has_conflicts: false,
+            conflicted_files: vec![],
+        },
+        StepOutcome::Conflicts(conflicted_files) => RevertResult {
+            success: false,
+            commit_oid: None,
+            has_conflicts: true,
+            conflicted_files,
+        },
+    })
+}
+
+pub fn continue_revert_internal(repo: &git2::Repository) -> Result<RevertResult, GitError> {
+    let oid = commit_resolution(repo, PickKind::Revert)?;
+
+    Ok(RevertResult {
+        success: true,
+        commit_oid: oid.map(|o| o.to_string()),
+        has_conflicts: false,
+        conflicted_files: vec![],
+    })
+}
+
+pub fn abort_revert_internal(repo: &git2::Repository) -> Result<(), GitError> {
+    if !repo.path().join("REVERT_HEAD").exists() {
+        return Err(GitError::NoOperationInProgress("revert".to_string()));
+    }
+
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), git2::ResetType::Hard, None)?;
+
+    remove_state_file(repo, "REVERT_HEAD");
+    remove_state_file(repo, "MERGE_MSG");
+    Ok(())
+}
+
+/// Cherry-pick one or more commits onto the current branch.
+///
+/// Each revision may be a single commit or an `A..B` range (applied oldest first).
+/// `record_origin` appends a "(cherry picked from commit ...)" line like `git cherry-pick -x`;
+/// `mainline` selects the parent to diff against when picking merge commits.
+/// Stops on the first conflict; resolve the files and call `continue_cherry_pick`.
+#[tauri::command]
+#[specta::specta]
+pub async fn cherry_pick_commits(
+    revisions: Vec<String>,
+    record_origin: bool,
+    mainline: Option<u32>,
+    state: State<'_, RepositoryState>,
+) -> Result<CherryPickResult, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        format!("Cherry-pick {}", revisions.join(", ")),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            cherry_pick_internal(&repo, &revisions, record_origin, mainline)
+        },
+    )
+    .await
+}
+
+/// Commit the resolved cherry-pick and apply any remaining commits.
+#[tauri::command]
+#[specta::specta]
+pub async fn continue_cherry_pick(
+    state: State<'_, RepositoryState>,
+) -> Result<CherryPickResult, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Continue cherry-pick".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            continue_cherry_pick_internal(&repo)
+        },
+    )
+    .await
+}
+
+/// Abort an in-progress cherry-pick, restoring HEAD from before the sequence.
+#[tauri::command]
+#[specta::specta]
+pub async fn abort_cherry_pick(state: State<'_, RepositoryState>) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Abort cherry-pick".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            abort_cherry_pick_internal(&repo)
+        },
+    )
+    .await
+}
+
+/// Create a commit that undoes the changes of the given commit.
+///
+/// `mainline` selects the parent to revert against when reverting a merge commit.
+#[tauri::command]
+#[specta::specta]
+pub async fn revert_commit(
+    oid: String,
+    mainline: Option<u32>,
+    state: State<'_, RepositoryState>,
+) -> Result<RevertResult, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(repo_path.clone(), format!("Revert {:.7}", oid), move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        revert_internal(&repo, &oid, mainline)
+    })
+    .await
+}
+
+/// Commit the resolved revert.
+#[tauri::command]
+#[specta::specta]
+pub async fn continue_revert(state: State<'_, RepositoryState>) -> Result<RevertResult, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Continue revert".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            continue_revert_internal(&repo)
+        },
+    )
+    .await
+}
+
+/// Abort an in-progress revert and discard its changes.
+#[tauri::command]
+#[specta::specta]
+pub async fn abort_revert(state: State<'_, RepositoryState>) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(repo_path.clone(), "Abort revert".to_string(), move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        abort_revert_internal(&repo)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_file, setup_repo};
+
+    /// Create `topic` from HEAD, commit on it, and switch back to the original branch.
+    fn commit_on_branch(repo: &git2::Repository, files: &[(&str, &str, &str)]) -> Vec<git2::Oid> {
+        let original = repo.head().unwrap().name().unwrap().to_string();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("topic", &head, false).unwrap();
+        repo.set_head("refs/heads/topic").unwrap();
+
+        let oids = files
+            .iter()
+            .map(|(name, content, message)| commit_file(repo, name, content, message))
+            .collect();
+
+        repo.set_head(&original).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        oids
+    }
+
+    #[test]
+    fn test_cherry_pick_with_origin_line() {
+        let (dir, repo) = setup_repo();
+        commit_file(&repo, "a.txt", "a\n", "base");
+        let picked = commit_on_branch(&repo, &[("b.txt", "b\n", "add b")]);
+
+        let result = cherry_pick_internal(&repo, &[picked[0].to_string()], true, None).unwrap();
+        assert!(result.success);
+        assert_eq!(result.created_oids.len(), 1);
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(
+            head.message().unwrap(),
+            format!("add b\n\n(cherry picked from commit {})\n", picked[0])
+        );
+        assert!(dir.path().join("b.txt").exists());
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_cherry_pick_range_stops_on_conflict_and_continues() {
+        let (dir, repo) = setup_repo();
+        commit_file(&repo, "f.txt", "base\n", "base");
+        let base = repo.head().unwrap().peel_to_commit().unwrap().id();
+        commit_on_branch(
+            &repo,
+            &[("f.txt", "topic\n", "change f"), ("g.txt", "g\n", "add g")],
+        );
+        commit_file(&repo, "f.txt", "main\n", "diverge f");
+
+        let range = format!("{}..topic", base);
+        let result = cherry_pick_internal(&repo, &[range], false, None).unwrap();
+        assert!(result.has_conflicts);
+        assert_eq!(result.conflicted_files, vec!["f.txt".to_string()]);
+        assert_eq!(result.remaining, 1);
+        assert_eq!(repo.state(), git2::RepositoryState::CherryPickSequence);
+
+        fs::write(dir.path().join("f.txt"), "merged\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("f.txt")).unwrap();
+        index.write().unwrap();
+
+        let result = continue_cherry_pick_internal(&repo).unwrap();
+        assert!(result.success);
+        assert_eq!(result.created_oids.len(), 2);
+        assert!(dir.path().join("g.txt").exists());
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("add g\n"));
+        assert_eq!(head.parent(0).unwrap().message(), Some("change f\n"));
+    }
+
+    #[test]
+    fn test_abort_cherry_pick_restores_head() {
+        let (dir, repo) = setup_repo();
+        commit_file(&repo, "f.txt", "base\n", "base");
+        let picked = commit_on_branch(&repo, &[("f.txt", "topic\n", "change f")]);
+        let before = commit_file(&repo, "f.txt", "main\n", "diverge f");
+
+        let result = cherry_pick_internal(&repo, &[picked[0].to_string()], false, None).unwrap();
+        assert!(result.has_conflicts);
+        assert_eq!(repo.state(), git2::RepositoryState::CherryPick);
+
+        abort_cherry_pick_internal(&repo).unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().target(), Some(before));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("f.txt")).unwrap(),
+            "main\n"
+        );
+    }
+
+    #[test]
+    fn test_abort_after_last_commit_of_range_conflicts() {
+        let (dir, repo) = setup_repo();
+        commit_file(&repo, "f.txt", "base\n", "base");
+        let base = repo.head().unwrap().peel_to_commit().unwrap().id();
+        commit_on_branch(
+            &repo,
+            &[("g.txt", "g\n", "add g"), ("f.txt", "topic\n", "change f")],
+        );
+        let before = commit_file(&repo, "f.txt", "main\n", "diverge f");
+
+        let result =
+            cherry_pick_internal(&repo, &[format!("{}..topic", base)], false, None).unwrap();
+        assert!(result.has_conflicts);
+        assert_eq!(result.created_oids.len(), 1);
+        assert_eq!(result.remaining, 0);
+
+        abort_cherry_pick_internal(&repo).unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().target(), Some(before));
+        assert!(!dir.path().join("g.txt").exists());
+    }
+
+    #[test]
+    fn test_revert_commit() {
+        let (dir, repo) = setup_repo();
+        commit_file(&repo, "a.txt", "a\n", "base");
+        let added = commit_file(&repo, "b.txt", "b\n", "add b");
+
+        let result = revert_internal(&repo, &added.to_string(), None).unwrap();
+        assert!(result.success);
+        assert!(!dir.path().join("b.txt").exists());
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(
+            head.message().unwrap(),
+            format!("Revert \"add b\"\n\nThis reverts commit {}.\n", added)
+        );
+    }
+
+    #[test]
+    fn test_clean_message_strips_comments() {
+        let raw = "fix: thing\n\nbody\n#Conflicts:\n#\tf.txt\n";
+        assert_eq!(clean_message(raw), "fix: thing\n\nbody\n");
+    }
+}
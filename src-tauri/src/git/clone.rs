@@ -2,14 +2,27 @@
 //!
 //! Provides repository cloning functionality with real-time progress events
 //! sent via Tauri Channels for UI feedback.
+//!
+//! Clones go through libgit2, except partial clones (`--filter`), which
+//! libgit2 cannot create; those run the `git` command line. A cancelled or
+//! failed clone leaves no partial repository behind.
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+use std::time::Duration;
 use tauri::ipc::Channel;
+use tauri::State;
 
-use crate::git::credentials::create_credentials_callback;
+use crate::git::cancellation::{CancellationToken, OperationRegistry};
+use crate::git::credentials::CredentialSession;
 use crate::git::error::GitError;
+use crate::git::network::{NetworkSettings, create_certificate_check};
+use crate::git::submodule::{SubmoduleProgress, update_submodules_internal};
 
 /// Progress events for clone operations.
 ///
@@ -33,8 +46,84 @@ pub enum CloneProgress {
         total: u32,
         path: String,
     },
+    /// Cloning and checking out a submodule
+    Submodule { path: String },
     /// Clone completed successfully
     Finished { path: String },
+    /// Clone was cancelled and the partial clone removed
+    Cancelled,
+}
+
+/// Partial clone filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum CloneFilter {
+    /// Download file contents only when needed (`--filter=blob:none`)
+    Blobless,
+    /// Download trees and file contents only when needed (`--filter=tree:0`)
+    Treeless,
+}
+
+impl CloneFilter {
+    fn spec(self) -> &'static str {
+        match self {
+            CloneFilter::Blobless => "blob:none",
+            CloneFilter::Treeless => "tree:0",
+        }
+    }
+}
+
+/// Optional clone settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CloneOptions {
+    /// Initialize and check out submodules, recursively (`--recurse-submodules`)
+    pub recurse_submodules: bool,
+    /// Only fetch this many commits of history (`--depth`)
+    pub depth: Option<u32>,
+    /// Branch to check out instead of the remote's default (`--branch`)
+    pub branch: Option<String>,
+    /// Only fetch the checked-out branch (`--single-branch`)
+    pub single_branch: bool,
+    /// Partial clone filter; needs the `git` command line to be installed
+    pub filter: Option<CloneFilter>,
+    /// Clone without a working tree (`--bare`)
+    pub bare: bool,
+    /// Bare clone that mirrors every ref of the remote (`--mirror`)
+    pub mirror: bool,
+}
+
+impl CloneOptions {
+    fn is_bare(&self) -> bool {
+        self.bare || self.mirror
+    }
+
+    fn validate(&self) -> Result<(), GitError> {
+        let invalid = |message: &str| Err(GitError::CloneFailed(message.to_string()));
+        if self.depth == Some(0) {
+            return invalid("Depth must be at least 1");
+        }
+        if self.is_bare() && self.recurse_submodules {
+            return invalid("Submodules cannot be checked out in a bare clone");
+        }
+        if self.mirror && (self.single_branch || self.branch.is_some()) {
+            return invalid("A mirror clone always fetches every ref");
+        }
+        Ok(())
+    }
+}
+
+/// Let libgit2 open repositories created by a partial clone.
+///
+/// libgit2 cannot fetch missing objects on demand, but it can open such a
+/// repository and read everything that was downloaded. Registering the
+/// extension swaps a global list, so it only ever happens once.
+pub fn register_partial_clone_extension() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        // SAFETY: guarded by `Once`, so the extension list is only set once
+        let _ = unsafe { git2::opts::set_extensions(&["partialclone"]) };
+    });
 }
 
 /// Extract repository name from a Git URL.
@@ -78,6 +167,9 @@ fn extract_repo_name(url: &str) -> Option<String> {
 /// * `url` - The repository URL (HTTPS or SSH)
 /// * `destination` - Local path where the repository will be cloned
 /// * `on_progress` - Channel for sending progress events
+/// * `options` - Optional settings: depth, branch, partial clone filter,
+///   bare/mirror and submodule recursion
+/// * `operation_id` - Id under which `cancel_operation` can stop the clone
 ///
 /// # Returns
 /// The path to the cloned repository on success
@@ -87,7 +179,13 @@ pub async fn clone_repository(
     url: String,
     destination: String,
     on_progress: Channel<CloneProgress>,
+    options: Option<CloneOptions>,
+    operation_id: Option<String>,
+    registry: State<'_, OperationRegistry>,
 ) -> Result<String, GitError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+
     // Validate destination is not empty
     if destination.trim().is_empty() {
         return Err(GitError::InvalidPath(
@@ -128,6 +226,9 @@ pub async fn clone_repository(
         }
     }
 
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
+
     // Send started event
     on_progress
         .send(CloneProgress::Started { url: url.clone() })
@@ -138,79 +239,419 @@ pub async fn clone_repository(
     let progress_channel = on_progress.clone();
 
     let result = tokio::task::spawn_blocking(move || {
-        let mut callbacks = git2::RemoteCallbacks::new();
-
-        // Transfer progress callback for receiving/resolving phases
-        let progress_transfer = progress_channel.clone();
-        callbacks.transfer_progress(move |stats| {
-            let received = stats.received_objects() as u32;
-            let total = stats.total_objects() as u32;
-            let indexed = stats.indexed_deltas() as u32;
-            let total_deltas = stats.total_deltas() as u32;
-
-            if total_deltas > 0 && indexed > 0 {
-                // We're in the resolving deltas phase
-                let _ = progress_transfer.send(CloneProgress::Resolving {
-                    current: indexed,
-                    total: total_deltas,
-                });
-            } else {
-                // We're in the receiving objects phase
-                let _ = progress_transfer.send(CloneProgress::Receiving {
-                    received,
-                    total,
-                    bytes: stats.received_bytes() as u32,
-                });
-            }
-            true
-        });
+        clone_internal(
+            &url_clone,
+            Path::new(&destination_clone),
+            &options,
+            &|progress| {
+                let _ = progress_channel.send(progress);
+            },
+            &cancel,
+        )?;
+        Ok::<_, GitError>(destination_clone)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?;
 
-        // Set up credentials callback for authentication
-        callbacks.credentials(create_credentials_callback());
+    if let Err(GitError::Cancelled) = result {
+        on_progress.send(CloneProgress::Cancelled).ok();
+    }
+    let result = result?;
 
-        // Configure fetch options with our callbacks
-        let mut fetch_options = git2::FetchOptions::new();
-        fetch_options.remote_callbacks(callbacks);
+    // Send finished event
+    on_progress
+        .send(CloneProgress::Finished {
+            path: result.clone(),
+        })
+        .ok();
+
+    Ok(result)
+}
+
+/// Clone `url` into `dest` according to `options`.
+///
+/// `dest` must not exist or be an empty directory. If the clone fails or is
+/// cancelled, `dest` is removed again, or emptied if it already existed.
+pub fn clone_internal(
+    url: &str,
+    dest: &Path,
+    options: &CloneOptions,
+    on_progress: &(dyn Fn(CloneProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<git2::Repository, GitError> {
+    options.validate()?;
+
+    let dest_existed = dest.exists();
+    if dest_existed
+        && dest
+            .read_dir()
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(true)
+    {
+        return Err(GitError::PathExists(dest.display().to_string()));
+    }
+    let result = clone_into(url, dest, options, on_progress, cancel);
+    if result.is_err() {
+        remove_partial_clone(dest, dest_existed);
+    }
+    result
+}
 
+fn clone_into(
+    url: &str,
+    dest: &Path,
+    options: &CloneOptions,
+    on_progress: &(dyn Fn(CloneProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<git2::Repository, GitError> {
+    cancel.check()?;
+
+    let repo = if let Some(filter) = options.filter {
+        clone_with_git_cli(url, dest, options, filter, on_progress, cancel)?;
+        register_partial_clone_extension();
+        git2::Repository::open(dest)?
+    } else {
+        clone_with_libgit2(url, dest, options, on_progress, cancel)?
+    };
+    cancel.check()?;
+
+    if options.recurse_submodules {
+        update_submodules_internal(
+            &repo,
+            None,
+            true,
+            &|progress| {
+                if let SubmoduleProgress::Updating { path } = progress {
+                    on_progress(CloneProgress::Submodule { path });
+                }
+            },
+            cancel,
+        )
+        .map_err(|e| match e {
+            GitError::Cancelled => e,
+            e => GitError::CloneFailed(format!("Submodule update failed: {}", e)),
+        })?;
+    }
+
+    Ok(repo)
+}
+
+/// Remove what a failed clone left in `dest`.
+fn remove_partial_clone(dest: &Path, keep_dir: bool) {
+    if !keep_dir {
+        let _ = std::fs::remove_dir_all(dest);
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dest) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let _ = std::fs::remove_dir_all(&path);
+        } else {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+/// Branch the remote's HEAD points to, for `--single-branch` without `--branch`.
+fn remote_default_branch(url: &str) -> Result<String, GitError> {
+    let mut remote = git2::Remote::create_detached(url)?;
+    let mut callbacks = git2::RemoteCallbacks::new();
+    let credentials = CredentialSession::new();
+    callbacks.credentials(credentials.callback());
+    callbacks.certificate_check(create_certificate_check(url));
+    let network = NetworkSettings::load(None);
+    network.apply_ca_info()?;
+    let connection = remote.connect_auth(
+        git2::Direction::Fetch,
+        Some(callbacks),
+        Some(network.proxy_options()),
+    );
+    credentials.finish(&connection);
+    let connection = connection.map_err(|e| GitError::CloneFailed(e.message().to_string()))?;
+
+    let head = connection.default_branch()?;
+    let head = head
+        .as_str()
+        .ok_or_else(|| GitError::CloneFailed("Remote HEAD is not valid UTF-8".to_string()))?;
+    Ok(head.trim_start_matches("refs/heads/").to_string())
+}
+
+fn clone_with_libgit2(
+    url: &str,
+    dest: &Path,
+    options: &CloneOptions,
+    on_progress: &(dyn Fn(CloneProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<git2::Repository, GitError> {
+    let mut callbacks = git2::RemoteCallbacks::new();
+
+    // Transfer progress callback for receiving/resolving phases
+    callbacks.transfer_progress(|stats| {
+        let received = stats.received_objects() as u32;
+        let total = stats.total_objects() as u32;
+        let indexed = stats.indexed_deltas() as u32;
+        let total_deltas = stats.total_deltas() as u32;
+
+        if total_deltas > 0 && indexed > 0 {
+            // We're in the resolving deltas phase
+            on_progress(CloneProgress::Resolving {
+                current: indexed,
+                total: total_deltas,
+            });
+        } else {
+            // We're in the receiving objects phase
+            on_progress(CloneProgress::Receiving {
+                received,
+                total,
+                bytes: stats.received_bytes() as u32,
+            });
+        }
+        !cancel.is_cancelled()
+    });
+
+    // Set up credentials callback for authentication
+    let credentials = CredentialSession::new();
+    callbacks.credentials(credentials.callback());
+    callbacks.certificate_check(create_certificate_check(url));
+
+    // Configure fetch options with our callbacks
+    let network = NetworkSettings::load(None);
+    network.apply_ca_info()?;
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+    fetch_options.proxy_options(network.proxy_options());
+    if let Some(depth) = options.depth {
+        fetch_options.depth(depth.min(i32::MAX as u32 - 1) as i32);
+    }
+
+    // Build and execute clone
+    let mut builder = git2::build::RepoBuilder::new();
+    builder.fetch_options(fetch_options);
+    builder.bare(options.is_bare());
+
+    if !options.is_bare() {
         // Configure checkout with progress callback
         let mut checkout_builder = git2::build::CheckoutBuilder::new();
-        let progress_checkout = progress_channel.clone();
-
-        checkout_builder.progress(move |path, current, total| {
+        checkout_builder.progress(|path, current, total| {
             let path_str = path
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_default();
 
-            let _ = progress_checkout.send(CloneProgress::Checkout {
+            on_progress(CloneProgress::Checkout {
                 current: current as u32,
                 total: total as u32,
                 path: path_str,
             });
         });
-
-        // Build and execute clone
-        let mut builder = git2::build::RepoBuilder::new();
-        builder.fetch_options(fetch_options);
         builder.with_checkout(checkout_builder);
+    }
 
-        let dest_path = Path::new(&destination_clone);
-        builder
-            .clone(&url_clone, dest_path)
-            .map_err(|e| GitError::CloneFailed(e.message().to_string()))?;
+    let branch = if options.single_branch {
+        match &options.branch {
+            Some(branch) => Some(branch.clone()),
+            None => Some(remote_default_branch(url)?),
+        }
+    } else {
+        options.branch.clone()
+    };
+    if let Some(branch) = &branch {
+        builder.branch(branch);
+    }
 
-        Ok::<_, GitError>(destination_clone)
+    if options.mirror {
+        builder.remote_create(|repo, name, url| {
+            let remote = repo.remote_with_fetch(name, url, "+refs/*:refs/*")?;
+            repo.config()?
+                .set_bool(&format!("remote.{}.mirror", name), true)?;
+            Ok(remote)
+        });
+    } else if options.single_branch
+        && let Some(branch) = branch.clone()
+    {
+        builder.remote_create(move |repo, name, url| {
+            let refspec = format!("+refs/heads/{0}:refs/remotes/{1}/{0}", branch, name);
+            repo.remote_with_fetch(name, url, &refspec)
+        });
+    }
+
+    let result = builder.clone(url, dest);
+    credentials.finish(&result);
+    result.map_err(|e| {
+        if cancel.is_cancelled() {
+            GitError::Cancelled
+        } else {
+            GitError::CloneFailed(e.message().to_string())
+        }
     })
-    .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))??;
+}
 
-    // Send finished event
-    on_progress
-        .send(CloneProgress::Finished {
-            path: result.clone(),
-        })
-        .ok();
+/// Parse a `git clone --progress` line into a progress event.
+fn parse_cli_progress(line: &str) -> Option<CloneProgress> {
+    let (phase, rest) = line.trim().split_once(':')?;
+    let counts = rest.split_once('(')?.1.split_once(')')?.0;
+    let (current, total) = counts.split_once('/')?;
+    let current: u32 = current.trim().parse().ok()?;
+    let total: u32 = total.trim().parse().ok()?;
 
-    Ok(result)
+    match phase.trim_start_matches("remote: ") {
+        "Receiving objects" => Some(CloneProgress::Receiving {
+            received: current,
+            total,
+            bytes: 0,
+        }),
+        "Resolving deltas" => Some(CloneProgress::Resolving { current, total }),
+        "Updating files" => Some(CloneProgress::Checkout {
+            current,
+            total,
+            path: String::new(),
+        }),
+        _ => None,
+    }
+}
+
+/// True if `git clone` output shows it could not authenticate.
+fn is_cli_auth_failure(output: &str) -> bool {
+    const MARKERS: [&str; 4] = [
+        "terminal prompts disabled",
+        "Authentication failed",
+        "Permission denied (publickey",
+        "could not read Password",
+    ];
+    MARKERS.iter().any(|marker| output.contains(marker))
+}
+
+/// Run `git clone` for a partial clone.
+///
+/// git gets the configured `http.proxy` and `http.sslCAInfo`, but it
+/// authenticates and checks SSH host keys on its own: FlowForge's credential
+/// chain, the stored GitHub token and the known_hosts prompt do not apply.
+/// Prompts are disabled, so a repository git has no credentials for fails
+/// with `AuthenticationFailed`.
+fn clone_with_git_cli(
+    url: &str,
+    dest: &Path,
+    options: &CloneOptions,
+    filter: CloneFilter,
+    on_progress: &(dyn Fn(CloneProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<(), GitError> {
+    let network = NetworkSettings::load(None);
+    let mut command = Command::new("git");
+    if let Some(proxy) = &network.proxy {
+        command.arg("-c").arg(format!("http.proxy={}", proxy));
+    }
+    if let Some(ca_info) = &network.ca_info {
+        command
+            .arg("-c")
+            .arg(format!("http.sslCAInfo={}", ca_info.display()));
+    }
+    command
+        .arg("clone")
+        .arg("--progress")
+        .arg(format!("--filter={}", filter.spec()));
+    if let Some(depth) = options.depth {
+        command.arg(format!("--depth={}", depth));
+    }
+    if let Some(branch) = &options.branch {
+        command.arg(format!("--branch={}", branch));
+    }
+    if options.single_branch {
+        command.arg("--single-branch");
+    }
+    if options.mirror {
+        command.arg("--mirror");
+    } else if options.bare {
+        command.arg("--bare");
+    }
+    command
+        .arg("--")
+        .arg(url)
+        .arg(dest)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    let mut child = command.spawn().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            GitError::UnsupportedOperation(
+                "Partial clones need the git command line tools".to_string(),
+            )
+        } else {
+            GitError::CloneFailed(format!("Could not run git: {}", e))
+        }
+    })?;
+
+    let stderr = child.stderr.take();
+    let child = Mutex::new(child);
+    let done = AtomicBool::new(false);
+
+    let output = std::thread::scope(|scope| {
+        // Kill git when the clone is cancelled; reading stderr then hits EOF
+        scope.spawn(|| {
+            while !done.load(Ordering::SeqCst) {
+                if cancel.is_cancelled() {
+                    if let Ok(mut child) = child.lock() {
+                        let _ = child.kill();
+                    }
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        });
+
+        // Progress lines are separated by carriage returns while they update in place
+        let mut output = String::new();
+        if let Some(mut stderr) = stderr {
+            let mut buffer = [0u8; 4096];
+            let mut line = Vec::new();
+            while let Ok(read) = stderr.read(&mut buffer) {
+                if read == 0 {
+                    break;
+                }
+                for &byte in &buffer[..read] {
+                    if byte == b'\r' || byte == b'\n' {
+                        let text = String::from_utf8_lossy(&line).to_string();
+                        match parse_cli_progress(&text) {
+                            Some(progress) => on_progress(progress),
+                            None if !text.trim().is_empty() => {
+                                output.push_str(&text);
+                                output.push('\n');
+                            }
+                            None => {}
+                        }
+                        line.clear();
+                    } else {
+                        line.push(byte);
+                    }
+                }
+            }
+        }
+        done.store(true, Ordering::SeqCst);
+        output
+    });
+
+    let status = child
+        .into_inner()
+        .map_err(|_| GitError::Internal("Clone process lock poisoned".to_string()))?
+        .wait()
+        .map_err(|e| GitError::CloneFailed(format!("Could not run git: {}", e)))?;
+    cancel.check()?;
+    if !status.success() {
+        let output = output.trim_end().to_string();
+        if is_cli_auth_failure(&output) {
+            return Err(GitError::AuthenticationFailed(format!(
+                "{}\nPartial clones use git's own credential helpers and SSH keys; \
+                 set up access there or clone without a filter",
+                output
+            )));
+        }
+        return Err(GitError::CloneFailed(output));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -250,4 +691,212 @@ mod tests {
         assert_eq!(extract_repo_name(""), None);
         assert_eq!(extract_repo_name(".git"), None);
     }
+
+    fn git_available() -> bool {
+        Command::new("git").arg("--version").output().is_ok()
+    }
+
+    /// A source repository with commits on `main` and `feature`.
+    fn setup_source() -> (tempfile::TempDir, git2::Repository) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path().join("source")).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let mut parent = None;
+        for (i, name) in ["a.txt", "b.txt", "c.txt"].iter().enumerate() {
+            std::fs::write(repo.workdir().unwrap().join(name), i.to_string()).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents: Vec<git2::Commit> = parent
+                .map(|oid| repo.find_commit(oid).unwrap())
+                .into_iter()
+                .collect();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            parent = Some(
+                repo.commit(Some("HEAD"), &sig, &sig, name, &tree, &parent_refs)
+                    .unwrap(),
+            );
+        }
+        {
+            let head = repo.find_commit(parent.unwrap()).unwrap();
+            repo.branch("feature", &head, false).unwrap();
+        }
+        (dir, repo)
+    }
+
+    fn clone(url: &str, dest: &Path, options: &CloneOptions) -> Result<git2::Repository, GitError> {
+        clone_internal(url, dest, options, &|_| {}, &CancellationToken::default())
+    }
+
+    #[test]
+    fn test_cancelled_clone_removes_partial_directory() {
+        let (dir, source) = setup_source();
+        let url = source.workdir().unwrap().to_str().unwrap().to_string();
+        let cancel = CancellationToken::new();
+
+        // Cancel as soon as objects start arriving
+        let dest = dir.path().join("cancelled");
+        let result = clone_internal(
+            &url,
+            &dest,
+            &CloneOptions::default(),
+            &|_| cancel.cancel(),
+            &cancel,
+        );
+        assert!(matches!(result, Err(GitError::Cancelled)));
+        assert!(!dest.exists());
+
+        // An existing empty destination is kept, but emptied
+        let existing = dir.path().join("existing");
+        std::fs::create_dir(&existing).unwrap();
+        let result = clone_internal(&url, &existing, &CloneOptions::default(), &|_| {}, &cancel);
+        assert!(matches!(result, Err(GitError::Cancelled)));
+        assert!(existing.exists());
+        assert_eq!(existing.read_dir().unwrap().count(), 0);
+
+        // A non-empty destination is refused before anything is written or removed
+        std::fs::write(existing.join("notes.txt"), "keep me").unwrap();
+        let result = clone(&url, &existing, &CloneOptions::default());
+        assert!(matches!(result, Err(GitError::PathExists(_))));
+        assert_eq!(
+            std::fs::read_to_string(existing.join("notes.txt")).unwrap(),
+            "keep me"
+        );
+    }
+
+    #[test]
+    fn test_parse_cli_progress() {
+        assert!(matches!(
+            parse_cli_progress("Receiving objects:  45% (450/1000), 1.2 MiB | 3 MiB/s"),
+            Some(CloneProgress::Receiving {
+                received: 450,
+                total: 1000,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_cli_progress("Resolving deltas: 100% (7/7), done."),
+            Some(CloneProgress::Resolving { current: 7, total: 7 })
+        ));
+        assert!(parse_cli_progress("remote: Enumerating objects: 12, done.").is_none());
+        assert!(parse_cli_progress("Cloning into 'repo'...").is_none());
+    }
+
+    #[test]
+    fn test_single_branch_clone_only_tracks_one_branch() {
+        let (dir, source) = setup_source();
+        let url = source.workdir().unwrap().to_str().unwrap().to_string();
+        let options = CloneOptions {
+            single_branch: true,
+            branch: Some("feature".to_string()),
+            ..Default::default()
+        };
+
+        let repo = clone(&url, &dir.path().join("single"), &options).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+        assert!(repo.find_reference("refs/remotes/origin/feature").is_ok());
+        assert!(repo.find_reference("refs/remotes/origin/main").is_err());
+        let remote = repo.find_remote("origin").unwrap();
+        let refspecs: Vec<String> = remote
+            .fetch_refspecs()
+            .unwrap()
+            .iter()
+            .flatten()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            refspecs,
+            vec!["+refs/heads/feature:refs/remotes/origin/feature".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_mirror_clone_is_bare_with_all_refs() {
+        let (dir, source) = setup_source();
+        let url = source.workdir().unwrap().to_str().unwrap().to_string();
+        let options = CloneOptions {
+            mirror: true,
+            ..Default::default()
+        };
+
+        let repo = clone(&url, &dir.path().join("mirror.git"), &options).unwrap();
+        assert!(repo.is_bare());
+        assert!(repo.find_reference("refs/heads/main").is_ok());
+        assert!(repo.find_reference("refs/heads/feature").is_ok());
+        assert!(
+            repo.config()
+                .unwrap()
+                .get_bool("remote.origin.mirror")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_option_combinations_are_rejected() {
+        let bare_submodules = CloneOptions {
+            bare: true,
+            recurse_submodules: true,
+            ..Default::default()
+        };
+        assert!(bare_submodules.validate().is_err());
+        let zero_depth = CloneOptions {
+            depth: Some(0),
+            ..Default::default()
+        };
+        assert!(zero_depth.validate().is_err());
+    }
+
+    #[test]
+    fn test_cli_auth_failures_are_recognised() {
+        assert!(is_cli_auth_failure(
+            "fatal: could not read Username for 'https://example.com': terminal prompts disabled"
+        ));
+        assert!(is_cli_auth_failure(
+            "git@example.com: Permission denied (publickey).\nfatal: Could not read from remote repository."
+        ));
+        assert!(!is_cli_auth_failure(
+            "fatal: repository 'https://example.com/missing.git/' not found"
+        ));
+    }
+
+    #[test]
+    fn test_blobless_clone_uses_git_cli() {
+        if !git_available() {
+            return;
+        }
+        let (dir, source) = setup_source();
+        source
+            .config()
+            .unwrap()
+            .set_bool("uploadpack.allowFilter", true)
+            .unwrap();
+        let url = format!("file://{}", source.workdir().unwrap().display());
+        let options = CloneOptions {
+            filter: Some(CloneFilter::Blobless),
+            ..Default::default()
+        };
+
+        let progress = std::sync::Mutex::new(0);
+        let repo = clone_internal(
+            &url,
+            &dir.path().join("partial"),
+            &options,
+            &|_| {
+                *progress.lock().unwrap() += 1;
+            },
+            &CancellationToken::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            repo.config()
+                .unwrap()
+                .get_string("remote.origin.partialclonefilter")
+                .unwrap(),
+            "blob:none"
+        );
+        assert!(repo.workdir().unwrap().join("c.txt").exists());
+        assert!(*progress.lock().unwrap() > 0);
+    }
 }
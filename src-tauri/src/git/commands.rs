@@ -3,6 +3,7 @@ use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
+use crate::git::auto_fetch::{AutoFetchState, start_auto_fetch, stop_auto_fetch};
 use crate::git::error::GitError;
 use crate::git::repository::{RepoStatus, RepositoryState};
 use crate::git::watcher::{WatcherState, start_watching, stop_watching};
@@ -23,13 +24,15 @@ pub struct RepoHealth {
 ///
 /// Validates the path exists and is a git repository,
 /// then stores it as the current active repository.
-/// Also starts a file watcher to detect external changes.
+/// Also starts a file watcher to detect external changes and the
+/// background auto-fetch scheduler.
 #[tauri::command]
 #[specta::specta]
 pub async fn open_repository(
     path: String,
     state: State<'_, RepositoryState>,
     watcher_state: State<'_, Mutex<WatcherState>>,
+    auto_fetch_state: State<'_, Mutex<AutoFetchState>>,
     app_handle: tauri::AppHandle,
 ) -> Result<RepoStatus, GitError> {
     let path_buf = PathBuf::from(&path);
@@ -44,7 +47,10 @@ pub async fn open_repository(
 
     // Start watching after successful open
     if let Ok(mut watcher) = watcher_state.lock() {
-        let _ = start_watching(&mut watcher, path_buf, app_handle);
+        let _ = start_watching(&mut watcher, path_buf.clone(), app_handle.clone());
+    }
+    if let Ok(mut auto_fetch) = auto_fetch_state.lock() {
+        start_auto_fetch(&mut auto_fetch, path_buf, app_handle);
     }
 
     Ok(status)
@@ -84,17 +90,21 @@ pub async fn is_git_repository(path: String) -> Result<bool, GitError> {
 
 /// Close the current repository.
 ///
-/// Stops the file watcher and clears the stored repository path.
+/// Stops the file watcher and auto-fetch, and clears the stored repository path.
 #[tauri::command]
 #[specta::specta]
 pub async fn close_repository(
     state: State<'_, RepositoryState>,
     watcher_state: State<'_, Mutex<WatcherState>>,
+    auto_fetch_state: State<'_, Mutex<AutoFetchState>>,
 ) -> Result<(), GitError> {
     // Stop watcher first
     if let Ok(mut watcher) = watcher_state.lock() {
         stop_watching(&mut watcher);
     }
+    if let Ok(mut auto_fetch) = auto_fetch_state.lock() {
+        stop_auto_fetch(&mut auto_fetch);
+    }
 
     state.close().await;
     Ok(())
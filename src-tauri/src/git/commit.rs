@@ -1,9 +1,15 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::path::Path;
 use tauri::State;
+use tauri::ipc::Channel;
 
 use crate::git::error::GitError;
+use crate::git::hooks::{HookEvent, commit_hook_env, run_hook, run_message_hooks};
+use crate::git::identity::{IdentityState, identity_warning};
 use crate::git::repository::RepositoryState;
+use crate::git::signing::create_commit_signed;
+use crate::git::undo::record_operation;
 
 /// Information about a created commit.
 ///
@@ -18,6 +24,9 @@ pub struct CommitInfo {
     pub short_oid: String,
     /// Commit message
     pub message: String,
+    /// Set when the commit identity differs from the identity profile
+    /// assigned to the repository
+    pub identity_warning: Option<String>,
 }
 
 /// Last commit message with subject and body parsed separately.
@@ -108,106 +117,169 @@ fn parse_commit_message(message: &str) -> (String, Option<String>) {
     (subject, body)
 }
 
+/// Undo journal description for a commit.
+fn commit_description(message: &str, amend: bool) -> String {
+    let (subject, _) = parse_commit_message(message);
+    if amend {
+        format!("Amend commit \"{}\"", subject)
+    } else {
+        format!("Commit \"{}\"", subject)
+    }
+}
+
+/// Create a commit from the index, running the commit hooks around it.
+///
+/// Runs `pre-commit`, `prepare-commit-msg`, `commit-msg` and `post-commit`
+/// like `git commit`; a failing hook aborts with `HookFailed`.
+pub fn create_commit_internal(
+    repo: &git2::Repository,
+    message: &str,
+    amend: bool,
+    on_hook_event: &(dyn Fn(HookEvent) + Sync),
+) -> Result<CommitInfo, GitError> {
+    // Get signature from git config
+    let sig = repo.signature().map_err(|e| {
+        GitError::SignatureError(format!(
+            "Could not determine commit author. Please configure git: {}",
+            e.message()
+        ))
+    })?;
+
+    // pre-commit may modify and restage files, so it runs before the index is read
+    let env = commit_hook_env(repo);
+    let env: Vec<(&str, &Path)> = env.iter().map(|(k, v)| (*k, v.as_path())).collect();
+    run_hook(repo, "pre-commit", &[], &env, None, on_hook_event)?;
+
+    // Get the index and write tree
+    let mut index = repo.index()?;
+    let tree_oid = index.write_tree()?;
+    let tree = repo.find_tree(tree_oid)?;
+
+    // Check if there are staged changes by comparing tree to HEAD
+    let has_staged_changes = if amend {
+        // For amend, compare new tree to parent's tree (if exists)
+        match repo.head() {
+            Ok(head) => {
+                let head_commit = head.peel_to_commit()?;
+                if head_commit.parent_count() > 0 {
+                    let parent = head_commit.parent(0)?;
+                    tree_oid != parent.tree_id()
+                } else {
+                    // First commit being amended - always allow
+                    true
+                }
+            }
+            Err(_) => true, // No HEAD, allow commit
+        }
+    } else {
+        // For normal commit, compare tree to HEAD tree
+        match repo.head() {
+            Ok(head) => {
+                let head_commit = head.peel_to_commit()?;
+                tree_oid != head_commit.tree_id()
+            }
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                // First commit - check if tree has any entries
+                tree.len() > 0
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    if !has_staged_changes {
+        return Err(GitError::NoStagedChanges);
+    }
+
+    let message = run_message_hooks(repo, message, "message", &env, on_hook_event)?;
+
+    let oid = if amend {
+        // Amend: reuse parent's parents
+        let head = repo.head()?.peel_to_commit()?;
+        let parents: Vec<git2::Commit> = head.parents().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+
+        create_commit_signed(
+            repo,
+            Some("HEAD"),
+            &sig,
+            &sig,
+            &message,
+            &tree,
+            &parent_refs,
+        )?
+    } else {
+        // Normal commit: HEAD is parent (if exists)
+        match repo.head() {
+            Ok(head) => {
+                let parent = head.peel_to_commit()?;
+                create_commit_signed(repo, Some("HEAD"), &sig, &sig, &message, &tree, &[&parent])?
+            }
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                // First commit - no parent
+                create_commit_signed(repo, Some("HEAD"), &sig, &sig, &message, &tree, &[])?
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    // post-commit cannot affect the outcome; its output is still streamed
+    let _ = run_hook(repo, "post-commit", &[], &env, None, on_hook_event);
+
+    Ok(CommitInfo {
+        oid: oid.to_string(),
+        short_oid: format!("{:.7}", oid),
+        message,
+        identity_warning: None,
+    })
+}
+
 /// Create a new commit from staged changes.
 ///
 /// Creates a commit with the given message from the current index (staged changes).
 /// If `amend` is true, replaces the last commit instead of creating a new one.
+/// Output of the repository's commit hooks is streamed to `on_hook_output`.
 ///
 /// # Errors
 /// - `NoStagedChanges` if index is empty (nothing staged)
 /// - `SignatureError` if git config lacks user.name/email
+/// - `HookFailed` if a pre-commit or commit-msg hook rejects the commit
 /// - Various git2 errors for other failures
 #[tauri::command]
 #[specta::specta]
 pub async fn create_commit(
     message: String,
     amend: bool,
+    on_hook_output: Option<Channel<HookEvent>>,
     state: State<'_, RepositoryState>,
+    identity: State<'_, IdentityState>,
 ) -> Result<CommitInfo, GitError> {
     let repo_path = state
         .get_path()
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+    let identity = identity.inner().clone();
 
-    tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-
-        // Get signature from git config
-        let sig = repo.signature().map_err(|e| {
-            GitError::SignatureError(format!(
-                "Could not determine commit author. Please configure git: {}",
-                e.message()
-            ))
-        })?;
-
-        // Get the index and write tree
-        let mut index = repo.index()?;
-        let tree_oid = index.write_tree()?;
-        let tree = repo.find_tree(tree_oid)?;
-
-        // Check if there are staged changes by comparing tree to HEAD
-        let has_staged_changes = if amend {
-            // For amend, compare new tree to parent's tree (if exists)
-            match repo.head() {
-                Ok(head) => {
-                    let head_commit = head.peel_to_commit()?;
-                    if head_commit.parent_count() > 0 {
-                        let parent = head_commit.parent(0)?;
-                        tree_oid != parent.tree_id()
-                    } else {
-                        // First commit being amended - always allow
-                        true
-                    }
-                }
-                Err(_) => true, // No HEAD, allow commit
-            }
-        } else {
-            // For normal commit, compare tree to HEAD tree
-            match repo.head() {
-                Ok(head) => {
-                    let head_commit = head.peel_to_commit()?;
-                    tree_oid != head_commit.tree_id()
-                }
-                Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
-                    // First commit - check if tree has any entries
-                    tree.len() > 0
-                }
-                Err(e) => return Err(e.into()),
-            }
-        };
-
-        if !has_staged_changes {
-            return Err(GitError::NoStagedChanges);
-        }
-
-        let oid = if amend {
-            // Amend: reuse parent's parents
-            let head = repo.head()?.peel_to_commit()?;
-            let parents: Vec<git2::Commit> = head.parents().collect();
-            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
-
-            repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &parent_refs)?
-        } else {
-            // Normal commit: HEAD is parent (if exists)
-            match repo.head() {
-                Ok(head) => {
-                    let parent = head.peel_to_commit()?;
-                    repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[&parent])?
-                }
-                Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
-                    // First commit - no parent
-                    repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[])?
+    record_operation(
+        repo_path.clone(),
+        commit_description(&message, amend),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            // The identity check only warns; an unreadable profile store must not block commits
+            let warning = identity
+                .assigned_profile(&repo_path)
+                .and_then(|profile| identity_warning(&repo, profile))
+                .unwrap_or_else(|e| {
+                    eprintln!("[identity] Warning: skipped identity check: {}", e);
+                    None
+                });
+            let mut info = create_commit_internal(&repo, &message, amend, &|event| {
+                if let Some(channel) = &on_hook_output {
+                    let _ = channel.send(event);
                 }
-                Err(e) => return Err(e.into()),
-            }
-        };
-
-        Ok(CommitInfo {
-            oid: oid.to_string(),
-            short_oid: format!("{:.7}", oid),
-            message,
-        })
-    })
+            })?;
+            info.identity_warning = warning;
+            Ok(info)
+        },
+    )
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
@@ -1,12 +1,16 @@
-//! Git global configuration access.
+//! Git configuration access.
 //!
 //! Provides read/write access to the user's global git configuration
-//! (user.name, user.email, init.defaultBranch).
+//! (user.name, user.email, init.defaultBranch), and a general editor for
+//! every entry at the system, global, local and worktree levels.
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::State;
 
 use crate::git::error::GitError;
+use crate::git::repository::RepositoryState;
 
 /// Snapshot of relevant global git configuration values.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -78,3 +82,495 @@ pub async fn set_git_global_config(key: String, value: String) -> Result<(), Git
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
+
+/// A git config level, from lowest to highest precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigLevel {
+    /// Windows `%PROGRAMDATA%\Git\config`.
+    ProgramData,
+    /// `/etc/gitconfig`.
+    System,
+    /// `~/.config/git/config`.
+    Xdg,
+    /// `~/.gitconfig`.
+    Global,
+    /// `.git/config`.
+    Local,
+    /// `config.worktree` of the current worktree, with `extensions.worktreeConfig`.
+    Worktree,
+    /// Values set by the application at runtime.
+    App,
+}
+
+impl ConfigLevel {
+    fn from_git2(level: git2::ConfigLevel) -> Self {
+        match level {
+            git2::ConfigLevel::ProgramData => Self::ProgramData,
+            git2::ConfigLevel::System => Self::System,
+            git2::ConfigLevel::XDG => Self::Xdg,
+            git2::ConfigLevel::Global => Self::Global,
+            git2::ConfigLevel::Local => Self::Local,
+            git2::ConfigLevel::Worktree => Self::Worktree,
+            git2::ConfigLevel::App | git2::ConfigLevel::Highest => Self::App,
+        }
+    }
+
+    fn to_git2(self) -> git2::ConfigLevel {
+        match self {
+            Self::ProgramData => git2::ConfigLevel::ProgramData,
+            Self::System => git2::ConfigLevel::System,
+            Self::Xdg => git2::ConfigLevel::XDG,
+            Self::Global => git2::ConfigLevel::Global,
+            Self::Local => git2::ConfigLevel::Local,
+            Self::Worktree => git2::ConfigLevel::Worktree,
+            Self::App => git2::ConfigLevel::App,
+        }
+    }
+}
+
+/// One value of a config key and where it came from.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigEntry {
+    /// Key in canonical form (`section.subsection.name`).
+    pub name: String,
+    /// Value, or None for a bare boolean key like `[core] bare`.
+    pub value: Option<String>,
+    pub level: ConfigLevel,
+    /// File of the level the entry came from, if known.
+    pub origin: Option<String>,
+    /// Whether the entry came from a file pulled in with `include`/`includeIf`.
+    pub included: bool,
+}
+
+/// Keys git reads as booleans.
+const BOOLEAN_KEYS: &[&str] = &[
+    "core.bare",
+    "core.filemode",
+    "core.ignorecase",
+    "core.symlinks",
+    "core.fsmonitor",
+    "core.untrackedcache",
+    "commit.gpgsign",
+    "tag.gpgsign",
+    "fetch.prune",
+    "fetch.prunetags",
+    "fetch.writecommitgraph",
+    "push.autosetupremote",
+    "push.followtags",
+    "rebase.autosquash",
+    "rebase.autostash",
+    "rebase.updaterefs",
+    "merge.autostash",
+    "rerere.enabled",
+    "advice.detachedhead",
+    "color.ui",
+];
+
+/// Keys git reads as integers.
+const INTEGER_KEYS: &[&str] = &[
+    "core.compression",
+    "core.abbrev",
+    "diff.renamelimit",
+    "gc.auto",
+    "merge.renamelimit",
+    "pack.threads",
+];
+
+/// Check a value for keys git would reject or misread.
+///
+/// Unknown keys accept any value; only the key's shape is checked.
+pub fn validate_config_value(key: &str, value: &str) -> Result<(), GitError> {
+    let (section, name) = match (key.find('.'), key.rfind('.')) {
+        (Some(first), Some(last)) => (&key[..first], &key[last + 1..]),
+        _ => return Err(GitError::InvalidConfigKey(key.to_string())),
+    };
+    let valid_part = |part: &str| {
+        part.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if !valid_part(section) || !valid_part(name) {
+        return Err(GitError::InvalidConfigKey(key.to_string()));
+    }
+
+    // Sections and names are case-insensitive; subsections are not
+    let canonical = format!("{}.{}", section, name).to_ascii_lowercase();
+    let is_bool = git2::Config::parse_bool(value).is_ok();
+    let allowed =
+        |choices: &[&str]| is_bool || choices.iter().any(|c| c.eq_ignore_ascii_case(value));
+    let invalid = |expected: &str| {
+        Err(GitError::InvalidConfigValue(format!(
+            "{} must be {}, got '{}'",
+            key, expected, value
+        )))
+    };
+
+    match canonical.as_str() {
+        "pull.rebase" if !allowed(&["merges", "m", "interactive", "i"]) => {
+            invalid("a boolean, 'merges' or 'interactive'")
+        }
+        "core.autocrlf" if !allowed(&["input"]) => invalid("a boolean or 'input'"),
+        "pull.ff" | "merge.ff" if !allowed(&["only"]) => invalid("a boolean or 'only'"),
+        "core.logallrefupdates" if !allowed(&["always"]) => invalid("a boolean or 'always'"),
+        "color.ui" if !allowed(&["auto", "always", "never"]) => {
+            invalid("a boolean, 'auto', 'always' or 'never'")
+        }
+        "pull.rebase"
+        | "core.autocrlf"
+        | "pull.ff"
+        | "merge.ff"
+        | "core.logallrefupdates"
+        | "color.ui" => Ok(()),
+        k if BOOLEAN_KEYS.contains(&k) && !is_bool => invalid("a boolean"),
+        k if INTEGER_KEYS.contains(&k) && git2::Config::parse_i64(value).is_err() => {
+            invalid("an integer")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Repository config if a path is given, else system/XDG/global only.
+fn open_config(
+    repo_path: Option<&Path>,
+) -> Result<(git2::Config, Vec<(ConfigLevel, PathBuf)>), GitError> {
+    let mut origins = Vec::new();
+    if let Ok(path) = git2::Config::find_system() {
+        origins.push((ConfigLevel::System, path));
+    }
+    if let Ok(path) = git2::Config::find_xdg() {
+        origins.push((ConfigLevel::Xdg, path));
+    }
+    if let Ok(path) = git2::Config::find_global() {
+        origins.push((ConfigLevel::Global, path));
+    }
+
+    let config = match repo_path {
+        Some(path) => {
+            let repo = git2::Repository::open(path)?;
+            origins.push((ConfigLevel::Local, repo.commondir().join("config")));
+            origins.push((ConfigLevel::Worktree, repo.path().join("config.worktree")));
+            repo.config()?
+        }
+        None => git2::Config::open_default()?,
+    };
+    Ok((config, origins))
+}
+
+/// All entries, lowest precedence level first.
+fn list_entries(
+    config: &git2::Config,
+    origins: &[(ConfigLevel, PathBuf)],
+    key: Option<&str>,
+) -> Result<Vec<ConfigEntry>, GitError> {
+    let mut entries = Vec::new();
+    let mut iter = match key {
+        Some(key) => config.multivar(key, None)?,
+        None => config.entries(None)?,
+    };
+    while let Some(entry) = iter.next() {
+        let entry = entry?;
+        let level = ConfigLevel::from_git2(entry.level());
+        entries.push(ConfigEntry {
+            name: entry.name().unwrap_or_default().to_string(),
+            value: entry
+                .has_value()
+                .then(|| String::from_utf8_lossy(entry.value_bytes()).into_owned()),
+            level,
+            origin: origins
+                .iter()
+                .find(|(l, _)| *l == level)
+                .map(|(_, path)| path.to_string_lossy().into_owned()),
+            included: entry.include_depth() > 0,
+        });
+    }
+    // Stable, so entries keep file order within a level
+    entries.sort_by_key(|e| e.level.to_git2() as i32);
+    Ok(entries)
+}
+
+/// The single file backing `level`.
+fn open_level(config: &git2::Config, level: ConfigLevel) -> Result<git2::Config, GitError> {
+    config.open_level(level.to_git2()).map_err(|e| {
+        if e.code() != git2::ErrorCode::NotFound {
+            GitError::from(e)
+        } else if level == ConfigLevel::Worktree {
+            GitError::OperationFailed(
+                "Worktree config requires extensions.worktreeConfig to be enabled".to_string(),
+            )
+        } else {
+            GitError::NotFound(format!("No {:?} config file", level))
+        }
+    })
+}
+
+pub(crate) fn set_value(
+    config: &git2::Config,
+    key: &str,
+    value: &str,
+    level: ConfigLevel,
+) -> Result<(), GitError> {
+    validate_config_value(key, value)?;
+    open_level(config, level)?.set_str(key, value)?;
+    Ok(())
+}
+
+/// Add another value for a multi-valued key (`git config --add`).
+fn add_value(
+    config: &git2::Config,
+    key: &str,
+    value: &str,
+    level: ConfigLevel,
+) -> Result<(), GitError> {
+    validate_config_value(key, value)?;
+    // A pattern no existing value matches appends instead of replacing
+    open_level(config, level)?.set_multivar(key, "$^", value)?;
+    Ok(())
+}
+
+/// Remove a key (`git config --unset`), or every value with `all`.
+pub(crate) fn unset_value(
+    config: &git2::Config,
+    key: &str,
+    level: ConfigLevel,
+    all: bool,
+) -> Result<(), GitError> {
+    let mut level_config = open_level(config, level)?;
+    let result = if all {
+        level_config.remove_multivar(key, ".*")
+    } else {
+        level_config.remove(key)
+    };
+    result.map_err(|e| match e.code() {
+        git2::ErrorCode::NotFound => GitError::NotFound(format!("Config key not set: {}", key)),
+        _ => GitError::from(e),
+    })
+}
+
+/// List every config entry with its level and origin file.
+///
+/// Without an open repository only the system, XDG and global levels are listed.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_config_entries(
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<ConfigEntry>, GitError> {
+    let repo_path = state.get_path().await;
+    tokio::task::spawn_blocking(move || {
+        let (config, origins) = open_config(repo_path.as_deref())?;
+        list_entries(&config, &origins, None)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Get every value of a key, optionally only those at one level.
+///
+/// The effective value is the last entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_config_values(
+    key: String,
+    level: Option<ConfigLevel>,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<ConfigEntry>, GitError> {
+    let repo_path = state.get_path().await;
+    tokio::task::spawn_blocking(move || {
+        let (config, origins) = open_config(repo_path.as_deref())?;
+        let mut entries = list_entries(&config, &origins, Some(&key))?;
+        if let Some(level) = level {
+            entries.retain(|e| e.level == level);
+        }
+        Ok(entries)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Set a key at a level, replacing its value there.
+///
+/// Fails for keys with several values at that level; unset them first.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_config_value(
+    key: String,
+    value: String,
+    level: ConfigLevel,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = state.get_path().await;
+    tokio::task::spawn_blocking(move || {
+        let (config, _) = open_config(repo_path.as_deref())?;
+        set_value(&config, &key, &value, level)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Add a value to a multi-valued key at a level (`git config --add`).
+#[tauri::command]
+#[specta::specta]
+pub async fn add_config_value(
+    key: String,
+    value: String,
+    level: ConfigLevel,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = state.get_path().await;
+    tokio::task::spawn_blocking(move || {
+        let (config, _) = open_config(repo_path.as_deref())?;
+        add_value(&config, &key, &value, level)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Remove a key at a level. `all` removes every value of a multi-valued key.
+#[tauri::command]
+#[specta::specta]
+pub async fn unset_config_value(
+    key: String,
+    level: ConfigLevel,
+    all: Option<bool>,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = state.get_path().await;
+    tokio::task::spawn_blocking(move || {
+        let (config, _) = open_config(repo_path.as_deref())?;
+        unset_value(&config, &key, level, all.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Check a key and value before saving, e.g. while the user types.
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_config_entry(key: String, value: String) -> Result<(), GitError> {
+    validate_config_value(&key, &value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A config made of a global and a local file, without the user's own files.
+    fn test_config(dir: &TempDir) -> (git2::Config, Vec<(ConfigLevel, PathBuf)>) {
+        let global = dir.path().join("gitconfig");
+        let local = dir.path().join("config");
+        std::fs::write(&global, "[user]\n\tname = Global\n").unwrap();
+        std::fs::write(&local, "[user]\n\tname = Local\n[core]\n\tbare\n").unwrap();
+
+        let mut config = git2::Config::new().unwrap();
+        config
+            .add_file(&global, git2::ConfigLevel::Global, false)
+            .unwrap();
+        config
+            .add_file(&local, git2::ConfigLevel::Local, false)
+            .unwrap();
+        let origins = vec![(ConfigLevel::Global, global), (ConfigLevel::Local, local)];
+        (config, origins)
+    }
+
+    #[test]
+    fn test_entries_report_level_and_origin() {
+        let dir = TempDir::new().unwrap();
+        let (config, origins) = test_config(&dir);
+
+        let entries = list_entries(&config, &origins, None).unwrap();
+        let names: Vec<_> = entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.value.as_deref(), e.level))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("user.name", Some("Global"), ConfigLevel::Global),
+                ("user.name", Some("Local"), ConfigLevel::Local),
+                ("core.bare", None, ConfigLevel::Local),
+            ]
+        );
+        assert_eq!(
+            entries[0].origin.as_deref(),
+            Some(dir.path().join("gitconfig").to_string_lossy().as_ref())
+        );
+    }
+
+    #[test]
+    fn test_set_add_and_unset_at_a_level() {
+        let dir = TempDir::new().unwrap();
+        let (config, origins) = test_config(&dir);
+
+        set_value(&config, "pull.rebase", "merges", ConfigLevel::Local).unwrap();
+        add_value(
+            &config,
+            "remote.origin.fetch",
+            "+refs/heads/*:refs/remotes/origin/*",
+            ConfigLevel::Local,
+        )
+        .unwrap();
+        add_value(
+            &config,
+            "remote.origin.fetch",
+            "+refs/tags/*:refs/tags/*",
+            ConfigLevel::Local,
+        )
+        .unwrap();
+        let values = list_entries(&config, &origins, Some("remote.origin.fetch")).unwrap();
+        assert_eq!(values.len(), 2);
+
+        // Several values: --unset refuses, --unset-all removes them
+        assert!(unset_value(&config, "remote.origin.fetch", ConfigLevel::Local, false).is_err());
+        unset_value(&config, "remote.origin.fetch", ConfigLevel::Local, true).unwrap();
+        assert!(
+            list_entries(&config, &origins, Some("remote.origin.fetch"))
+                .unwrap()
+                .is_empty()
+        );
+
+        unset_value(&config, "user.name", ConfigLevel::Local, false).unwrap();
+        let local = std::fs::read_to_string(dir.path().join("config")).unwrap();
+        assert!(local.contains("rebase = merges"));
+        assert!(!local.contains("Local"));
+        assert!(matches!(
+            unset_value(&config, "user.name", ConfigLevel::Local, false),
+            Err(GitError::NotFound(_))
+        ));
+        assert_eq!(config.get_string("user.name").unwrap(), "Global");
+    }
+
+    #[test]
+    fn test_well_known_keys_are_validated() {
+        assert!(validate_config_value("core.autocrlf", "input").is_ok());
+        assert!(validate_config_value("core.autocrlf", "yes").is_ok());
+        assert!(validate_config_value("pull.rebase", "interactive").is_ok());
+        assert!(validate_config_value("Commit.GpgSign", "off").is_ok());
+        assert!(validate_config_value("alias.co", "checkout").is_ok());
+
+        assert!(matches!(
+            validate_config_value("pull.rebase", "preserve"),
+            Err(GitError::InvalidConfigValue(_))
+        ));
+        assert!(matches!(
+            validate_config_value("core.autocrlf", "output"),
+            Err(GitError::InvalidConfigValue(_))
+        ));
+        assert!(matches!(
+            validate_config_value("fetch.prune", "sometimes"),
+            Err(GitError::InvalidConfigValue(_))
+        ));
+        assert!(matches!(
+            validate_config_value("gc.auto", "lots"),
+            Err(GitError::InvalidConfigValue(_))
+        ));
+        assert!(matches!(
+            validate_config_value("nodot", "x"),
+            Err(GitError::InvalidConfigKey(_))
+        ));
+        assert!(matches!(
+            validate_config_value("1core.bare", "true"),
+            Err(GitError::InvalidConfigKey(_))
+        ));
+    }
+}
@@ -41,30 +41,36 @@ pub async fn list_conflict_files(
         let repo = git2::Repository::open(&repo_path)?;
         let index = repo.index()?;
 
-        let mut paths = Vec::new();
-        for conflict in index.conflicts()? {
-            if let Ok(conflict) = conflict {
-                // Prefer our path, then their, then ancestor
-                let path = conflict
-                    .our
-                    .as_ref()
-                    .or(conflict.their.as_ref())
-                    .or(conflict.ancestor.as_ref())
-                    .and_then(|entry| std::str::from_utf8(&entry.path).ok())
-                    .map(|s| s.to_string());
-
-                if let Some(p) = path {
-                    paths.push(p);
-                }
-            }
-        }
-
-        Ok(paths)
+        collect_conflicted_paths(&index)
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
+/// Collect the paths of all conflicted entries in an index.
+///
+/// Shared by the merge, rebase and cherry-pick flows so every operation
+/// reports conflicts the same way.
+pub(crate) fn collect_conflicted_paths(index: &git2::Index) -> Result<Vec<String>, GitError> {
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()?.flatten() {
+        // Prefer our path, then their, then ancestor
+        let path = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+            .and_then(|entry| std::str::from_utf8(&entry.path).ok())
+            .map(|s| s.to_string());
+
+        if let Some(p) = path {
+            paths.push(p);
+        }
+    }
+
+    Ok(paths)
+}
+
 /// Read ours/theirs/base content for a specific conflicted file from index stages.
 ///
 /// Uses git2 index stages (1=ancestor, 2=ours, 3=theirs) to read clean content
@@ -137,6 +143,16 @@ pub async fn get_conflict_content(
                         })
                     })
                     .unwrap_or_else(|| "MERGE_HEAD".to_string())
+            } else if let Some((label, oid)) = ["CHERRY_PICK_HEAD", "REVERT_HEAD"]
+                .iter()
+                .find_map(|f| {
+                    std::fs::read_to_string(repo.path().join(f))
+                        .ok()
+                        .map(|oid| (*f, oid.trim().to_string()))
+                })
+            {
+                // Label the picked commit by its short OID
+                format!("{} ({:.7})", label, oid)
             } else {
                 "MERGE_HEAD".to_string()
             }
@@ -179,8 +195,18 @@ pub async fn resolve_conflict_file(
     tokio::task::spawn_blocking(move || {
         let repo = git2::Repository::open(&repo_path)?;
 
-        // Verify a merge is in progress
-        if repo.state() != git2::RepositoryState::Merge {
+        // Verify a merge, rebase, cherry-pick or revert is in progress
+        if !matches!(
+            repo.state(),
+            git2::RepositoryState::Merge
+                | git2::RepositoryState::Rebase
+                | git2::RepositoryState::RebaseInteractive
+                | git2::RepositoryState::RebaseMerge
+                | git2::RepositoryState::CherryPick
+                | git2::RepositoryState::CherryPickSequence
+                | git2::RepositoryState::Revert
+                | git2::RepositoryState::RevertSequence
+        ) {
             return Err(GitError::NoMergeInProgress);
         }
 
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 
 use crate::git::history::CommitSummary;
+use crate::git::signing::CommitSignature;
 use crate::git::staging::FileChange;
 
 /// Valid conventional commit types.
@@ -624,7 +625,6 @@ pub async fn suggest_commit_type(
         let mut opts = git2::StatusOptions::new();
         opts.include_untracked(true)
             .recurse_untracked_dirs(true)
-            .exclude_submodules(true)
             .include_ignored(false);
 
         let statuses = repo.statuses(Some(&mut opts))?;
@@ -655,6 +655,7 @@ pub async fn suggest_commit_type(
                     status: file_status,
                     additions: None,
                     deletions: None,
+                    submodule: None,
                 });
             }
         }
@@ -702,16 +703,8 @@ pub async fn get_scope_suggestions(
             .filter_map(|oid| oid.ok())
             .filter_map(|oid| {
                 let commit = repo.find_commit(oid).ok()?;
-                let author = commit.author();
-
-                Some(CommitSummary {
-                    oid: oid.to_string(),
-                    short_oid: format!("{:.7}", oid),
-                    message_subject: commit.summary().unwrap_or("").to_string(),
-                    author_name: author.name().unwrap_or("Unknown").to_string(),
-                    author_email: author.email().unwrap_or("").to_string(),
-                    timestamp_ms: (author.when().seconds() as f64) * 1000.0,
-                })
+                // Only subjects are needed for scope extraction; skip verification
+                Some(CommitSummary::from_commit(&commit, CommitSignature::unsigned()))
             })
             .collect();
 
@@ -738,7 +731,6 @@ pub async fn infer_scope_from_staged(
         let mut opts = git2::StatusOptions::new();
         opts.include_untracked(true)
             .recurse_untracked_dirs(true)
-            .exclude_submodules(true)
             .include_ignored(false);
 
         let statuses = repo.statuses(Some(&mut opts))?;
@@ -760,6 +752,7 @@ pub async fn infer_scope_from_staged(
                     status: crate::git::staging::FileStatus::Modified,
                     additions: None,
                     deletions: None,
+                    submodule: None,
                 });
             }
         }
@@ -853,6 +846,7 @@ mod tests {
             status: crate::git::staging::FileStatus::Modified,
             additions: Some(10),
             deletions: Some(5),
+            submodule: None,
         }];
         let suggestion = infer_commit_type(&files);
         assert_eq!(suggestion.suggested_type, CommitType::Test);
@@ -866,6 +860,7 @@ mod tests {
             status: crate::git::staging::FileStatus::Modified,
             additions: Some(10),
             deletions: Some(5),
+            submodule: None,
         }];
         let suggestion = infer_commit_type(&files);
         assert_eq!(suggestion.suggested_type, CommitType::Docs);
@@ -893,12 +888,14 @@ mod tests {
                 status: crate::git::staging::FileStatus::Modified,
                 additions: None,
                 deletions: None,
+                submodule: None,
             },
             FileChange {
                 path: "src/git/commit.rs".to_string(),
                 status: crate::git::staging::FileStatus::Modified,
                 additions: None,
                 deletions: None,
+                submodule: None,
             },
         ];
         let scope = infer_scope_from_files(&files);
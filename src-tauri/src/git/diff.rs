@@ -6,6 +6,10 @@ use tauri::State;
 
 use crate::git::error::GitError;
 use crate::git::repository::RepositoryState;
+use crate::git::submodule::{
+    SubmoduleChange, describe_submodule_change, gitlink_in_tree, staged_submodule_change,
+    subproject_text, unstaged_submodule_change,
+};
 
 /// Origin type of a diff line.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -60,6 +64,8 @@ pub struct FileDiff {
     pub hunks: Vec<DiffHunk>,
     pub is_binary: bool,
     pub language: String,
+    /// Pointer change when the path is a submodule
+    pub submodule: Option<SubmoduleChange>,
 }
 
 /// Extract hunks (and optionally per-line detail) from a git2::Diff.
@@ -224,6 +230,18 @@ pub async fn get_file_diff(
     tokio::task::spawn_blocking(move || {
         let repo = git2::Repository::open(&repo_path)?;
 
+        let is_submodule = repo.find_submodule(&file_path).is_ok();
+        let submodule = if !is_submodule {
+            None
+        } else if staged {
+            staged_submodule_change(&repo, &file_path)
+        } else {
+            unstaged_submodule_change(&repo, &file_path)
+        };
+        if let Some(change) = submodule {
+            return Ok(submodule_file_diff(file_path, change));
+        }
+
         let mut diff_opts = git2::DiffOptions::new();
         diff_opts.context_lines(context_lines).pathspec(&file_path);
 
@@ -242,12 +260,26 @@ pub async fn get_file_diff(
             hunks,
             is_binary,
             language,
+            submodule: None,
         })
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
+/// Diff of a submodule pointer, shown the way git prints it ("Subproject commit <sha>").
+fn submodule_file_diff(path: String, change: SubmoduleChange) -> FileDiff {
+    FileDiff {
+        path,
+        old_content: subproject_text(change.old_oid.as_deref(), false),
+        new_content: subproject_text(change.new_oid.as_deref(), change.dirty),
+        hunks: Vec::new(),
+        is_binary: false,
+        language: "plaintext".to_string(),
+        submodule: Some(change),
+    }
+}
+
 /// Get per-line diff detail for a specific file.
 ///
 /// Returns enhanced hunk data with individual line information for interactive staging.
@@ -412,6 +444,16 @@ pub async fn get_commit_file_diff(
             None
         };
 
+        let old_gitlink = parent_tree
+            .as_ref()
+            .and_then(|tree| gitlink_in_tree(tree, &file_path));
+        let new_gitlink = gitlink_in_tree(&commit_tree, &file_path);
+        if old_gitlink.is_some() || new_gitlink.is_some() {
+            let change =
+                describe_submodule_change(&repo, &file_path, old_gitlink, new_gitlink, false);
+            return Ok(submodule_file_diff(file_path, change));
+        }
+
         // Get old content from parent tree
         let old_content = if let Some(ref tree) = parent_tree {
             get_blob_content(&repo, tree, &file_path)?
@@ -442,6 +484,7 @@ pub async fn get_commit_file_diff(
                 hunks,
                 is_binary: true,
                 language,
+                submodule: None,
             });
         }
 
@@ -452,12 +495,89 @@ pub async fn get_commit_file_diff(
             hunks,
             is_binary,
             language,
+            submodule: None,
         })
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
+/// Per-file diffs between two trees, like `get_commit_file_diff` for every file.
+///
+/// `old_tree` of None diffs against the empty tree.
+pub(crate) fn tree_file_diffs(
+    repo: &git2::Repository,
+    old_tree: Option<&git2::Tree>,
+    new_tree: &git2::Tree,
+    context_lines: u32,
+) -> Result<Vec<FileDiff>, GitError> {
+    let mut diff_opts = git2::DiffOptions::new();
+    diff_opts.context_lines(context_lines);
+    let diff = repo.diff_tree_to_tree(old_tree, Some(new_tree), Some(&mut diff_opts))?;
+
+    let mut files = Vec::new();
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let old_gitlink = old_tree.and_then(|tree| gitlink_in_tree(tree, &path));
+        let new_gitlink = gitlink_in_tree(new_tree, &path);
+        if old_gitlink.is_some() || new_gitlink.is_some() {
+            let change = describe_submodule_change(repo, &path, old_gitlink, new_gitlink, false);
+            files.push(submodule_file_diff(path, change));
+            continue;
+        }
+
+        // No patch is produced for binary files
+        let patch = git2::Patch::from_diff(&diff, idx)?;
+        let is_binary = patch.is_none() || delta.flags().is_binary();
+        let hunks = match &patch {
+            Some(patch) if !is_binary => (0..patch.num_hunks())
+                .map(|i| {
+                    let (hunk, _) = patch.hunk(i)?;
+                    Ok(DiffHunk {
+                        old_start: hunk.old_start(),
+                        old_lines: hunk.old_lines(),
+                        new_start: hunk.new_start(),
+                        new_lines: hunk.new_lines(),
+                        header: String::from_utf8_lossy(hunk.header()).to_string(),
+                    })
+                })
+                .collect::<Result<Vec<_>, git2::Error>>()?,
+            _ => Vec::new(),
+        };
+        let (old_content, new_content) = if is_binary {
+            (String::new(), String::new())
+        } else {
+            let old_path = delta
+                .old_file()
+                .path()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            let old_content = match old_tree {
+                Some(tree) => get_blob_content(repo, tree, &old_path)?,
+                None => String::new(),
+            };
+            (old_content, get_blob_content(repo, new_tree, &path)?)
+        };
+
+        files.push(FileDiff {
+            language: detect_language(&path),
+            path,
+            old_content,
+            new_content,
+            hunks,
+            is_binary,
+            submodule: None,
+        });
+    }
+    Ok(files)
+}
+
 /// Get a file's binary content as a base64 data URI from the working tree.
 ///
 /// Returns a data URI like `data:image/png;base64,...` for use in `<img>` tags.
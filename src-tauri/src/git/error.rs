@@ -91,6 +91,45 @@ pub enum GitError {
     #[error("File is not conflicted: {0}")]
     FileNotConflicted(String),
 
+    #[error("Resolve all conflicts before continuing: {0}")]
+    UnresolvedConflicts(String),
+
+    // Rebase errors
+    #[error("No rebase in progress")]
+    NoRebaseInProgress,
+
+    #[error("Another operation is already in progress: {0}")]
+    OperationInProgress(String),
+
+    #[error("Invalid rebase plan: {0}")]
+    InvalidRebasePlan(String),
+
+    // Cherry-pick / revert errors
+    #[error("No {0} in progress")]
+    NoOperationInProgress(String),
+
+    #[error("Commit {0} is a merge; choose a mainline parent")]
+    MainlineRequired(String),
+
+    // Operation errors
+    #[error("Not supported for this operation: {0}")]
+    UnsupportedOperation(String),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    // Hook errors
+    #[error("Hook failed: {0}")]
+    HookFailed(String),
+
+    // Signing errors
+    #[error("Signing failed: {0}")]
+    SigningFailed(String),
+
+    // Submodule errors
+    #[error("Submodule not found: {0}")]
+    SubmoduleNotFound(String),
+
     // Clone errors
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
@@ -113,6 +152,13 @@ pub enum GitError {
 
     #[error("Cannot partially stage a binary file")]
     BinaryPartialStaging,
+
+    // Config errors
+    #[error("Invalid config key: {0}")]
+    InvalidConfigKey(String),
+
+    #[error("Invalid config value: {0}")]
+    InvalidConfigValue(String),
 }
 
 impl From<git2::Error> for GitError {
@@ -9,6 +9,7 @@ use std::path::Path;
 use std::time::Duration;
 
 use crate::git::error::GitError;
+use crate::git::network::http_client;
 
 // ── Types ────────────────────────────────────────────────────────────────────
 
@@ -147,7 +148,7 @@ fn categorize_template(name: &str) -> &'static str {
 #[specta::specta]
 pub async fn list_gitignore_templates() -> Result<GitignoreTemplateList, GitError> {
     // Try GitHub API first
-    let client = reqwest::Client::new();
+    let client = http_client().map_err(GitError::NetworkError)?;
     let result = client
         .get("https://api.github.com/gitignore/templates")
         .header("User-Agent", "FlowForge")
@@ -201,7 +202,7 @@ pub async fn list_gitignore_templates() -> Result<GitignoreTemplateList, GitErro
 #[specta::specta]
 pub async fn get_gitignore_template(name: String) -> Result<GitignoreTemplate, GitError> {
     // Try GitHub API first
-    let client = reqwest::Client::new();
+    let client = http_client().map_err(GitError::NetworkError)?;
     let url = format!("https://api.github.com/gitignore/templates/{}", name);
     let result = client
         .get(&url)
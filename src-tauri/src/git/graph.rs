@@ -6,6 +6,7 @@ use tauri::State;
 
 use crate::git::error::GitError;
 use crate::git::repository::RepositoryState;
+use crate::git::signing::{verify_commit_signatures, CommitSignature};
 
 /// Branch type classification for Gitflow-based coloring.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
@@ -51,6 +52,8 @@ pub struct GraphNode {
     pub is_head_ancestor: bool,
     /// The "ideological branch" name that owns this commit for coloring
     pub ideological_branch: String,
+    /// Signature verification result
+    pub signature: CommitSignature,
 }
 
 /// An edge in the commit graph connecting parent and child commits.
@@ -217,11 +220,27 @@ async fn get_commit_graph_impl(
                         branch_names,
                         is_head_ancestor: head_ancestors.contains(&oid),
                         ideological_branch: String::new(), // Will be set below
+                        signature: CommitSignature::unsigned(), // Will be set below
                     });
                 }
             }
         }
 
+        // Verify signatures for the whole page at once (signed commits run gpg/ssh-keygen)
+        let page_oids: Vec<git2::Oid> = nodes
+            .iter()
+            .filter_map(|n| git2::Oid::from_str(&n.oid).ok())
+            .collect();
+        let mut signatures = verify_commit_signatures(&repo, &page_oids);
+        for node in &mut nodes {
+            if let Some(signature) = git2::Oid::from_str(&node.oid)
+                .ok()
+                .and_then(|oid| signatures.remove(&oid))
+            {
+                node.signature = signature;
+            }
+        }
+
         // ── 4. Ideological branch assignment (Ungit-style) ──
         // Sort refs by priority: local branches first, then by Gitflow priority.
         // Each ref traverses all parents depth-first; the first ref to reach
@@ -400,6 +419,7 @@ mod tests {
             branch_names: vec![],
             is_head_ancestor: is_head,
             ideological_branch: branch.to_string(),
+            signature: CommitSignature::unsigned(),
         }
     }
 
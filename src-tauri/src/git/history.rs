@@ -6,6 +6,8 @@ use tauri::State;
 
 use crate::git::error::GitError;
 use crate::git::repository::RepositoryState;
+use crate::git::signing::{verify_commit_signature, verify_commit_signatures, CommitSignature};
+use crate::git::submodule::{SubmoduleChange, describe_submodule_change};
 
 /// Summary of a commit for list display.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -18,6 +20,39 @@ pub struct CommitSummary {
     pub author_email: String,
     /// Unix timestamp in milliseconds (safe for JS Number up to year 275760)
     pub timestamp_ms: f64,
+    /// Signature verification result
+    pub signature: CommitSignature,
+}
+
+impl CommitSummary {
+    pub(crate) fn from_commit(commit: &git2::Commit, signature: CommitSignature) -> Self {
+        let author = commit.author();
+
+        Self {
+            oid: commit.id().to_string(),
+            short_oid: format!("{:.7}", commit.id()),
+            message_subject: commit.summary().unwrap_or("").to_string(),
+            author_name: author.name().unwrap_or("Unknown").to_string(),
+            author_email: author.email().unwrap_or("").to_string(),
+            timestamp_ms: (author.when().seconds() as f64) * 1000.0,
+            signature,
+        }
+    }
+
+    /// Summaries for a list of commits, verifying their signatures in one batch.
+    pub(crate) fn from_commits(repo: &git2::Repository, commits: &[git2::Commit]) -> Vec<Self> {
+        let oids: Vec<git2::Oid> = commits.iter().map(|c| c.id()).collect();
+        let mut signatures = verify_commit_signatures(repo, &oids);
+        commits
+            .iter()
+            .map(|commit| {
+                let signature = signatures
+                    .remove(&commit.id())
+                    .unwrap_or_else(CommitSignature::unsigned);
+                Self::from_commit(commit, signature)
+            })
+            .collect()
+    }
 }
 
 /// A file changed in a commit.
@@ -28,6 +63,8 @@ pub struct FileChanged {
     pub status: String,
     pub additions: i32,
     pub deletions: i32,
+    /// Pointer change when the path is a submodule
+    pub submodule: Option<SubmoduleChange>,
 }
 
 /// Full details of a commit.
@@ -47,6 +84,8 @@ pub struct CommitDetails {
     pub committer_timestamp_ms: f64,
     pub parent_oids: Vec<String>,
     pub files_changed: Vec<FileChanged>,
+    /// Signature verification result
+    pub signature: CommitSignature,
 }
 
 /// Get paginated commit history.
@@ -81,26 +120,14 @@ pub async fn get_commit_history(
         revwalk.push_head()?;
         revwalk.set_sorting(git2::Sort::TIME)?;
 
-        let commits: Vec<CommitSummary> = revwalk
+        let commits: Vec<git2::Commit> = revwalk
             .skip(skip as usize)
             .take(limit as usize)
             .filter_map(|oid| oid.ok())
-            .filter_map(|oid| {
-                let commit = repo.find_commit(oid).ok()?;
-                let author = commit.author();
-
-                Some(CommitSummary {
-                    oid: oid.to_string(),
-                    short_oid: format!("{:.7}", oid),
-                    message_subject: commit.summary().unwrap_or("").to_string(),
-                    author_name: author.name().unwrap_or("Unknown").to_string(),
-                    author_email: author.email().unwrap_or("").to_string(),
-                    timestamp_ms: (author.when().seconds() as f64) * 1000.0,
-                })
-            })
+            .filter_map(|oid| repo.find_commit(oid).ok())
             .collect();
 
-        Ok(commits)
+        Ok(CommitSummary::from_commits(&repo, &commits))
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
@@ -164,11 +191,27 @@ pub async fn get_commit_details(
                     _ => "modified",
                 };
 
+                let is_gitlink = |file: &git2::DiffFile| file.mode() == git2::FileMode::Commit;
+                let submodule = (is_gitlink(&delta.old_file()) || is_gitlink(&delta.new_file()))
+                    .then(|| {
+                        let side = |file: git2::DiffFile| {
+                            (is_gitlink(&file) && !file.id().is_zero()).then(|| file.id())
+                        };
+                        describe_submodule_change(
+                            &repo,
+                            &path,
+                            side(delta.old_file()),
+                            side(delta.new_file()),
+                            false,
+                        )
+                    });
+
                 files_changed.borrow_mut().push(FileChanged {
                     path: path.clone(),
                     status: status.to_string(),
                     additions: 0,
                     deletions: 0,
+                    submodule,
                 });
 
                 stats_map.borrow_mut().insert(path, (0, 0));
@@ -217,6 +260,7 @@ pub async fn get_commit_details(
             committer_timestamp_ms: (committer.when().seconds() as f64) * 1000.0,
             parent_oids,
             files_changed: files,
+            signature: verify_commit_signature(&repo, commit_oid),
         })
     })
     .await
@@ -255,10 +299,10 @@ pub async fn search_commits(
         revwalk.push_head()?;
         revwalk.set_sorting(git2::Sort::TIME)?;
 
-        let mut results = Vec::new();
+        let mut matches = Vec::new();
 
         for oid_result in revwalk {
-            if results.len() >= limit as usize {
+            if matches.len() >= limit as usize {
                 break;
             }
 
@@ -275,19 +319,11 @@ pub async fn search_commits(
             // Check if message contains query (case-insensitive)
             let message = commit.message().unwrap_or("");
             if message.to_lowercase().contains(&query_lower) {
-                let author = commit.author();
-                results.push(CommitSummary {
-                    oid: oid.to_string(),
-                    short_oid: format!("{:.7}", oid),
-                    message_subject: commit.summary().unwrap_or("").to_string(),
-                    author_name: author.name().unwrap_or("Unknown").to_string(),
-                    author_email: author.email().unwrap_or("").to_string(),
-                    timestamp_ms: (author.when().seconds() as f64) * 1000.0,
-                });
+                matches.push(commit);
             }
         }
 
-        Ok(results)
+        Ok(CommitSummary::from_commits(&repo, &matches))
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
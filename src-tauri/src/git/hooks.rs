@@ -0,0 +1,340 @@
+//! Git hook discovery and execution.
+//!
+//! libgit2 never runs hooks, so commands that create commits, merge or push
+//! run them explicitly through [`run_hook`]. Hooks are looked up in
+//! `core.hooksPath` (or `.git/hooks`), run from the working tree root with
+//! git's arguments and environment, and their output is streamed line by
+//! line while it is also collected for the error returned on failure.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use crate::git::error::GitError;
+
+/// Progress events emitted while hooks run.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", tag = "event", content = "data")]
+pub enum HookEvent {
+    Started { hook: String },
+    Output { hook: String, line: String },
+    Finished { hook: String, success: bool },
+}
+
+/// Directory hooks are read from: `core.hooksPath` if set, else `<common git dir>/hooks`.
+pub(crate) fn hooks_dir(repo: &git2::Repository) -> PathBuf {
+    let configured = repo
+        .config()
+        .and_then(|config| config.get_path("core.hooksPath"))
+        .ok();
+
+    match configured {
+        // A relative hooksPath is relative to where hooks run (the working tree root)
+        Some(path) if path.is_relative() => hook_cwd(repo).join(path),
+        Some(path) => path,
+        None => repo.commondir().join("hooks"),
+    }
+}
+
+/// Working directory hooks run in.
+fn hook_cwd(repo: &git2::Repository) -> PathBuf {
+    repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Path of an installed, executable hook (sample hooks are ignored).
+pub(crate) fn find_hook(repo: &git2::Repository, name: &str) -> Option<PathBuf> {
+    let path = hooks_dir(repo).join(name);
+    is_executable(&path).then_some(path)
+}
+
+fn hook_command(path: &Path) -> Command {
+    if cfg!(windows) {
+        // Hooks are shell scripts; Git for Windows ships `sh`
+        let mut command = Command::new("sh");
+        command.arg(path);
+        command
+    } else {
+        Command::new(path)
+    }
+}
+
+/// Run a hook if it is installed.
+///
+/// Returns `Ok(false)` when the hook does not exist, `Ok(true)` when it ran
+/// and succeeded, and `HookFailed` with the hook's combined output when it
+/// exited non-zero.
+pub(crate) fn run_hook(
+    repo: &git2::Repository,
+    name: &str,
+    args: &[&str],
+    env: &[(&str, &Path)],
+    stdin: Option<&str>,
+    on_event: &(dyn Fn(HookEvent) + Sync),
+) -> Result<bool, GitError> {
+    let Some(path) = find_hook(repo, name) else {
+        return Ok(false);
+    };
+
+    on_event(HookEvent::Started {
+        hook: name.to_string(),
+    });
+
+    let mut command = hook_command(&path);
+    command
+        .args(args)
+        .current_dir(hook_cwd(repo))
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    for (key, value) in env {
+        command.env(key, value);
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| GitError::HookFailed(format!("Could not run {} hook: {}", name, e)))?;
+
+    let output = Mutex::new(String::new());
+    let input = stdin.zip(child.stdin.take());
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    // Input is written while the output is drained so a hook that prints
+    // before reading cannot fill its pipe and stall both sides
+    std::thread::scope(|scope| {
+        if let Some((input, mut pipe)) = input {
+            scope.spawn(move || {
+                // A hook may exit without reading its input; that is not an error
+                let _ = pipe.write_all(input.as_bytes());
+            });
+        }
+
+        let forward = |reader: Box<dyn std::io::Read + Send>| {
+            for line in BufReader::new(reader).lines().map_while(Result::ok) {
+                if let Ok(mut collected) = output.lock() {
+                    collected.push_str(&line);
+                    collected.push('\n');
+                }
+                on_event(HookEvent::Output {
+                    hook: name.to_string(),
+                    line,
+                });
+            }
+        };
+
+        if let Some(stdout) = stdout {
+            scope.spawn(move || forward(Box::new(stdout)));
+        }
+        if let Some(stderr) = stderr {
+            scope.spawn(move || forward(Box::new(stderr)));
+        }
+    });
+
+    let status = child
+        .wait()
+        .map_err(|e| GitError::HookFailed(format!("Could not run {} hook: {}", name, e)))?;
+
+    on_event(HookEvent::Finished {
+        hook: name.to_string(),
+        success: status.success(),
+    });
+
+    if status.success() {
+        return Ok(true);
+    }
+
+    let output = output.into_inner().unwrap_or_default();
+    let code = status
+        .code()
+        .map(|c| c.to_string())
+        .unwrap_or_else(|| "signal".to_string());
+    Err(GitError::HookFailed(format!(
+        "{} hook exited with status {}\n{}",
+        name,
+        code,
+        output.trim_end()
+    )))
+}
+
+/// Environment git provides to commit hooks.
+pub(crate) fn commit_hook_env(repo: &git2::Repository) -> Vec<(&'static str, PathBuf)> {
+    vec![
+        ("GIT_INDEX_FILE", repo.path().join("index")),
+        ("GIT_EDITOR", PathBuf::from(":")),
+    ]
+}
+
+/// Run `prepare-commit-msg` and `commit-msg` on a message and return the
+/// (possibly rewritten) result.
+///
+/// The message is exchanged through `.git/COMMIT_EDITMSG`, like git does.
+/// `source` is passed to `prepare-commit-msg` ("message", "merge", ...).
+pub(crate) fn run_message_hooks(
+    repo: &git2::Repository,
+    message: &str,
+    source: &str,
+    env: &[(&str, &Path)],
+    on_event: &(dyn Fn(HookEvent) + Sync),
+) -> Result<String, GitError> {
+    if find_hook(repo, "prepare-commit-msg").is_none() && find_hook(repo, "commit-msg").is_none() {
+        return Ok(message.to_string());
+    }
+
+    let msg_path = repo.path().join("COMMIT_EDITMSG");
+    std::fs::write(&msg_path, message)
+        .map_err(|e| GitError::OperationFailed(format!("Failed to write COMMIT_EDITMSG: {}", e)))?;
+    let msg_arg = msg_path.to_string_lossy().to_string();
+
+    run_hook(
+        repo,
+        "prepare-commit-msg",
+        &[&msg_arg, source],
+        env,
+        None,
+        on_event,
+    )?;
+    run_hook(repo, "commit-msg", &[&msg_arg], env, None, on_event)?;
+
+    let edited = std::fs::read_to_string(&msg_path)
+        .map_err(|e| GitError::OperationFailed(format!("Failed to read COMMIT_EDITMSG: {}", e)))?;
+    if edited.trim().is_empty() {
+        return Err(GitError::OperationFailed(
+            "Aborting commit due to empty commit message".to_string(),
+        ));
+    }
+
+    Ok(edited)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::git::test_support::setup_repo;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn install_hook(dir: &Path, name: &str, script: &str) {
+        fs::create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_missing_hook_is_skipped() {
+        let (_dir, repo) = setup_repo();
+        assert!(!run_hook(&repo, "pre-commit", &[], &[], None, &|_| {}).unwrap());
+    }
+
+    #[test]
+    fn test_failing_hook_returns_output() {
+        let (_dir, repo) = setup_repo();
+        install_hook(
+            &repo.path().join("hooks"),
+            "pre-commit",
+            "echo 'lint failed' >&2; exit 3",
+        );
+
+        let events = Mutex::new(Vec::new());
+        let err = run_hook(&repo, "pre-commit", &[], &[], None, &|e| {
+            events.lock().unwrap().push(e)
+        })
+        .unwrap_err();
+
+        match err {
+            GitError::HookFailed(message) => {
+                assert!(message.contains("status 3"));
+                assert!(message.contains("lint failed"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        let events = events.into_inner().unwrap();
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, HookEvent::Output { line, .. } if line == "lint failed"))
+        );
+    }
+
+    #[test]
+    fn test_hooks_path_and_stdin() {
+        let (dir, repo) = setup_repo();
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", "custom-hooks")
+            .unwrap();
+        install_hook(
+            &dir.path().join("custom-hooks"),
+            "pre-push",
+            "read line; echo \"$1 $line\" > pushed.txt",
+        );
+
+        assert!(
+            run_hook(
+                &repo,
+                "pre-push",
+                &["origin"],
+                &[],
+                Some("refs/heads/main abc\n"),
+                &|_| {}
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("pushed.txt")).unwrap(),
+            "origin refs/heads/main abc\n"
+        );
+    }
+
+    #[test]
+    fn test_hook_printing_before_reading_large_input_does_not_stall() {
+        let (dir, repo) = setup_repo();
+        install_hook(
+            &dir.path().join(".git/hooks"),
+            "pre-push",
+            "yes | head -n 50000; wc -l > lines.txt",
+        );
+
+        let input = "refs/heads/main abc refs/heads/main def\n".repeat(5000);
+        assert!(run_hook(&repo, "pre-push", &[], &[], Some(&input), &|_| {}).unwrap());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("lines.txt"))
+                .unwrap()
+                .trim(),
+            "5000"
+        );
+    }
+
+    #[test]
+    fn test_commit_msg_hook_rewrites_message() {
+        let (_dir, repo) = setup_repo();
+        install_hook(
+            &repo.path().join("hooks"),
+            "commit-msg",
+            "echo 'Signed-off-by: Hook' >> \"$1\"",
+        );
+
+        let message = run_message_hooks(&repo, "Subject\n", "message", &[], &|_| {}).unwrap();
+        assert_eq!(message, "Subject\nSigned-off-by: Hook\n");
+    }
+}
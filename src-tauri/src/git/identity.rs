@@ -0,0 +1,622 @@
+//! Identity profiles.
+//!
+//! A profile bundles a commit identity (name, email, signing key) with the
+//! GitHub account it belongs to. Profiles are stored by the app and reach
+//! repositories in two ways: applied to one repository, which writes
+//! `user.*` into its local config, or assigned to directories, which adds a
+//! managed `[includeIf "gitdir:<dir>/"]` block to the global config pointing
+//! at a per-profile include file. `create_commit` checks the effective
+//! identity against the profile assigned to the repository before committing.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::git::credentials::home_dir;
+use crate::git::error::GitError;
+use crate::git::repository::RepositoryState;
+
+/// Profile store, relative to the app config directory.
+const STORE_FILE: &str = "identity-profiles.json";
+
+/// Include files of directory-assigned profiles, relative to the app config directory.
+const INCLUDE_DIR: &str = "identities";
+
+/// Serializes store reads and writes across concurrent commands.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A commit identity the user can switch between.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityProfile {
+    /// Stable identifier; derived from the label when saving a new profile
+    #[serde(default)]
+    pub id: String,
+    /// Display name of the profile (e.g. "Work")
+    pub label: String,
+    /// Value for user.name
+    pub user_name: String,
+    /// Value for user.email
+    pub user_email: String,
+    /// Value for user.signingKey, if commits are signed
+    pub signing_key: Option<String>,
+    /// GitHub login the profile pushes as
+    pub github_account: Option<String>,
+    /// Directories whose repositories use this profile via `includeIf "gitdir:"`
+    #[serde(default)]
+    pub directories: Vec<String>,
+}
+
+/// The identity a repository commits with, and the profile it should use.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryIdentity {
+    /// Effective user.name
+    pub user_name: Option<String>,
+    /// Effective user.email
+    pub user_email: Option<String>,
+    /// Effective user.signingKey
+    pub signing_key: Option<String>,
+    /// Profile assigned to the repository or one of its parent directories
+    pub profile: Option<IdentityProfile>,
+    /// False if a profile is assigned and the effective identity differs from it
+    pub matches: bool,
+}
+
+/// On-disk store.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileStore {
+    profiles: Vec<IdentityProfile>,
+    /// Git directory of repositories a profile was applied to, to profile id
+    repositories: BTreeMap<String, String>,
+}
+
+/// Where profiles live - one per app instance.
+#[derive(Debug, Clone)]
+pub struct IdentityState {
+    dir: PathBuf,
+}
+
+impl IdentityState {
+    /// Store profiles in `dir`, normally the app config directory.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn store_path(&self) -> PathBuf {
+        self.dir.join(STORE_FILE)
+    }
+
+    fn include_dir(&self) -> PathBuf {
+        self.dir.join(INCLUDE_DIR)
+    }
+
+    fn load(&self) -> Result<ProfileStore, GitError> {
+        match fs::read_to_string(self.store_path()) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| {
+                GitError::Internal(format!("Failed to parse identity profiles: {}", e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ProfileStore::default()),
+            Err(e) => Err(GitError::Internal(format!(
+                "Failed to read identity profiles: {}",
+                e
+            ))),
+        }
+    }
+
+    fn save(&self, store: &ProfileStore) -> Result<(), GitError> {
+        let write = || -> std::io::Result<()> {
+            fs::create_dir_all(&self.dir)?;
+            let json = serde_json::to_string_pretty(store).map_err(std::io::Error::other)?;
+            fs::write(self.store_path(), json)
+        };
+        write().map_err(|e| GitError::Internal(format!("Failed to write identity profiles: {}", e)))
+    }
+
+    /// Profile that should be in effect for the repository at `repo_path`.
+    pub fn assigned_profile(&self, repo_path: &Path) -> Result<Option<IdentityProfile>, GitError> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let store = self.load()?;
+        let repo = git2::Repository::open(repo_path)?;
+        Ok(assigned_profile(&store, repo.path()))
+    }
+}
+
+/// Key under which a repository's assignment is stored.
+fn repo_key(git_dir: &Path) -> String {
+    fs::canonicalize(git_dir)
+        .unwrap_or_else(|_| git_dir.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// A profile directory as an absolute path, expanding `~/`.
+fn expand_directory(directory: &str) -> PathBuf {
+    match (directory.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(directory),
+    }
+}
+
+/// The profile applied to the repository, else the profile with the
+/// deepest directory containing it (like the last matching `includeIf`).
+fn assigned_profile(store: &ProfileStore, git_dir: &Path) -> Option<IdentityProfile> {
+    let key = repo_key(git_dir);
+    if let Some(id) = store.repositories.get(&key) {
+        return store.profiles.iter().find(|p| &p.id == id).cloned();
+    }
+
+    let git_dir = Path::new(&key);
+    store
+        .profiles
+        .iter()
+        .flat_map(|profile| {
+            profile.directories.iter().filter_map(move |directory| {
+                let directory = expand_directory(directory);
+                let directory = fs::canonicalize(&directory).unwrap_or(directory);
+                git_dir
+                    .starts_with(&directory)
+                    .then(|| (directory.components().count(), profile))
+            })
+        })
+        .max_by_key(|(depth, _)| *depth)
+        .map(|(_, profile)| profile.clone())
+}
+
+/// Id for a new profile: the label in lowercase with dashes, made unique.
+fn profile_id(label: &str, profiles: &[IdentityProfile]) -> String {
+    let slug: String = label
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug = if slug.is_empty() {
+        "profile".to_string()
+    } else {
+        slug
+    };
+
+    let taken = |id: &str| profiles.iter().any(|p| p.id == id);
+    if !taken(&slug) {
+        return slug;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", slug, n))
+        .find(|id| !taken(id))
+        .expect("unbounded range")
+}
+
+/// Escape `text` for use in a libgit2 config value pattern (POSIX ERE).
+fn escape_pattern(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.[]{}()*+?^$|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Reject profiles that cannot be written to an include file as-is.
+///
+/// Values are quoted in the include file, but a newline or other control
+/// character would still end the entry early and let the rest of the value
+/// be read as further config.
+fn validate_profile(profile: &IdentityProfile) -> Result<(), GitError> {
+    if profile.user_name.trim().is_empty() || profile.user_email.trim().is_empty() {
+        return Err(GitError::InvalidConfigValue(
+            "an identity profile needs a name and an email".to_string(),
+        ));
+    }
+    let fields = [
+        ("label", Some(&profile.label)),
+        ("name", Some(&profile.user_name)),
+        ("email", Some(&profile.user_email)),
+        ("signing key", profile.signing_key.as_ref()),
+    ];
+    for (field, value) in fields {
+        if value.is_some_and(|v| v.chars().any(char::is_control)) {
+            return Err(GitError::InvalidConfigValue(format!(
+                "the identity profile {} must not contain control characters",
+                field
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Contents of a profile's include file.
+fn include_contents(profile: &IdentityProfile) -> String {
+    let quote = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut contents = format!(
+        "# Managed by FlowForge for identity profile '{}'\n[user]\n\tname = \"{}\"\n\temail = \"{}\"\n",
+        profile.label,
+        quote(&profile.user_name),
+        quote(&profile.user_email)
+    );
+    if let Some(key) = &profile.signing_key {
+        contents.push_str(&format!("\tsigningKey = \"{}\"\n", quote(key)));
+    }
+    contents
+}
+
+/// Rewrite the managed `includeIf "gitdir:"` blocks in `global` and the
+/// include files in `include_dir` to match the profiles' directories.
+///
+/// Blocks are recognised by their path pointing into `include_dir`; the
+/// user's own `includeIf` blocks are left alone.
+fn sync_includes(
+    global: &mut git2::Config,
+    include_dir: &Path,
+    profiles: &[IdentityProfile],
+) -> Result<(), GitError> {
+    let managed = format!("^{}", escape_pattern(&include_dir.to_string_lossy()));
+    let mut keys = Vec::new();
+    let mut entries = global.entries(Some(r"includeif\.gitdir.*\.path"))?;
+    while let Some(entry) = entries.next() {
+        let entry = entry?;
+        if let Some(name) = entry.name()
+            && entry
+                .value()
+                .is_some_and(|v| Path::new(v).starts_with(include_dir))
+            && !keys.iter().any(|k| k == name)
+        {
+            keys.push(name.to_string());
+        }
+    }
+    drop(entries);
+    for key in keys {
+        global.remove_multivar(&key, &managed)?;
+    }
+
+    if include_dir.exists() {
+        fs::remove_dir_all(include_dir)
+            .map_err(|e| GitError::Internal(format!("Failed to clear include files: {}", e)))?;
+    }
+    for profile in profiles.iter().filter(|p| !p.directories.is_empty()) {
+        fs::create_dir_all(include_dir)
+            .map_err(|e| GitError::Internal(format!("Failed to write include file: {}", e)))?;
+        let file = include_dir.join(format!("{}.gitconfig", profile.id));
+        fs::write(&file, include_contents(profile))
+            .map_err(|e| GitError::Internal(format!("Failed to write include file: {}", e)))?;
+
+        for directory in &profile.directories {
+            // A trailing slash makes git match everything below the directory
+            let directory = directory.trim_end_matches('/');
+            let key = format!("includeIf.gitdir:{}/.path", directory);
+            global.set_multivar(&key, "$^", &file.to_string_lossy())?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a profile's identity into a repository's local config.
+fn apply_to_repository(repo: &git2::Repository, profile: &IdentityProfile) -> Result<(), GitError> {
+    let mut local = repo.config()?.open_level(git2::ConfigLevel::Local)?;
+    local.set_str("user.name", &profile.user_name)?;
+    local.set_str("user.email", &profile.user_email)?;
+    match &profile.signing_key {
+        Some(key) => local.set_str("user.signingKey", key)?,
+        None => match local.remove("user.signingKey") {
+            Err(e) if e.code() != git2::ErrorCode::NotFound => return Err(e.into()),
+            _ => {}
+        },
+    }
+    Ok(())
+}
+
+/// The repository's effective identity compared with `profile`.
+fn repository_identity(
+    repo: &git2::Repository,
+    profile: Option<IdentityProfile>,
+) -> Result<RepositoryIdentity, GitError> {
+    // Snapshot so values from includeIf files are read consistently
+    let config = repo.config()?.snapshot()?;
+    let user_name = config.get_string("user.name").ok();
+    let user_email = config.get_string("user.email").ok();
+    let signing_key = config.get_string("user.signingkey").ok();
+    let matches = profile.as_ref().is_none_or(|p| {
+        user_name.as_deref() == Some(p.user_name.as_str())
+            && user_email
+                .as_deref()
+                .is_some_and(|email| email.eq_ignore_ascii_case(&p.user_email))
+    });
+    Ok(RepositoryIdentity {
+        user_name,
+        user_email,
+        signing_key,
+        profile,
+        matches,
+    })
+}
+
+/// A warning if the repository would commit as someone other than its
+/// assigned profile.
+pub fn identity_warning(
+    repo: &git2::Repository,
+    profile: Option<IdentityProfile>,
+) -> Result<Option<String>, GitError> {
+    let identity = repository_identity(repo, profile)?;
+    Ok(match identity.profile {
+        Some(profile) if !identity.matches => Some(format!(
+            "Committed as {} <{}>, but identity profile '{}' expects {} <{}>",
+            identity.user_name.as_deref().unwrap_or("(no name)"),
+            identity.user_email.as_deref().unwrap_or("no email"),
+            profile.label,
+            profile.user_name,
+            profile.user_email
+        )),
+        _ => None,
+    })
+}
+
+/// The global config file, where managed `includeIf` blocks live.
+fn open_global_config() -> Result<git2::Config, GitError> {
+    git2::Config::open_default()?
+        .open_level(git2::ConfigLevel::Global)
+        .map_err(|e| GitError::OperationFailed(format!("Failed to open global config: {}", e)))
+}
+
+/// List the stored identity profiles.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_identity_profiles(
+    identity: State<'_, IdentityState>,
+) -> Result<Vec<IdentityProfile>, GitError> {
+    let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(identity.load()?.profiles)
+}
+
+/// Create or update a profile and rewrite the managed `includeIf` blocks.
+///
+/// A profile with an empty or unknown id is added; its id is returned.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_identity_profile(
+    mut profile: IdentityProfile,
+    identity: State<'_, IdentityState>,
+) -> Result<IdentityProfile, GitError> {
+    validate_profile(&profile)?;
+
+    let identity = identity.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut store = identity.load()?;
+        match store.profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) if !profile.id.is_empty() => *existing = profile.clone(),
+            _ => {
+                profile.id = profile_id(&profile.label, &store.profiles);
+                store.profiles.push(profile.clone());
+            }
+        }
+        sync_includes(
+            &mut open_global_config()?,
+            &identity.include_dir(),
+            &store.profiles,
+        )?;
+        identity.save(&store)?;
+        Ok(profile)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Delete a profile, its repository assignments and its `includeIf` blocks.
+///
+/// Local configs it was applied to keep their `user.*` values.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_identity_profile(
+    id: String,
+    identity: State<'_, IdentityState>,
+) -> Result<(), GitError> {
+    let identity = identity.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut store = identity.load()?;
+        let count = store.profiles.len();
+        store.profiles.retain(|p| p.id != id);
+        if store.profiles.len() == count {
+            return Err(GitError::NotFound(format!(
+                "Identity profile not found: {}",
+                id
+            )));
+        }
+        store.repositories.retain(|_, profile_id| *profile_id != id);
+        sync_includes(
+            &mut open_global_config()?,
+            &identity.include_dir(),
+            &store.profiles,
+        )?;
+        identity.save(&store)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Apply a profile to the open repository: write its identity into the
+/// local config and remember the assignment for the commit check.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_identity_profile(
+    id: String,
+    state: State<'_, RepositoryState>,
+    identity: State<'_, IdentityState>,
+) -> Result<RepositoryIdentity, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    let identity = identity.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut store = identity.load()?;
+        let profile = store
+            .profiles
+            .iter()
+            .find(|p| p.id == id)
+            .cloned()
+            .ok_or_else(|| GitError::NotFound(format!("Identity profile not found: {}", id)))?;
+
+        let repo = git2::Repository::open(&repo_path)?;
+        apply_to_repository(&repo, &profile)?;
+        store.repositories.insert(repo_key(repo.path()), id);
+        identity.save(&store)?;
+        repository_identity(&repo, Some(profile))
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// The open repository's effective identity and the profile assigned to it.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_repository_identity(
+    state: State<'_, RepositoryState>,
+    identity: State<'_, IdentityState>,
+) -> Result<RepositoryIdentity, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    let identity = identity.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let profile = identity.assigned_profile(&repo_path)?;
+        let repo = git2::Repository::open(&repo_path)?;
+        repository_identity(&repo, profile)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn profile(label: &str, email: &str, directories: &[&str]) -> IdentityProfile {
+        IdentityProfile {
+            id: profile_id(label, &[]),
+            label: label.to_string(),
+            user_name: label.to_string(),
+            user_email: email.to_string(),
+            signing_key: None,
+            github_account: None,
+            directories: directories.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_profile_ids_are_unique_slugs() {
+        let existing = vec![profile("Work", "me@work.example", &[])];
+        assert_eq!(profile_id("Open Source!", &existing), "open-source");
+        assert_eq!(profile_id("work", &existing), "work-2");
+        assert_eq!(profile_id("***", &existing), "profile");
+    }
+
+    #[test]
+    fn test_profiles_with_control_characters_are_rejected() {
+        assert!(validate_profile(&profile("Work", "me@work.example", &[])).is_ok());
+        assert!(validate_profile(&profile("Work", " ", &[])).is_err());
+
+        let injected = profile("Work\n[core]\n\tsshCommand = evil", "me@work.example", &[]);
+        assert!(matches!(
+            validate_profile(&injected),
+            Err(GitError::InvalidConfigValue(_))
+        ));
+        let mut key = profile("Work", "me@work.example", &[]);
+        key.signing_key = Some("ABCD\r\n[alias]".to_string());
+        assert!(validate_profile(&key).is_err());
+    }
+
+    #[test]
+    fn test_sync_includes_replaces_only_managed_blocks() {
+        let dir = TempDir::new().unwrap();
+        let global_path = dir.path().join("gitconfig");
+        let include_dir = dir.path().join("app").join(INCLUDE_DIR);
+        std::fs::write(
+            &global_path,
+            "[includeIf \"gitdir:~/own/\"]\n\tpath = ~/own.gitconfig\n",
+        )
+        .unwrap();
+        let mut global = git2::Config::open(&global_path).unwrap();
+
+        let work = profile("Work", "me@work.example", &["/src/work", "/src/client/"]);
+        sync_includes(&mut global, &include_dir, std::slice::from_ref(&work)).unwrap();
+        let contents = std::fs::read_to_string(&global_path).unwrap();
+        assert!(contents.contains("gitdir:/src/work/"));
+        assert!(contents.contains("gitdir:/src/client/"));
+        assert!(contents.contains("~/own.gitconfig"));
+        let include = std::fs::read_to_string(include_dir.join("work.gitconfig")).unwrap();
+        assert!(include.contains("email = \"me@work.example\""));
+
+        // Moving the profile to another directory drops the old blocks
+        let moved = IdentityProfile {
+            directories: vec!["/elsewhere".to_string()],
+            ..work
+        };
+        sync_includes(&mut global, &include_dir, &[moved]).unwrap();
+        let global = git2::Config::open(&global_path).unwrap();
+        let mut paths = Vec::new();
+        global
+            .entries(Some(r"includeif\..*\.path"))
+            .unwrap()
+            .for_each(|e| {
+                paths.push((
+                    e.name().unwrap().to_string(),
+                    e.value().unwrap().to_string(),
+                ))
+            })
+            .unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].1, "~/own.gitconfig");
+        assert_eq!(paths[1].0, "includeif.gitdir:/elsewhere/.path");
+    }
+
+    #[test]
+    fn test_commit_check_uses_assigned_profile() {
+        let dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path().join("work").join("repo")).unwrap();
+        let mut config = repo
+            .config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap();
+        config.set_str("user.name", "Someone").unwrap();
+        config
+            .set_str("user.email", "someone@home.example")
+            .unwrap();
+
+        let work_dir = dir.path().join("work");
+        let mut store = ProfileStore {
+            profiles: vec![
+                profile("Work", "me@work.example", &[work_dir.to_str().unwrap()]),
+                profile("Home", "someone@home.example", &[]),
+            ],
+            ..Default::default()
+        };
+        let assigned = assigned_profile(&store, repo.path());
+        assert_eq!(assigned.as_ref().map(|p| p.id.as_str()), Some("work"));
+        let warning = identity_warning(&repo, assigned).unwrap().unwrap();
+        assert!(warning.contains("me@work.example"));
+
+        // A profile applied to the repository wins over its directory
+        let home = store.profiles[1].clone();
+        apply_to_repository(&repo, &home).unwrap();
+        store
+            .repositories
+            .insert(repo_key(repo.path()), home.id.clone());
+        let assigned = assigned_profile(&store, repo.path());
+        assert_eq!(assigned.as_ref().map(|p| p.id.as_str()), Some("home"));
+        assert_eq!(identity_warning(&repo, assigned).unwrap(), None);
+        assert_eq!(identity_warning(&repo, None).unwrap(), None);
+    }
+}
@@ -1,10 +1,17 @@
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::path::Path;
 use tauri::State;
+use tauri::ipc::Channel;
 
+use crate::git::cherry_pick::clean_message;
+use crate::git::conflict::collect_conflicted_paths;
 use crate::git::error::GitError;
+use crate::git::hooks::{HookEvent, commit_hook_env, run_hook, run_message_hooks};
 use crate::git::repository::RepositoryState;
+use crate::git::signing::create_commit_signed;
+use crate::git::undo::record_operation;
 
 /// Result of merge analysis.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -49,10 +56,14 @@ pub struct MergeStatus {
 }
 
 /// Merge a source branch into the current branch.
+///
+/// Runs `pre-merge-commit`, `prepare-commit-msg`, `commit-msg` and
+/// `post-merge` hooks, streaming their output to `on_hook_output`.
 #[tauri::command]
 #[specta::specta]
 pub async fn merge_branch(
     source_branch: String,
+    on_hook_output: Option<Channel<HookEvent>>,
     state: State<'_, RepositoryState>,
 ) -> Result<MergeResult, GitError> {
     let repo_path = state
@@ -60,145 +71,139 @@ pub async fn merge_branch(
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-
-        // Find source branch and get its commit
-        let branch = repo
-            .find_branch(&source_branch, BranchType::Local)
-            .map_err(|_| GitError::BranchNotFound(source_branch.clone()))?;
-        let source_commit = branch.get().peel_to_commit()?;
-
-        // Get annotated commit for merge analysis
-        let annotated = repo.find_annotated_commit(source_commit.id())?;
+    record_operation(
+        repo_path.clone(),
+        format!("Merge '{}'", source_branch),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            let on_hook_event = |event| {
+                if let Some(channel) = &on_hook_output {
+                    let _ = channel.send(event);
+                }
+            };
+
+            // Find source branch and get its commit
+            let branch = repo
+                .find_branch(&source_branch, BranchType::Local)
+                .map_err(|_| GitError::BranchNotFound(source_branch.clone()))?;
+            let source_commit = branch.get().peel_to_commit()?;
+
+            // Get annotated commit for merge analysis
+            let annotated = repo.find_annotated_commit(source_commit.id())?;
+
+            // Run merge analysis
+            let (analysis, _preference) = repo.merge_analysis(&[&annotated])?;
+
+            // Handle up-to-date case
+            if analysis.is_up_to_date() {
+                return Ok(MergeResult {
+                    success: true,
+                    analysis: MergeAnalysisResult::UpToDate,
+                    commit_oid: None,
+                    fast_forwarded: false,
+                    has_conflicts: false,
+                    conflicted_files: vec![],
+                });
+            }
 
-        // Run merge analysis
-        let (analysis, _preference) = repo.merge_analysis(&[&annotated])?;
+            // Handle unborn HEAD
+            if analysis.is_unborn() {
+                return Ok(MergeResult {
+                    success: false,
+                    analysis: MergeAnalysisResult::Unborn,
+                    commit_oid: None,
+                    fast_forwarded: false,
+                    has_conflicts: false,
+                    conflicted_files: vec![],
+                });
+            }
 
-        // Handle up-to-date case
-        if analysis.is_up_to_date() {
-            return Ok(MergeResult {
-                success: true,
-                analysis: MergeAnalysisResult::UpToDate,
-                commit_oid: None,
-                fast_forwarded: false,
-                has_conflicts: false,
-                conflicted_files: vec![],
-            });
-        }
-
-        // Handle unborn HEAD
-        if analysis.is_unborn() {
-            return Ok(MergeResult {
-                success: false,
-                analysis: MergeAnalysisResult::Unborn,
-                commit_oid: None,
-                fast_forwarded: false,
-                has_conflicts: false,
-                conflicted_files: vec![],
-            });
-        }
-
-        // Handle fast-forward
-        if analysis.is_fast_forward() {
-            let head = repo.head()?;
-            let refname = head
-                .name()
-                .ok_or_else(|| GitError::Internal("HEAD has no name".to_string()))?;
-
-            // Update reference to point to source commit
-            repo.reference(
-                refname,
-                source_commit.id(),
-                true,
-                &format!("merge {}: fast-forward", source_branch),
-            )?;
+            // Handle fast-forward
+            if analysis.is_fast_forward() {
+                let head = repo.head()?;
+                let refname = head
+                    .name()
+                    .ok_or_else(|| GitError::Internal("HEAD has no name".to_string()))?;
+
+                // Update reference to point to source commit
+                repo.reference(
+                    refname,
+                    source_commit.id(),
+                    true,
+                    &format!("merge {}: fast-forward", source_branch),
+                )?;
+
+                // Update working directory
+                repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+
+                // post-merge cannot affect the outcome; "0" means not a squash merge
+                let _ = run_hook(&repo, "post-merge", &["0"], &[], None, &on_hook_event);
+
+                return Ok(MergeResult {
+                    success: true,
+                    analysis: MergeAnalysisResult::FastForward,
+                    commit_oid: Some(source_commit.id().to_string()),
+                    fast_forwarded: true,
+                    has_conflicts: false,
+                    conflicted_files: vec![],
+                });
+            }
 
-            // Update working directory
-            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+            // Normal merge
+            repo.merge(&[&annotated], None, None)?;
 
-            return Ok(MergeResult {
-                success: true,
-                analysis: MergeAnalysisResult::FastForward,
-                commit_oid: Some(source_commit.id().to_string()),
-                fast_forwarded: true,
-                has_conflicts: false,
-                conflicted_files: vec![],
-            });
-        }
-
-        // Normal merge
-        repo.merge(&[&annotated], None, None)?;
-
-        // Check for conflicts
-        let index = repo.index()?;
-        if index.has_conflicts() {
-            let mut conflicted_files = Vec::new();
-            for conflict in index.conflicts()? {
-                if let Ok(conflict) = conflict {
-                    if let Some(ancestor) = conflict.ancestor {
-                        if let Some(path) = std::str::from_utf8(&ancestor.path).ok() {
-                            conflicted_files.push(path.to_string());
-                        }
-                    } else if let Some(our) = conflict.our {
-                        if let Some(path) = std::str::from_utf8(&our.path).ok() {
-                            conflicted_files.push(path.to_string());
-                        }
-                    } else if let Some(their) = conflict.their {
-                        if let Some(path) = std::str::from_utf8(&their.path).ok() {
-                            conflicted_files.push(path.to_string());
+            // Check for conflicts
+            let index = repo.index()?;
+            if index.has_conflicts() {
+                let mut conflicted_files = Vec::new();
+                for conflict in index.conflicts()? {
+                    if let Ok(conflict) = conflict {
+                        if let Some(ancestor) = conflict.ancestor {
+                            if let Some(path) = std::str::from_utf8(&ancestor.path).ok() {
+                                conflicted_files.push(path.to_string());
+                            }
+                        } else if let Some(our) = conflict.our {
+                            if let Some(path) = std::str::from_utf8(&our.path).ok() {
+                                conflicted_files.push(path.to_string());
+                            }
+                        } else if let Some(their) = conflict.their {
+                            if let Some(path) = std::str::from_utf8(&their.path).ok() {
+                                conflicted_files.push(path.to_string());
+                            }
                         }
                     }
                 }
+
+                return Ok(MergeResult {
+                    success: false,
+                    analysis: MergeAnalysisResult::Normal,
+                    commit_oid: None,
+                    fast_forwarded: false,
+                    has_conflicts: true,
+                    conflicted_files,
+                });
             }
 
-            return Ok(MergeResult {
-                success: false,
+            // No conflicts - create merge commit
+            let head_commit = repo.head()?.peel_to_commit()?;
+            let merge_commit_oid = commit_merge(
+                &repo,
+                &format!("Merge branch '{}'", source_branch),
+                &[&head_commit, &source_commit],
+                &on_hook_event,
+            )?;
+
+            Ok(MergeResult {
+                success: true,
                 analysis: MergeAnalysisResult::Normal,
-                commit_oid: None,
+                commit_oid: Some(merge_commit_oid.to_string()),
                 fast_forwarded: false,
-                has_conflicts: true,
-                conflicted_files,
-            });
-        }
-
-        // No conflicts - create merge commit
-        let head_commit = repo.head()?.peel_to_commit()?;
-        let mut index = repo.index()?;
-        let tree_oid = index.write_tree()?;
-        let tree = repo.find_tree(tree_oid)?;
-
-        let sig = repo.signature().map_err(|e| {
-            GitError::SignatureError(format!(
-                "Could not determine commit author. Please configure git: {}",
-                e.message()
-            ))
-        })?;
-
-        let message = format!("Merge branch '{}'", source_branch);
-        let merge_commit_oid = repo.commit(
-            Some("HEAD"),
-            &sig,
-            &sig,
-            &message,
-            &tree,
-            &[&head_commit, &source_commit],
-        )?;
-
-        // Clean up merge state
-        repo.cleanup_state()?;
-
-        Ok(MergeResult {
-            success: true,
-            analysis: MergeAnalysisResult::Normal,
-            commit_oid: Some(merge_commit_oid.to_string()),
-            fast_forwarded: false,
-            has_conflicts: false,
-            conflicted_files: vec![],
-        })
-    })
+                has_conflicts: false,
+                conflicted_files: vec![],
+            })
+        },
+    )
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// Get the status of an in-progress merge.
@@ -215,19 +220,11 @@ pub async fn get_merge_status(state: State<'_, RepositoryState>) -> Result<Merge
 
         let in_progress = repo.state() == git2::RepositoryState::Merge;
 
-        let mut conflicted_files = Vec::new();
-        if in_progress {
-            let index = repo.index()?;
-            for conflict in index.conflicts()? {
-                if let Ok(conflict) = conflict {
-                    if let Some(our) = conflict.our {
-                        if let Some(path) = std::str::from_utf8(&our.path).ok() {
-                            conflicted_files.push(path.to_string());
-                        }
-                    }
-                }
-            }
-        }
+        let conflicted_files = if in_progress {
+            collect_conflicted_paths(&repo.index()?)?
+        } else {
+            vec![]
+        };
 
         Ok(MergeStatus {
             in_progress,
@@ -238,6 +235,95 @@ pub async fn get_merge_status(state: State<'_, RepositoryState>) -> Result<Merge
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
+/// Commit the merge staged in the index and clear the merge state.
+///
+/// Runs the hooks `git merge` does: `pre-merge-commit`, then
+/// `prepare-commit-msg` and `commit-msg` on `message`, and `post-merge`
+/// once the commit is written.
+pub(crate) fn commit_merge(
+    repo: &git2::Repository,
+    message: &str,
+    parents: &[&git2::Commit],
+    on_hook_event: &(dyn Fn(HookEvent) + Sync),
+) -> Result<git2::Oid, GitError> {
+    let env = commit_hook_env(repo);
+    let env: Vec<(&str, &Path)> = env.iter().map(|(k, v)| (*k, v.as_path())).collect();
+    run_hook(repo, "pre-merge-commit", &[], &env, None, on_hook_event)?;
+
+    // Written after pre-merge-commit, which may stage changes
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+    let sig = repo.signature().map_err(|e| {
+        GitError::SignatureError(format!(
+            "Could not determine commit author. Please configure git: {}",
+            e.message()
+        ))
+    })?;
+
+    let message = run_message_hooks(repo, message, "merge", &env, on_hook_event)?;
+    let oid = create_commit_signed(repo, Some("HEAD"), &sig, &sig, &message, &tree, parents)?;
+
+    // Clean up merge state
+    repo.cleanup_state()?;
+
+    let _ = run_hook(repo, "post-merge", &["0"], &[], None, on_hook_event);
+
+    Ok(oid)
+}
+
+/// Commit a merge whose conflicts have been resolved.
+///
+/// Uses the pending `MERGE_MSG` and every `MERGE_HEAD` as additional parents,
+/// and runs the same hooks as [`commit_merge`].
+pub fn continue_merge_internal(
+    repo: &git2::Repository,
+    on_hook_event: &(dyn Fn(HookEvent) + Sync),
+) -> Result<String, GitError> {
+    if repo.state() != git2::RepositoryState::Merge {
+        return Err(GitError::NoMergeInProgress);
+    }
+
+    let index = repo.index()?;
+    if index.has_conflicts() {
+        let files = collect_conflicted_paths(&index)?;
+        return Err(GitError::UnresolvedConflicts(files.join(", ")));
+    }
+
+    let head_commit = repo.head()?.peel_to_commit()?;
+    let merge_heads = std::fs::read_to_string(repo.path().join("MERGE_HEAD")).unwrap_or_default();
+
+    let mut parents = vec![head_commit];
+    for line in merge_heads.lines().filter(|l| !l.trim().is_empty()) {
+        let oid = git2::Oid::from_str(line.trim())
+            .map_err(|e| GitError::Internal(format!("Invalid MERGE_HEAD: {}", e)))?;
+        parents.push(repo.find_commit(oid)?);
+    }
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+
+    let message = repo
+        .message()
+        .map(|m| clean_message(&m))
+        .unwrap_or_else(|_| "Merge commit\n".to_string());
+
+    let oid = commit_merge(repo, &message, &parent_refs, on_hook_event)?;
+    Ok(oid.to_string())
+}
+
+pub fn abort_merge_internal(repo: &git2::Repository) -> Result<(), GitError> {
+    // Check if merge is in progress
+    if repo.state() != git2::RepositoryState::Merge {
+        return Err(GitError::NoMergeInProgress);
+    }
+
+    // Clean up merge state
+    repo.cleanup_state()?;
+
+    // Reset to HEAD (force to discard merge changes)
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+
+    Ok(())
+}
+
 /// Abort an in-progress merge.
 #[tauri::command]
 #[specta::specta]
@@ -247,22 +333,9 @@ pub async fn abort_merge(state: State<'_, RepositoryState>) -> Result<(), GitErr
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
+    record_operation(repo_path.clone(), "Abort merge".to_string(), move || {
         let repo = git2::Repository::open(&repo_path)?;
-
-        // Check if merge is in progress
-        if repo.state() != git2::RepositoryState::Merge {
-            return Err(GitError::NoMergeInProgress);
-        }
-
-        // Clean up merge state
-        repo.cleanup_state()?;
-
-        // Reset to HEAD (force to discard merge changes)
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
-
-        Ok(())
+        abort_merge_internal(&repo)
     })
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
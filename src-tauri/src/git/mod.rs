@@ -1,6 +1,9 @@
+pub mod auto_fetch;
 pub mod branch;
 pub mod browse;
+pub mod cancellation;
 pub mod changelog;
+pub mod cherry_pick;
 pub mod clone;
 pub mod commands;
 pub mod conflict;
@@ -12,19 +15,32 @@ pub mod diff;
 pub mod error;
 pub mod gitignore;
 pub mod graph;
+pub mod network;
 pub mod nuget;
+pub mod operation;
 pub mod history;
+pub mod hooks;
+pub mod identity;
 pub mod insights;
 pub mod init;
 pub mod merge;
+pub mod rebase;
+pub mod reflog;
 pub mod remote;
 pub mod repository;
+pub mod signing;
 pub mod staging;
 pub mod stash;
+pub mod submodule;
 pub mod tag;
+#[cfg(test)]
+mod test_support;
 pub mod undo;
 pub mod watcher;
 pub mod worktree;
 
+pub use auto_fetch::AutoFetchState;
+pub use cancellation::OperationRegistry;
+pub use identity::IdentityState;
 pub use repository::RepositoryState;
 pub use watcher::WatcherState;
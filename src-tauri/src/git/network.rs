@@ -0,0 +1,507 @@
+//! Transport security and proxy settings for network operations.
+//!
+//! SSH host keys are verified against `~/.ssh/known_hosts`. A host that is
+//! not listed is trusted on first use once the user accepts its fingerprint
+//! (through the credential prompt), and a key that changed or is marked
+//! `@revoked` is always rejected. Hosts on a port other than 22 are looked up
+//! and saved as `[host]:port`, like OpenSSH does. `http.sslCAInfo` and `http.proxy` from git config apply both to
+//! libgit2 transfers and to the reqwest clients used for GitHub and NuGet;
+//! without `http.proxy`, `HTTPS_PROXY` and friends are used.
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::git::credentials::{CredentialPromptKind, home_dir, host_pattern_matches, prompt};
+use crate::git::error::GitError;
+
+/// Network settings from git config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkSettings {
+    /// `http.sslCAInfo`: extra CA certificates (PEM bundle)
+    pub ca_info: Option<PathBuf>,
+    /// `http.proxy`
+    pub proxy: Option<String>,
+}
+
+impl NetworkSettings {
+    /// Settings from a repository's config, or the global config without one.
+    pub fn load(repo: Option<&git2::Repository>) -> Self {
+        let config = match repo {
+            Some(repo) => repo.config().ok(),
+            None => git2::Config::open_default().ok(),
+        };
+        config
+            .and_then(|mut config| config.snapshot().ok())
+            .map(|config| Self::from_config(&config))
+            .unwrap_or_default()
+    }
+
+    fn from_config(config: &git2::Config) -> Self {
+        let get = |key: &str| {
+            config
+                .get_string(key)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+        Self {
+            ca_info: config
+                .get_path("http.sslCAInfo")
+                .ok()
+                .filter(|path| !path.as_os_str().is_empty()),
+            proxy: get("http.proxy"),
+        }
+    }
+
+    /// Proxy options for libgit2: the configured proxy, else auto-detection,
+    /// which reads the proxy environment variables and honours `NO_PROXY`.
+    pub fn proxy_options(&self) -> git2::ProxyOptions<'static> {
+        let mut options = git2::ProxyOptions::new();
+        match &self.proxy {
+            Some(proxy) => options.url(proxy),
+            None => options.auto(),
+        };
+        options
+    }
+
+    /// Make libgit2 trust the configured CA bundle.
+    ///
+    /// libgit2 only has a process-wide setting, so the file is swapped in
+    /// when it differs from the one applied last.
+    pub fn apply_ca_info(&self) -> Result<(), GitError> {
+        static APPLIED: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+        let Some(ca_info) = &self.ca_info else {
+            return Ok(());
+        };
+        let mut applied = APPLIED
+            .lock()
+            .map_err(|_| GitError::Internal("CA settings lock poisoned".to_string()))?;
+        if applied.as_ref() == Some(ca_info) {
+            return Ok(());
+        }
+        if !ca_info.is_file() {
+            return Err(GitError::PathNotFound(format!(
+                "http.sslCAInfo {}",
+                ca_info.display()
+            )));
+        }
+        // SAFETY: serialised by APPLIED; libgit2 reads the setting when a
+        // TLS stream is created
+        unsafe { git2::opts::set_ssl_cert_file(ca_info) }.map_err(GitError::from)?;
+        *applied = Some(ca_info.clone());
+        Ok(())
+    }
+}
+
+/// A reqwest client honouring the global `http.proxy` and `http.sslCAInfo`.
+///
+/// Without `http.proxy`, reqwest picks up the proxy environment variables itself.
+pub fn http_client() -> Result<reqwest::Client, String> {
+    let settings = NetworkSettings::load(None);
+    let mut builder = reqwest::Client::builder();
+
+    if let Some(proxy) = &settings.proxy {
+        let proxy = reqwest::Proxy::all(proxy.as_str())
+            .map_err(|e| format!("Invalid http.proxy '{}': {}", proxy, e))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(ca_info) = &settings.ca_info {
+        let pem = std::fs::read(ca_info)
+            .map_err(|e| format!("Failed to read {}: {}", ca_info.display(), e))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|e| format!("Invalid certificates in {}: {}", ca_info.display(), e))?;
+        builder = builder.tls_certs_merge(certs);
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Create the certificate check callback for a transfer from or to `url`.
+///
+/// TLS certificates are left to libgit2 (see [`NetworkSettings::apply_ca_info`]);
+/// SSH host keys are checked against `~/.ssh/known_hosts`. libgit2 only passes
+/// the host name, so the SSH port comes from `url`.
+pub fn create_certificate_check(
+    url: &str,
+) -> impl FnMut(&git2::cert::Cert<'_>, &str) -> Result<git2::CertificateCheckStatus, git2::Error> + use<>
+{
+    let port = ssh_port(url);
+    move |cert: &git2::cert::Cert<'_>, host: &str| {
+        let Some(hostkey) = cert.as_hostkey() else {
+            return Ok(git2::CertificateCheckStatus::CertificatePassthrough);
+        };
+        let (Some(key_type), Some(key)) = (hostkey.hostkey_type(), hostkey.hostkey()) else {
+            return Ok(git2::CertificateCheckStatus::CertificatePassthrough);
+        };
+        let known_hosts = home_dir()
+            .map(|home| home.join(".ssh").join("known_hosts"))
+            .ok_or_else(|| git2::Error::from_str("Cannot locate ~/.ssh/known_hosts"))?;
+        let fingerprint = hostkey
+            .hash_sha256()
+            .map(|hash| format!("SHA256:{}", STANDARD_NO_PAD.encode(hash)))
+            .unwrap_or_else(|| "unavailable".to_string());
+
+        let known_host = known_hosts_name(host, port);
+        verify_host_key(&known_hosts, &known_host, key_type.name(), key, &mut || {
+            let kind = CredentialPromptKind::UnknownHostKey {
+                host: host.to_string(),
+                key_type: key_type.name().to_string(),
+                fingerprint: fingerprint.clone(),
+            };
+            prompt(host, kind).is_some()
+        })
+        .map(|()| git2::CertificateCheckStatus::CertificateOk)
+    }
+}
+
+/// Port of an `ssh://` URL; 22 for scp-style (`user@host:path`) and other URLs.
+fn ssh_port(url: &str) -> u16 {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return 22;
+    };
+    if !matches!(scheme, "ssh" | "git+ssh" | "ssh+git") {
+        return 22;
+    }
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let host_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let port = match host_port.strip_prefix('[') {
+        // [v6::addr]:port
+        Some(bracketed) => bracketed
+            .split_once(']')
+            .and_then(|(_, port)| port.strip_prefix(':')),
+        None => host_port.rsplit_once(':').map(|(_, port)| port),
+    };
+    port.and_then(|p| p.parse().ok()).unwrap_or(22)
+}
+
+/// Name of a host in known_hosts: `host` on port 22, else `[host]:port`.
+fn known_hosts_name(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{}]:{}", host, port)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum HostKeyStatus {
+    Known,
+    Changed,
+    /// The key is listed in an `@revoked` line
+    Revoked,
+    Unknown,
+}
+
+/// Check `host`'s key against `known_hosts`, asking `confirm` for unknown
+/// hosts and remembering keys the user accepted.
+fn verify_host_key(
+    known_hosts: &Path,
+    host: &str,
+    key_type: &str,
+    key: &[u8],
+    confirm: &mut dyn FnMut() -> bool,
+) -> Result<(), git2::Error> {
+    let contents = std::fs::read_to_string(known_hosts).unwrap_or_default();
+    match lookup_host_key(&contents, host, key_type, key) {
+        HostKeyStatus::Known => Ok(()),
+        HostKeyStatus::Changed => Err(git2::Error::from_str(&format!(
+            "The {} host key for {} has changed; remove the old key from {} if this is expected",
+            key_type,
+            host,
+            known_hosts.display()
+        ))),
+        HostKeyStatus::Revoked => Err(git2::Error::from_str(&format!(
+            "The {} host key for {} is marked as revoked in {}",
+            key_type,
+            host,
+            known_hosts.display()
+        ))),
+        HostKeyStatus::Unknown => {
+            if !confirm() {
+                return Err(git2::Error::from_str(&format!(
+                    "Host key verification failed: {} is not a known host",
+                    host
+                )));
+            }
+            append_known_host(known_hosts, &contents, host, key_type, key)
+                .map_err(|e| git2::Error::from_str(&format!("Failed to save host key: {}", e)))
+        }
+    }
+}
+
+fn append_known_host(
+    known_hosts: &Path,
+    contents: &str,
+    host: &str,
+    key_type: &str,
+    key: &[u8],
+) -> std::io::Result<()> {
+    if let Some(dir) = known_hosts.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(known_hosts)?;
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        writeln!(file)?;
+    }
+    writeln!(file, "{} {} {}", host, key_type, STANDARD.encode(key))
+}
+
+/// Look `host` (already in `[host]:port` form if needed) up in known_hosts.
+/// A matching `@revoked` line wins over any other entry.
+fn lookup_host_key(contents: &str, host: &str, key_type: &str, key: &[u8]) -> HostKeyStatus {
+    let encoded = STANDARD.encode(key);
+    let mut known = false;
+    let mut changed = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let marker = if line.starts_with('@') {
+            fields.next()
+        } else {
+            None
+        };
+        let (Some(hosts), Some(entry_type), Some(entry_key)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if entry_type != key_type || !known_host_matches(hosts, host) {
+            continue;
+        }
+        match marker {
+            None if entry_key == encoded => known = true,
+            None => changed = true,
+            Some("@revoked") if entry_key == encoded => return HostKeyStatus::Revoked,
+            // @cert-authority keys sign host certificates, which libgit2 does not expose
+            Some(_) => {}
+        }
+    }
+
+    if known {
+        HostKeyStatus::Known
+    } else if changed {
+        HostKeyStatus::Changed
+    } else {
+        HostKeyStatus::Unknown
+    }
+}
+
+/// Match the host field of a known_hosts line: a hashed `|1|salt|hash`
+/// entry or a comma-separated list of (possibly negated) patterns.
+fn known_host_matches(hosts: &str, host: &str) -> bool {
+    if let Some(hashed) = hosts.strip_prefix("|1|") {
+        let Some((salt, hash)) = hashed.split_once('|') else {
+            return false;
+        };
+        let (Ok(salt), Ok(hash)) = (STANDARD.decode(salt), STANDARD.decode(hash)) else {
+            return false;
+        };
+        // OpenSSH hashes known_hosts entries with HMAC-SHA1 keyed by the salt
+        let Ok(mut mac) = Hmac::<Sha1>::new_from_slice(&salt) else {
+            return false;
+        };
+        mac.update(host.as_bytes());
+        return mac.verify_slice(&hash).is_ok();
+    }
+
+    let patterns: Vec<&str> = hosts.split(',').collect();
+    let negated = patterns.iter().any(|p| {
+        p.strip_prefix('!')
+            .is_some_and(|p| host_pattern_matches(p, host))
+    });
+    !negated
+        && patterns
+            .iter()
+            .any(|p| !p.starts_with('!') && host_pattern_matches(p, host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hmac_sha1(key: &[u8], message: &[u8]) -> Vec<u8> {
+        let mut mac = Hmac::<Sha1>::new_from_slice(key).unwrap();
+        mac.update(message);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_lookup_host_key_plain_and_hashed_entries() {
+        let key = b"host key bytes";
+        let other = b"another key";
+        let salt = b"0123456789abcdefghij";
+        let hashed = format!(
+            "|1|{}|{}",
+            STANDARD.encode(salt),
+            STANDARD.encode(hmac_sha1(salt, b"hashed.example"))
+        );
+        let contents = format!(
+            "# comment\n\
+             github.com,140.82.121.4 ssh-ed25519 {key}\n\
+             *.corp,!build.corp ssh-ed25519 {key}\n\
+             {hashed} ssh-ed25519 {key}\n\
+             @cert-authority *.example ssh-ed25519 {other}\n",
+            key = STANDARD.encode(key),
+            other = STANDARD.encode(other),
+        );
+
+        let lookup = |host, key: &[u8]| lookup_host_key(&contents, host, "ssh-ed25519", key);
+        assert_eq!(lookup("github.com", key), HostKeyStatus::Known);
+        assert_eq!(lookup("git.corp", key), HostKeyStatus::Known);
+        assert_eq!(lookup("build.corp", key), HostKeyStatus::Unknown);
+        assert_eq!(lookup("hashed.example", key), HostKeyStatus::Known);
+        assert_eq!(lookup("github.com", other), HostKeyStatus::Changed);
+        assert_eq!(lookup("gitlab.com", key), HostKeyStatus::Unknown);
+        assert_eq!(
+            lookup_host_key(&contents, "github.com", "ssh-rsa", key),
+            HostKeyStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_revoked_keys_are_rejected() {
+        let key = b"revoked key";
+        let contents = format!(
+            "git.example ssh-ed25519 {key}\n@revoked * ssh-ed25519 {key}\n",
+            key = STANDARD.encode(key),
+        );
+        assert_eq!(
+            lookup_host_key(&contents, "git.example", "ssh-ed25519", key),
+            HostKeyStatus::Revoked
+        );
+        assert_eq!(
+            lookup_host_key(&contents, "other.example", "ssh-ed25519", key),
+            HostKeyStatus::Revoked
+        );
+        assert_eq!(
+            lookup_host_key(&contents, "git.example", "ssh-ed25519", b"new key"),
+            HostKeyStatus::Changed
+        );
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let known_hosts = dir.path().join("known_hosts");
+        std::fs::write(&known_hosts, &contents).unwrap();
+        let error = verify_host_key(&known_hosts, "new.example", "ssh-ed25519", key, &mut || {
+            panic!("revoked key prompted")
+        })
+        .unwrap_err();
+        assert!(error.message().contains("revoked"));
+    }
+
+    #[test]
+    fn test_non_default_ports_use_bracketed_names() {
+        assert_eq!(ssh_port("ssh://git@git.example:2222/repo.git"), 2222);
+        assert_eq!(ssh_port("ssh://[::1]:2200/repo.git"), 2200);
+        assert_eq!(ssh_port("ssh://git.example/repo.git"), 22);
+        assert_eq!(ssh_port("git@git.example:org/repo.git"), 22);
+        assert_eq!(ssh_port("https://git.example:8443/repo.git"), 22);
+        assert_eq!(known_hosts_name("git.example", 22), "git.example");
+        assert_eq!(known_hosts_name("git.example", 2222), "[git.example]:2222");
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let known_hosts = dir.path().join("known_hosts");
+        let key = b"server key";
+        std::fs::write(
+            &known_hosts,
+            format!(
+                "git.example ssh-ed25519 {}\n",
+                STANDARD.encode(b"port 22 key")
+            ),
+        )
+        .unwrap();
+
+        let name = known_hosts_name("git.example", 2222);
+        verify_host_key(&known_hosts, &name, "ssh-ed25519", key, &mut || true).unwrap();
+        let contents = std::fs::read_to_string(&known_hosts).unwrap();
+        assert!(contents.ends_with(&format!(
+            "[git.example]:2222 ssh-ed25519 {}\n",
+            STANDARD.encode(key)
+        )));
+        assert_eq!(
+            lookup_host_key(&contents, &name, "ssh-ed25519", key),
+            HostKeyStatus::Known
+        );
+        assert_eq!(
+            lookup_host_key(&contents, "git.example", "ssh-ed25519", key),
+            HostKeyStatus::Changed
+        );
+    }
+
+    #[test]
+    fn test_unknown_host_is_trusted_on_first_use_only_when_accepted() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let known_hosts = dir.path().join(".ssh").join("known_hosts");
+        let key = b"server key";
+
+        let mut asked = 0;
+        let rejected =
+            verify_host_key(&known_hosts, "git.example", "ssh-ed25519", key, &mut || {
+                asked += 1;
+                false
+            });
+        assert!(rejected.is_err());
+        assert!(!known_hosts.exists());
+
+        verify_host_key(&known_hosts, "git.example", "ssh-ed25519", key, &mut || {
+            asked += 1;
+            true
+        })
+        .unwrap();
+        assert_eq!(asked, 2);
+
+        // Now known: no prompt, and a different key is refused
+        verify_host_key(&known_hosts, "git.example", "ssh-ed25519", key, &mut || {
+            panic!("known host prompted")
+        })
+        .unwrap();
+        let changed = verify_host_key(
+            &known_hosts,
+            "git.example",
+            "ssh-ed25519",
+            b"mitm",
+            &mut || panic!("changed key prompted"),
+        );
+        assert!(changed.unwrap_err().message().contains("has changed"));
+    }
+
+    #[test]
+    fn test_network_settings_from_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config");
+        let mut config = git2::Config::open(&path).unwrap();
+        assert_eq!(
+            NetworkSettings::from_config(&config),
+            NetworkSettings::default()
+        );
+
+        config
+            .set_str("http.proxy", "http://proxy.corp:3128")
+            .unwrap();
+        config
+            .set_str("http.sslCAInfo", "/etc/corp-ca.pem")
+            .unwrap();
+        let settings = NetworkSettings::from_config(&config);
+        assert_eq!(settings.proxy.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(settings.ca_info, Some(PathBuf::from("/etc/corp-ca.pem")));
+        assert!(matches!(
+            settings.apply_ca_info(),
+            Err(GitError::PathNotFound(_))
+        ));
+    }
+}
@@ -8,6 +8,7 @@ use specta::Type;
 use std::time::Duration;
 
 use crate::git::error::GitError;
+use crate::git::network::http_client;
 
 // ── Types ──────────────────────────────────────────────────────────────────
 
@@ -76,7 +77,7 @@ struct CatalogEntry {
 #[tauri::command]
 #[specta::specta]
 pub async fn fetch_nuget_info(package_id: String) -> Result<NugetPackageInfo, GitError> {
-    let client = reqwest::Client::new();
+    let client = http_client().map_err(GitError::NetworkError)?;
 
     // 1. Search API for package metadata
     let search_url = format!(
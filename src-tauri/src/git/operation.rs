@@ -0,0 +1,514 @@
+//! Detection and control of in-progress multi-step operations.
+//!
+//! A repository can be left mid-merge, mid-rebase, mid-cherry-pick,
+//! mid-revert or mid-bisect, either by FlowForge or by the git CLI.
+//! This module reports which operation is running (with its progress)
+//! and dispatches the generic continue/abort/skip commands to the
+//! matching implementation.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+use crate::git::cherry_pick::{
+    SEQUENCER_DIR, abort_cherry_pick_internal, abort_revert_internal,
+    continue_cherry_pick_internal, continue_revert_internal, skip_cherry_pick_internal,
+};
+use crate::git::conflict::collect_conflicted_paths;
+use crate::git::error::GitError;
+use crate::git::merge::{abort_merge_internal, continue_merge_internal};
+use crate::git::rebase::{
+    abort_rebase_internal, continue_rebase_internal, rebase_dir, skip_rebase_internal,
+};
+use crate::git::repository::RepositoryState;
+use crate::git::undo::record_operation;
+
+/// Directory used by `git am` and `git rebase --apply`.
+const APPLY_DIR: &str = "rebase-apply";
+
+/// Kind of operation currently in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationKind {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+    Bisect,
+    /// Applying patches with `git am`
+    ApplyMailbox,
+}
+
+impl OperationKind {
+    fn name(&self) -> &'static str {
+        match self {
+            OperationKind::Merge => "merge",
+            OperationKind::Rebase => "rebase",
+            OperationKind::CherryPick => "cherry-pick",
+            OperationKind::Revert => "revert",
+            OperationKind::Bisect => "bisect",
+            OperationKind::ApplyMailbox => "am",
+        }
+    }
+}
+
+/// State of an in-progress operation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationState {
+    /// Which operation is in progress
+    pub kind: OperationKind,
+    /// Step currently being applied (1-based), for multi-step operations
+    pub current_step: Option<u32>,
+    /// Total number of steps, for multi-step operations
+    pub total_steps: Option<u32>,
+    /// Commit the operation is replaying onto (rebase, am)
+    pub onto: Option<String>,
+    /// Commit being merged, picked or reverted
+    pub target: Option<String>,
+    /// Branch the operation was started from (rebase, bisect)
+    pub head_name: Option<String>,
+    /// List of conflicted file paths
+    pub conflicted_files: Vec<String>,
+    /// Whether the stopped step can be skipped
+    pub can_skip: bool,
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn read_number(path: &Path) -> Option<u32> {
+    read_trimmed(path).and_then(|s| s.parse().ok())
+}
+
+/// First OID listed in a state file such as `MERGE_HEAD`.
+fn read_head_file(repo: &git2::Repository, name: &str) -> Option<String> {
+    read_trimmed(&repo.path().join(name)).and_then(|s| s.lines().next().map(|l| l.to_string()))
+}
+
+fn short_ref_name(name: &str) -> String {
+    name.strip_prefix("refs/heads/").unwrap_or(name).to_string()
+}
+
+/// Detect the operation in progress, if any.
+pub(crate) fn detect_operation(
+    repo: &git2::Repository,
+) -> Result<Option<OperationState>, GitError> {
+    use git2::RepositoryState as S;
+
+    let mut op = OperationState {
+        kind: OperationKind::Merge,
+        current_step: None,
+        total_steps: None,
+        onto: None,
+        target: None,
+        head_name: None,
+        conflicted_files: vec![],
+        can_skip: false,
+    };
+
+    match repo.state() {
+        S::Clean => return Ok(None),
+        S::Merge => {
+            op.target = read_head_file(repo, "MERGE_HEAD");
+        }
+        S::CherryPick | S::CherryPickSequence => {
+            op.kind = OperationKind::CherryPick;
+            op.target = read_head_file(repo, "CHERRY_PICK_HEAD");
+            op.can_skip = op.target.is_some();
+        }
+        S::Revert | S::RevertSequence => {
+            op.kind = OperationKind::Revert;
+            op.target = read_head_file(repo, "REVERT_HEAD");
+            // Only single reverts can be skipped; a `git revert A B` sequence
+            // keeps its remaining reverts in the sequencer, which skip would drop
+            op.can_skip = op.target.is_some() && !repo.path().join(SEQUENCER_DIR).is_dir();
+        }
+        S::Bisect => {
+            op.kind = OperationKind::Bisect;
+            op.head_name =
+                read_trimmed(&repo.path().join("BISECT_START")).map(|n| short_ref_name(&n));
+        }
+        S::RebaseInteractive | S::RebaseMerge => {
+            let dir = rebase_dir(repo);
+            op.kind = OperationKind::Rebase;
+            op.current_step = read_number(&dir.join("msgnum"));
+            op.total_steps = read_number(&dir.join("end"));
+            op.onto = read_trimmed(&dir.join("onto"));
+            // git records the stopped commit in stopped-sha, libgit2 in current
+            op.target = read_trimmed(&dir.join("stopped-sha"))
+                .or_else(|| read_trimmed(&dir.join("current")));
+            op.head_name = read_trimmed(&dir.join("head-name")).map(|n| short_ref_name(&n));
+            op.can_skip = true;
+        }
+        S::Rebase | S::ApplyMailbox | S::ApplyMailboxOrRebase => {
+            let dir = repo.path().join(APPLY_DIR);
+            op.kind = if dir.join("applying").exists() {
+                OperationKind::ApplyMailbox
+            } else {
+                OperationKind::Rebase
+            };
+            op.current_step = read_number(&dir.join("next"));
+            op.total_steps = read_number(&dir.join("last"));
+            op.onto = read_trimmed(&dir.join("onto"));
+            op.head_name = read_trimmed(&dir.join("head-name")).map(|n| short_ref_name(&n));
+        }
+    }
+
+    op.conflicted_files = collect_conflicted_paths(&repo.index()?)?;
+    Ok(Some(op))
+}
+
+fn require_operation(repo: &git2::Repository) -> Result<OperationState, GitError> {
+    detect_operation(repo)?.ok_or_else(|| GitError::NoOperationInProgress("operation".to_string()))
+}
+
+/// Restore the pre-operation HEAD of a `git am` / `git rebase --apply` session.
+fn abort_apply(repo: &git2::Repository) -> Result<(), GitError> {
+    let dir = repo.path().join(APPLY_DIR);
+
+    let orig_head = read_trimmed(&dir.join("orig-head"))
+        .or_else(|| read_trimmed(&repo.path().join("ORIG_HEAD")));
+    let target = match orig_head {
+        Some(oid) => repo.revparse_single(&oid)?.peel_to_commit()?,
+        None => repo.head()?.peel_to_commit()?,
+    };
+
+    let head_name = read_trimmed(&dir.join("head-name")).unwrap_or_default();
+    if head_name.starts_with("refs/") {
+        repo.set_head(&head_name)?;
+    }
+    repo.reset(target.as_object(), git2::ResetType::Hard, None)?;
+
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| {
+            GitError::OperationFailed(format!("Failed to remove {} state: {}", APPLY_DIR, e))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// End a bisect session, returning to the branch it was started from.
+fn abort_bisect(repo: &git2::Repository) -> Result<(), GitError> {
+    if let Some(start) = read_trimmed(&repo.path().join("BISECT_START")) {
+        let branch_ref = format!("refs/heads/{}", start);
+        if repo.find_reference(&branch_ref).is_ok() {
+            repo.set_head(&branch_ref)?;
+        } else {
+            let commit = repo.revparse_single(&start)?.peel_to_commit()?;
+            repo.set_head_detached(commit.id())?;
+        }
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
+    }
+
+    for name in [
+        "BISECT_LOG",
+        "BISECT_START",
+        "BISECT_TERMS",
+        "BISECT_NAMES",
+        "BISECT_EXPECTED_REV",
+        "BISECT_ANCESTORS_OK",
+        "BISECT_RUN",
+        "BISECT_HEAD",
+    ] {
+        let _ = fs::remove_file(repo.path().join(name));
+    }
+
+    let bisect_refs: Vec<String> = repo
+        .references_glob("refs/bisect/*")?
+        .flatten()
+        .filter_map(|r| r.name().map(|n| n.to_string()))
+        .collect();
+    for name in bisect_refs {
+        repo.find_reference(&name)?.delete()?;
+    }
+
+    Ok(())
+}
+
+fn unsupported(action: &str, kind: OperationKind) -> GitError {
+    GitError::UnsupportedOperation(format!("cannot {} a {}", action, kind.name()))
+}
+
+pub fn continue_operation_internal(
+    repo: &git2::Repository,
+) -> Result<Option<OperationState>, GitError> {
+    let op = require_operation(repo)?;
+
+    match op.kind {
+        OperationKind::Merge => {
+            continue_merge_internal(repo, &|_| {})?;
+        }
+        OperationKind::Rebase if rebase_dir(repo).is_dir() => {
+            continue_rebase_internal(repo)?;
+        }
+        OperationKind::CherryPick => {
+            continue_cherry_pick_internal(repo)?;
+        }
+        OperationKind::Revert => {
+            continue_revert_internal(repo)?;
+        }
+        kind => return Err(unsupported("continue", kind)),
+    }
+
+    detect_operation(repo)
+}
+
+pub fn skip_operation_internal(
+    repo: &git2::Repository,
+) -> Result<Option<OperationState>, GitError> {
+    let op = require_operation(repo)?;
+    if !op.can_skip {
+        return Err(unsupported("skip", op.kind));
+    }
+
+    match op.kind {
+        OperationKind::Rebase => {
+            skip_rebase_internal(repo)?;
+        }
+        OperationKind::CherryPick => {
+            skip_cherry_pick_internal(repo)?;
+        }
+        // Skippable reverts are single reverts with nothing after the stopped commit
+        OperationKind::Revert => abort_revert_internal(repo)?,
+        kind => return Err(unsupported("skip", kind)),
+    }
+
+    detect_operation(repo)
+}
+
+pub fn abort_operation_internal(repo: &git2::Repository) -> Result<(), GitError> {
+    let op = require_operation(repo)?;
+
+    match op.kind {
+        OperationKind::Merge => abort_merge_internal(repo),
+        OperationKind::Rebase if rebase_dir(repo).is_dir() => abort_rebase_internal(repo),
+        OperationKind::Rebase | OperationKind::ApplyMailbox => abort_apply(repo),
+        OperationKind::CherryPick => abort_cherry_pick_internal(repo),
+        OperationKind::Revert => abort_revert_internal(repo),
+        OperationKind::Bisect => abort_bisect(repo),
+    }
+}
+
+/// Get the operation currently in progress, if any.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_operation_state(
+    state: State<'_, RepositoryState>,
+) -> Result<Option<OperationState>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        detect_operation(&repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Continue the in-progress operation after resolving conflicts.
+///
+/// Returns the operation state afterwards, or None once it has completed.
+#[tauri::command]
+#[specta::specta]
+pub async fn continue_operation(
+    state: State<'_, RepositoryState>,
+) -> Result<Option<OperationState>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Continue operation".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            continue_operation_internal(&repo)
+        },
+    )
+    .await
+}
+
+/// Skip the stopped step of a rebase, cherry-pick or revert.
+///
+/// Returns the operation state afterwards, or None once it has completed.
+#[tauri::command]
+#[specta::specta]
+pub async fn skip_operation(
+    state: State<'_, RepositoryState>,
+) -> Result<Option<OperationState>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Skip operation step".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            skip_operation_internal(&repo)
+        },
+    )
+    .await
+}
+
+/// Abort the in-progress operation and restore the state before it started.
+#[tauri::command]
+#[specta::specta]
+pub async fn abort_operation(state: State<'_, RepositoryState>) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Abort operation".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            abort_operation_internal(&repo)
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::cherry_pick::{cherry_pick_internal, revert_internal};
+    use crate::git::rebase::{get_rebase_todo_internal, start_rebase_internal};
+    use crate::git::test_support::{commit_file, setup_repo};
+
+    /// Commit a conflicting change to `f.txt` on a `topic` branch and on the current branch.
+    fn diverge(repo: &git2::Repository) -> git2::Oid {
+        commit_file(repo, "f.txt", "base\n", "base");
+        let original = repo.head().unwrap().name().unwrap().to_string();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("topic", &head, false).unwrap();
+
+        repo.set_head("refs/heads/topic").unwrap();
+        let topic = commit_file(repo, "f.txt", "topic\n", "topic change");
+
+        repo.set_head(&original).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit_file(repo, "f.txt", "main\n", "main change");
+        topic
+    }
+
+    #[test]
+    fn test_clean_repo_has_no_operation() {
+        let (_dir, repo) = setup_repo();
+        commit_file(&repo, "a.txt", "a\n", "base");
+        assert!(detect_operation(&repo).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_detects_cherry_pick_and_skips() {
+        let (_dir, repo) = setup_repo();
+        let topic = diverge(&repo);
+        let before = repo.head().unwrap().target();
+
+        cherry_pick_internal(&repo, &[topic.to_string()], false, None).unwrap();
+
+        let op = detect_operation(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::CherryPick);
+        assert_eq!(op.target, Some(topic.to_string()));
+        assert_eq!(op.conflicted_files, vec!["f.txt".to_string()]);
+        assert!(op.can_skip);
+
+        assert!(skip_operation_internal(&repo).unwrap().is_none());
+        assert_eq!(repo.head().unwrap().target(), before);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_revert_sequence_cannot_be_skipped() {
+        let (_dir, repo) = setup_repo();
+        commit_file(&repo, "f.txt", "base\n", "base");
+        let first = commit_file(&repo, "f.txt", "one\n", "one");
+        let second = commit_file(&repo, "f.txt", "two\n", "two");
+
+        revert_internal(&repo, &first.to_string(), None).unwrap();
+        let op = detect_operation(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::Revert);
+        assert!(op.can_skip);
+
+        // What `git revert <second> <first>` leaves behind when it stops
+        let sequencer = repo.path().join(SEQUENCER_DIR);
+        fs::create_dir_all(&sequencer).unwrap();
+        fs::write(sequencer.join("head"), format!("{}\n", second)).unwrap();
+        fs::write(sequencer.join("todo"), format!("revert {} two\n", second)).unwrap();
+
+        let op = detect_operation(&repo).unwrap().unwrap();
+        assert!(!op.can_skip);
+        assert!(matches!(
+            skip_operation_internal(&repo),
+            Err(GitError::UnsupportedOperation(_))
+        ));
+        assert!(sequencer.join("todo").exists());
+        assert!(repo.path().join("REVERT_HEAD").exists());
+    }
+
+    #[test]
+    fn test_detects_rebase_progress_and_aborts() {
+        let (_dir, repo) = setup_repo();
+        diverge(&repo);
+
+        // Rebase the topic branch onto the diverged main branch
+        let main = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.set_head("refs/heads/topic").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let base = main.parent(0).unwrap().id().to_string();
+        let plan = get_rebase_todo_internal(&repo, &base).unwrap();
+        start_rebase_internal(&repo, &main.id().to_string(), &plan).unwrap();
+
+        let op = detect_operation(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::Rebase);
+        assert_eq!(op.current_step, Some(1));
+        assert_eq!(op.total_steps, Some(1));
+        assert_eq!(op.onto, Some(main.id().to_string()));
+        assert_eq!(op.head_name, Some("topic".to_string()));
+
+        abort_operation_internal(&repo).unwrap();
+        assert!(detect_operation(&repo).unwrap().is_none());
+        assert_eq!(repo.head().unwrap().shorthand(), Some("topic"));
+    }
+
+    #[test]
+    fn test_merge_cannot_be_skipped() {
+        let (dir, repo) = setup_repo();
+        let topic = diverge(&repo);
+        let annotated = repo.find_annotated_commit(topic).unwrap();
+        repo.merge(&[&annotated], None, None).unwrap();
+
+        let op = detect_operation(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::Merge);
+        assert!(matches!(
+            skip_operation_internal(&repo),
+            Err(GitError::UnsupportedOperation(_))
+        ));
+
+        fs::write(dir.path().join("f.txt"), "merged\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("f.txt")).unwrap();
+        index.write().unwrap();
+
+        assert!(continue_operation_internal(&repo).unwrap().is_none());
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+    }
+}
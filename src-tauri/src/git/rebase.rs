@@ -0,0 +1,1002 @@
+//! Interactive rebase engine.
+//!
+//! Executes a todo plan (pick, reword, edit, squash, fixup, drop, reorder)
+//! over a commit range with libgit2's rebase (`git2::Rebase`). libgit2 only
+//! picks commits in history order, so the plan's order is written over its
+//! operation list and the action of each step is kept next to it. The state
+//! lives in git's `rebase-merge` directory, so the repository reports a
+//! rebase while stopped and the sequence can be resumed once conflicts are
+//! resolved with `list_conflict_files`/`resolve_conflict_file`.
+//!
+//! Steps are committed with `create_commit_signed` instead of
+//! `Rebase::commit`, which cannot sign commits.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::git::conflict::collect_conflicted_paths;
+use crate::git::error::GitError;
+use crate::git::repository::RepositoryState;
+use crate::git::signing::create_commit_signed;
+use crate::git::undo::record_operation;
+
+/// Directory (inside `.git`) holding the state of a rebase.
+const REBASE_DIR: &str = "rebase-merge";
+
+/// Plan actions in libgit2's operation order, one todo line per `cmt.N` file.
+const ACTIONS_FILE: &str = "flowforge-actions";
+
+/// Sub-directory holding replacement messages for reword/squash steps, keyed by OID.
+const MESSAGES_DIR: &str = "flowforge-messages";
+
+/// Action applied to a commit in a rebase plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseAction {
+    /// Use the commit as-is
+    Pick,
+    /// Use the commit with a new message
+    Reword,
+    /// Use the commit, then stop so it can be amended
+    Edit,
+    /// Meld into the previous commit, combining both messages
+    Squash,
+    /// Meld into the previous commit, keeping only the previous message
+    Fixup,
+    /// Remove the commit
+    Drop,
+}
+
+impl RebaseAction {
+    /// Keyword used for this action in `git-rebase-todo`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    /// Parse a todo keyword, accepting git's single-letter abbreviations.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "pick" | "p" => Some(RebaseAction::Pick),
+            "reword" | "r" => Some(RebaseAction::Reword),
+            "edit" | "e" => Some(RebaseAction::Edit),
+            "squash" | "s" => Some(RebaseAction::Squash),
+            "fixup" | "f" => Some(RebaseAction::Fixup),
+            "drop" | "d" => Some(RebaseAction::Drop),
+            _ => None,
+        }
+    }
+}
+
+/// A single step of a rebase plan.
+///
+/// The order of items in the plan is the order commits are applied in,
+/// so reordering the list reorders the rewritten history.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseTodoItem {
+    /// What to do with the commit
+    pub action: RebaseAction,
+    /// Full OID of the commit
+    pub oid: String,
+    /// First line of the original commit message (display only)
+    pub message_subject: String,
+    /// Replacement message for reword/squash steps (None keeps the default)
+    pub new_message: Option<String>,
+}
+
+/// Why a rebase returned control to the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseStopReason {
+    /// Every step was applied and the branch was updated
+    Completed,
+    /// A step produced conflicts that must be resolved before continuing
+    Conflicts,
+    /// An `edit` step stopped so the commit can be amended
+    Edit,
+}
+
+/// Result of starting or resuming a rebase.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseProgress {
+    /// Why the rebase returned
+    pub reason: RebaseStopReason,
+    /// Number of steps processed so far (the stopped step included)
+    pub current_step: u32,
+    /// Total number of steps in the plan
+    pub total_steps: u32,
+    /// OID of the original commit being applied when the rebase stopped
+    pub stopped_oid: Option<String>,
+    /// Conflicted file paths (empty unless stopped on conflicts)
+    pub conflicted_files: Vec<String>,
+    /// HEAD commit OID after the operation
+    pub head_oid: Option<String>,
+}
+
+/// Path of the rebase state directory for a repository.
+pub(crate) fn rebase_dir(repo: &git2::Repository) -> PathBuf {
+    repo.path().join(REBASE_DIR)
+}
+
+fn read_state_file(dir: &Path, name: &str) -> Option<String> {
+    fs::read_to_string(dir.join(name)).ok()
+}
+
+fn write_state_file(dir: &Path, name: &str, content: &str) -> Result<(), GitError> {
+    fs::write(dir.join(name), content).map_err(|e| {
+        GitError::OperationFailed(format!("Failed to write rebase state '{}': {}", name, e))
+    })
+}
+
+fn remove_state_file(dir: &Path, name: &str) {
+    let _ = fs::remove_file(dir.join(name));
+}
+
+/// True for todo lines that describe a step (not blank, not a comment).
+fn is_step_line(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with('#')
+}
+
+/// Parse a single plan line into its action and commit reference.
+///
+/// Commands FlowForge cannot execute (exec, break, label, reset, merge)
+/// and option flags such as `fixup -C` are rejected.
+fn parse_todo_line(line: &str) -> Result<(RebaseAction, String), GitError> {
+    let mut parts = line.split_whitespace();
+    let keyword = parts.next().unwrap_or("");
+    let action = RebaseAction::from_keyword(keyword).ok_or_else(|| {
+        GitError::InvalidRebasePlan(format!("Unsupported rebase command: {}", keyword))
+    })?;
+
+    let oid = parts
+        .next()
+        .filter(|oid| !oid.starts_with('-'))
+        .ok_or_else(|| GitError::InvalidRebasePlan(format!("Unsupported todo line: {}", line)))?;
+
+    Ok((action, oid.to_string()))
+}
+
+/// Format a plan as todo lines.
+fn format_todo(items: &[(RebaseAction, git2::Oid, String)]) -> String {
+    items
+        .iter()
+        .map(|(action, oid, subject)| format!("{} {} {}\n", action.as_str(), oid, subject))
+        .collect()
+}
+
+/// Action of the rebase operation at `index`.
+fn step_action(dir: &Path, index: usize) -> Result<RebaseAction, GitError> {
+    let actions = read_state_file(dir, ACTIONS_FILE).unwrap_or_default();
+    let line = actions
+        .lines()
+        .filter(|l| is_step_line(l))
+        .nth(index)
+        .ok_or_else(|| {
+            GitError::InvalidRebasePlan(format!("Rebase state has no step {}", index + 1))
+        })?;
+    Ok(parse_todo_line(line)?.0)
+}
+
+/// Checkout behaviour for the rebase: never overwrite local changes, and
+/// write conflict markers for conflicted steps.
+fn rebase_options() -> git2::RebaseOptions<'static> {
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout
+        .safe()
+        .allow_conflicts(true)
+        .conflict_style_merge(true);
+    let mut options = git2::RebaseOptions::new();
+    options.checkout_options(checkout);
+    options
+}
+
+/// Open the rebase in progress.
+fn open_rebase(repo: &git2::Repository) -> Result<git2::Rebase<'_>, GitError> {
+    if !rebase_dir(repo).is_dir() {
+        return Err(GitError::NoRebaseInProgress);
+    }
+    Ok(repo.open_rebase(Some(&mut rebase_options()))?)
+}
+
+/// Commits reachable from `head` but not from `base`, oldest first.
+fn commits_in_range(
+    repo: &git2::Repository,
+    base: git2::Oid,
+    head: git2::Oid,
+) -> Result<Vec<git2::Oid>, GitError> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(head)?;
+    revwalk.hide(base)?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+    revwalk
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(GitError::from)
+}
+
+/// True if tracked files have staged or unstaged modifications.
+pub(crate) fn has_uncommitted_changes(repo: &git2::Repository) -> Result<bool, GitError> {
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(false).exclude_submodules(true);
+    Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+}
+
+pub(crate) fn committer_signature(
+    repo: &git2::Repository,
+) -> Result<git2::Signature<'static>, GitError> {
+    repo.signature().map_err(|e| {
+        GitError::SignatureError(format!(
+            "Could not determine committer. Please configure git: {}",
+            e.message()
+        ))
+    })
+}
+
+/// Message to use when applying `commit` with the given action on top of `head`.
+fn step_message(
+    dir: &Path,
+    action: RebaseAction,
+    commit: &git2::Commit,
+    head: &git2::Commit,
+) -> String {
+    let original = commit.message().unwrap_or("").to_string();
+    let custom = read_state_file(&dir.join(MESSAGES_DIR), &commit.id().to_string());
+
+    match action {
+        RebaseAction::Reword => custom.unwrap_or(original),
+        RebaseAction::Squash => custom.unwrap_or_else(|| {
+            format!(
+                "{}\n\n{}",
+                head.message().unwrap_or("").trim_end(),
+                original.trim_end()
+            )
+        }),
+        RebaseAction::Fixup => head.message().unwrap_or("").to_string(),
+        _ => original,
+    }
+}
+
+/// Replace `head` with a commit of `tree` and `message` that keeps its
+/// author and parents, signed like any new commit (unlike `Commit::amend`).
+fn rewrite_commit(
+    repo: &git2::Repository,
+    head: &git2::Commit,
+    committer: &git2::Signature,
+    message: &str,
+    tree: &git2::Tree,
+) -> Result<git2::Oid, GitError> {
+    let parents: Vec<git2::Commit> = head.parents().collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    create_commit_signed(
+        repo,
+        None,
+        &head.author(),
+        committer,
+        message,
+        tree,
+        &parent_refs,
+    )
+}
+
+/// Record the applied step staged in the index as a commit on the detached HEAD.
+///
+/// Squash and fixup rewrite the previous commit; every other action
+/// creates a new commit that keeps the original author. A picked commit
+/// whose changes are already present is dropped and None is returned;
+/// reword and edit steps are always committed so the user gets to stop there.
+fn commit_step(
+    repo: &git2::Repository,
+    dir: &Path,
+    action: RebaseAction,
+    original: &git2::Commit,
+) -> Result<Option<git2::Oid>, GitError> {
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+    if action == RebaseAction::Pick && tree.id() == head.tree_id() {
+        return Ok(None);
+    }
+
+    let committer = committer_signature(repo)?;
+    let message = step_message(dir, action, original, &head);
+    let oid = match action {
+        RebaseAction::Squash | RebaseAction::Fixup => {
+            rewrite_commit(repo, &head, &committer, &message, &tree)?
+        }
+        _ => create_commit_signed(
+            repo,
+            None,
+            &original.author(),
+            &committer,
+            &message,
+            &tree,
+            &[&head],
+        )?,
+    };
+    repo.set_head_detached(oid)?;
+
+    // libgit2 reads this when rewriting notes at the end of the rebase
+    let mut rewritten = read_state_file(dir, "rewritten").unwrap_or_default();
+    rewritten.push_str(&format!("{} {}\n", original.id(), oid));
+    write_state_file(dir, "rewritten", &rewritten)?;
+
+    Ok(Some(oid))
+}
+
+/// Progress of a rebase stopped at its current operation.
+fn stopped(
+    repo: &git2::Repository,
+    rebase: &mut git2::Rebase,
+    reason: RebaseStopReason,
+    conflicted_files: Vec<String>,
+) -> RebaseProgress {
+    let current = rebase.operation_current();
+    RebaseProgress {
+        reason,
+        current_step: current.map_or(0, |i| i as u32 + 1),
+        total_steps: rebase.len() as u32,
+        stopped_oid: current
+            .and_then(|i| rebase.nth(i))
+            .map(|op| op.id().to_string()),
+        conflicted_files,
+        head_oid: repo
+            .head()
+            .ok()
+            .and_then(|h| h.target())
+            .map(|oid| oid.to_string()),
+    }
+}
+
+/// Apply the remaining operations until the plan is exhausted or a step stops.
+fn run_rebase(
+    repo: &git2::Repository,
+    mut rebase: git2::Rebase,
+) -> Result<RebaseProgress, GitError> {
+    let dir = rebase_dir(repo);
+
+    // Each step is merged onto HEAD and checked out, conflicts included
+    while let Some(operation) = rebase.next() {
+        let commit = repo.find_commit(operation?.id())?;
+        let index = repo.index()?;
+        if index.has_conflicts() {
+            let conflicted_files = collect_conflicted_paths(&index)?;
+            return Ok(stopped(
+                repo,
+                &mut rebase,
+                RebaseStopReason::Conflicts,
+                conflicted_files,
+            ));
+        }
+
+        let current = rebase.operation_current().unwrap_or_default();
+        let action = step_action(&dir, current)?;
+        if let Some(new_oid) = commit_step(repo, &dir, action, &commit)?
+            && action == RebaseAction::Edit
+        {
+            write_state_file(&dir, "amend", &new_oid.to_string())?;
+            return Ok(stopped(repo, &mut rebase, RebaseStopReason::Edit, vec![]));
+        }
+    }
+
+    // Points the rebased branch at the rewritten HEAD and removes the state
+    let total_steps = rebase.len() as u32;
+    rebase.finish(Some(&committer_signature(repo)?))?;
+    let head_oid = repo
+        .head()?
+        .target()
+        .ok_or_else(|| GitError::Internal("HEAD has no target".to_string()))?;
+
+    Ok(RebaseProgress {
+        reason: RebaseStopReason::Completed,
+        current_step: total_steps,
+        total_steps,
+        stopped_oid: None,
+        conflicted_files: vec![],
+        head_oid: Some(head_oid.to_string()),
+    })
+}
+
+/// Build the default plan for `base..HEAD`: a `pick` for every non-merge commit.
+pub fn get_rebase_todo_internal(
+    repo: &git2::Repository,
+    base_oid: &str,
+) -> Result<Vec<RebaseTodoItem>, GitError> {
+    let base = repo.revparse_single(base_oid)?.peel_to_commit()?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    let mut items = Vec::new();
+    for oid in commits_in_range(repo, base.id(), head.id())? {
+        let commit = repo.find_commit(oid)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        items.push(RebaseTodoItem {
+            action: RebaseAction::Pick,
+            oid: oid.to_string(),
+            message_subject: commit.summary().unwrap_or("").to_string(),
+            new_message: None,
+        });
+    }
+
+    Ok(items)
+}
+
+/// Validate a plan, write the rebase state and start applying it onto `base_oid`.
+pub fn start_rebase_internal(
+    repo: &git2::Repository,
+    base_oid: &str,
+    todo: &[RebaseTodoItem],
+) -> Result<RebaseProgress, GitError> {
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err(GitError::OperationInProgress(format!("{:?}", repo.state())));
+    }
+    if has_uncommitted_changes(repo)? {
+        return Err(GitError::DirtyWorkingDirectory);
+    }
+
+    let head = repo.head()?;
+    let head_commit = head.peel_to_commit()?;
+    let base = repo.revparse_single(base_oid)?.peel_to_commit()?;
+
+    let range: HashSet<git2::Oid> = commits_in_range(repo, base.id(), head_commit.id())?
+        .into_iter()
+        .collect();
+
+    // Resolve and validate every step before touching the repository
+    let mut steps = Vec::with_capacity(todo.len());
+    let mut seen = HashSet::new();
+    let mut has_target = false;
+    for item in todo {
+        let commit = repo.revparse_single(&item.oid)?.peel_to_commit()?;
+        if !range.contains(&commit.id()) {
+            return Err(GitError::InvalidRebasePlan(format!(
+                "Commit {:.7} is not between the base and HEAD",
+                commit.id()
+            )));
+        }
+        if !seen.insert(commit.id()) {
+            return Err(GitError::InvalidRebasePlan(format!(
+                "Commit {:.7} appears more than once",
+                commit.id()
+            )));
+        }
+        if item.action != RebaseAction::Drop && commit.parent_count() > 1 {
+            return Err(GitError::InvalidRebasePlan(format!(
+                "Merge commits cannot be rebased: {:.7}",
+                commit.id()
+            )));
+        }
+        match item.action {
+            RebaseAction::Squash | RebaseAction::Fixup if !has_target => {
+                return Err(GitError::InvalidRebasePlan(format!(
+                    "Cannot {} without a previous commit",
+                    item.action.as_str()
+                )));
+            }
+            RebaseAction::Drop => {}
+            _ => has_target = true,
+        }
+        steps.push((
+            item.action,
+            commit,
+            item.new_message.clone().filter(|m| !m.trim().is_empty()),
+        ));
+    }
+
+    // libgit2 detaches HEAD at the base and lists the range in history order;
+    // the plan then replaces that list, leaving out dropped commits
+    let branch = if head.is_branch() {
+        repo.reference_to_annotated_commit(&head)?
+    } else {
+        repo.find_annotated_commit(head_commit.id())?
+    };
+    let onto = repo.find_annotated_commit(base.id())?;
+    let listed = repo
+        .rebase(
+            Some(&branch),
+            Some(&onto),
+            Some(&onto),
+            Some(&mut rebase_options()),
+        )?
+        .len();
+
+    let steps: Vec<_> = steps
+        .into_iter()
+        .filter(|(action, _, _)| *action != RebaseAction::Drop)
+        .collect();
+    let todo_lines: Vec<(RebaseAction, git2::Oid, String)> = steps
+        .iter()
+        .map(|(action, commit, _)| {
+            (
+                *action,
+                commit.id(),
+                commit.summary().unwrap_or("").to_string(),
+            )
+        })
+        .collect();
+
+    let dir = rebase_dir(repo);
+    for (i, (_, commit, _)) in steps.iter().enumerate() {
+        write_state_file(
+            &dir,
+            &format!("cmt.{}", i + 1),
+            &format!("{}\n", commit.id()),
+        )?;
+    }
+    for i in steps.len()..listed {
+        remove_state_file(&dir, &format!("cmt.{}", i + 1));
+    }
+    write_state_file(&dir, "end", &format!("{}\n", steps.len()))?;
+    write_state_file(&dir, ACTIONS_FILE, &format_todo(&todo_lines))?;
+    write_state_file(&dir, "rewritten", "")?;
+
+    fs::create_dir_all(dir.join(MESSAGES_DIR))
+        .map_err(|e| GitError::OperationFailed(format!("Failed to create rebase state: {}", e)))?;
+    for (_, commit, message) in &steps {
+        if let Some(message) = message {
+            write_state_file(&dir.join(MESSAGES_DIR), &commit.id().to_string(), message)?;
+        }
+    }
+
+    run_rebase(repo, open_rebase(repo)?)
+}
+
+/// Commit the resolved (or amended) stopped step, then resume the plan.
+pub fn continue_rebase_internal(repo: &git2::Repository) -> Result<RebaseProgress, GitError> {
+    let mut rebase = open_rebase(repo)?;
+    let dir = rebase_dir(repo);
+
+    let index = repo.index()?;
+    if index.has_conflicts() {
+        let files = collect_conflicted_paths(&index)?;
+        return Err(GitError::UnresolvedConflicts(files.join(", ")));
+    }
+
+    if let Some(amend) = read_state_file(&dir, "amend") {
+        // Edit stop: fold staged changes into the commit, unless the
+        // user already committed on top of it themselves
+        let head = repo.head()?.peel_to_commit()?;
+        let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+        if head.id().to_string() == amend.trim() && tree.id() != head.tree_id() {
+            let committer = committer_signature(repo)?;
+            let message = head.message().unwrap_or("").to_string();
+            let oid = rewrite_commit(repo, &head, &committer, &message, &tree)?;
+            repo.set_head_detached(oid)?;
+        }
+        remove_state_file(&dir, "amend");
+    } else if let Some(current) = rebase.operation_current() {
+        // Conflict stop: record the resolution as the step's commit
+        let stopped_oid = rebase
+            .nth(current)
+            .map(|op| op.id())
+            .ok_or_else(|| GitError::Internal("Rebase has no current step".to_string()))?;
+        let original = repo.find_commit(stopped_oid)?;
+        let action = step_action(&dir, current)?;
+
+        if let Some(oid) = commit_step(repo, &dir, action, &original)?
+            && action == RebaseAction::Edit
+        {
+            write_state_file(&dir, "amend", &oid.to_string())?;
+            return Ok(stopped(repo, &mut rebase, RebaseStopReason::Edit, vec![]));
+        }
+    }
+
+    run_rebase(repo, rebase)
+}
+
+/// Discard the stopped step's changes and resume with the next step.
+pub fn skip_rebase_internal(repo: &git2::Repository) -> Result<RebaseProgress, GitError> {
+    let rebase = open_rebase(repo)?;
+
+    discard_step_changes(repo)?;
+    remove_state_file(&rebase_dir(repo), "amend");
+
+    run_rebase(repo, rebase)
+}
+
+/// Restore the original branch and HEAD and remove the rebase state.
+pub fn abort_rebase_internal(repo: &git2::Repository) -> Result<(), GitError> {
+    // The branch itself is only moved when the rebase finishes, so libgit2
+    // points HEAD back at it and hard-resets to the original commit
+    open_rebase(repo)?.abort()?;
+    Ok(())
+}
+
+/// Get the default rebase plan for the commits between `base_oid` and HEAD.
+///
+/// Every non-merge commit is listed oldest first with a `pick` action,
+/// ready to be edited by the user and passed to `start_interactive_rebase`.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rebase_todo(
+    base_oid: String,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<RebaseTodoItem>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        get_rebase_todo_internal(&repo, &base_oid)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Start an interactive rebase of HEAD onto `base_oid` using the given plan.
+///
+/// Commits of the range that are missing from the plan are dropped.
+/// Stops early on conflicts or `edit` steps; resume with `continue_rebase`.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_interactive_rebase(
+    base_oid: String,
+    todo: Vec<RebaseTodoItem>,
+    state: State<'_, RepositoryState>,
+) -> Result<RebaseProgress, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Interactive rebase".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            start_rebase_internal(&repo, &base_oid, &todo)
+        },
+    )
+    .await
+}
+
+/// Continue a stopped rebase after resolving conflicts or amending an `edit` step.
+#[tauri::command]
+#[specta::specta]
+pub async fn continue_rebase(
+    state: State<'_, RepositoryState>,
+) -> Result<RebaseProgress, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Continue rebase".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            continue_rebase_internal(&repo)
+        },
+    )
+    .await
+}
+
+/// Reset the index and working tree to HEAD, discarding the stopped step.
+///
+/// `Repository::reset` would also clean up the repository state and remove
+/// the rebase directory, so the index and checkout are handled directly.
+fn discard_step_changes(repo: &git2::Repository) -> Result<(), GitError> {
+    let tree = repo.head()?.peel_to_tree()?;
+    let mut index = repo.index()?;
+    index.read_tree(&tree)?;
+    index.write()?;
+    repo.checkout_tree(
+        tree.as_object(),
+        Some(git2::build::CheckoutBuilder::new().force()),
+    )?;
+    Ok(())
+}
+
+/// Skip the stopped rebase step, discarding its changes.
+#[tauri::command]
+#[specta::specta]
+pub async fn skip_rebase(state: State<'_, RepositoryState>) -> Result<RebaseProgress, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        "Skip rebase step".to_string(),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            skip_rebase_internal(&repo)
+        },
+    )
+    .await
+}
+
+/// Abort an in-progress rebase and restore the original branch.
+#[tauri::command]
+#[specta::specta]
+pub async fn abort_rebase(state: State<'_, RepositoryState>) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(repo_path.clone(), "Abort rebase".to_string(), move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        abort_rebase_internal(&repo)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_file, configure_ssh_signing, setup_repo};
+
+    fn item(action: RebaseAction, oid: git2::Oid) -> RebaseTodoItem {
+        RebaseTodoItem {
+            action,
+            oid: oid.to_string(),
+            message_subject: String::new(),
+            new_message: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_todo_line() {
+        let (action, oid) = parse_todo_line("pick 1234567 Add feature").unwrap();
+        assert_eq!(action, RebaseAction::Pick);
+        assert_eq!(oid, "1234567");
+
+        let (action, _) = parse_todo_line("f 1234567").unwrap();
+        assert_eq!(action, RebaseAction::Fixup);
+
+        assert!(parse_todo_line("exec make test").is_err());
+        assert!(parse_todo_line("fixup -C 1234567").is_err());
+    }
+
+    #[test]
+    fn test_default_plan_lists_commits_oldest_first() {
+        let (_dir, repo) = setup_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        let first = commit_file(&repo, "b.txt", "b\n", "first");
+        let second = commit_file(&repo, "c.txt", "c\n", "second");
+
+        let plan = get_rebase_todo_internal(&repo, &base.to_string()).unwrap();
+        let oids: Vec<String> = plan.iter().map(|i| i.oid.clone()).collect();
+        assert_eq!(oids, vec![first.to_string(), second.to_string()]);
+        assert!(plan.iter().all(|i| i.action == RebaseAction::Pick));
+    }
+
+    #[test]
+    fn test_reorder_fixup_and_drop() {
+        let (dir, repo) = setup_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        let add_b = commit_file(&repo, "b.txt", "b\n", "add b");
+        let add_c = commit_file(&repo, "c.txt", "c\n", "add c");
+        let fix_c = commit_file(&repo, "c.txt", "c fixed\n", "fix c");
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+
+        let plan = vec![
+            item(RebaseAction::Pick, add_c),
+            item(RebaseAction::Fixup, fix_c),
+            item(RebaseAction::Drop, add_b),
+        ];
+        let result = start_rebase_internal(&repo, &base.to_string(), &plan).unwrap();
+        assert_eq!(result.reason, RebaseStopReason::Completed);
+        // Dropped commits are left out of the rebase entirely
+        assert_eq!(result.total_steps, 2);
+
+        // Branch is re-attached and points at the single rewritten commit
+        let head = repo.head().unwrap();
+        assert_eq!(head.name(), Some(branch.as_str()));
+        let commit = head.peel_to_commit().unwrap();
+        assert_eq!(commit.message(), Some("add c"));
+        assert_eq!(commit.parent_id(0).unwrap(), base);
+
+        let tree = commit.tree().unwrap();
+        assert!(tree.get_path(Path::new("b.txt")).is_err());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("c.txt")).unwrap(),
+            "c fixed\n"
+        );
+        assert!(!rebase_dir(&repo).exists());
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_reword_uses_new_message() {
+        let (_dir, repo) = setup_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        let add_b = commit_file(&repo, "b.txt", "b\n", "wip");
+
+        let mut reword = item(RebaseAction::Reword, add_b);
+        reword.new_message = Some("feat: add b".to_string());
+        start_rebase_internal(&repo, &base.to_string(), &[reword]).unwrap();
+
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.message(), Some("feat: add b"));
+    }
+
+    #[test]
+    fn test_empty_edit_step_still_stops() {
+        let (_dir, repo) = setup_repo();
+        let base = commit_file(&repo, "f.txt", "0\n", "base");
+        let to_one = commit_file(&repo, "f.txt", "1\n", "to one");
+        let back = commit_file(&repo, "f.txt", "0\n", "back to zero");
+        let again = commit_file(&repo, "f.txt", "1\n", "to one again");
+
+        // Without "back to zero", "to one again" changes nothing
+        let plan = vec![
+            item(RebaseAction::Pick, to_one),
+            item(RebaseAction::Drop, back),
+            item(RebaseAction::Edit, again),
+        ];
+        let result = start_rebase_internal(&repo, &base.to_string(), &plan).unwrap();
+        assert_eq!(result.reason, RebaseStopReason::Edit);
+        assert_eq!(result.stopped_oid, Some(again.to_string()));
+        assert_eq!(result.current_step, 2);
+
+        let result = continue_rebase_internal(&repo).unwrap();
+        assert_eq!(result.reason, RebaseStopReason::Completed);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("to one again"));
+        assert_eq!(head.tree_id(), head.parent(0).unwrap().tree_id());
+
+        // A pick of the same empty change is dropped instead
+        let plan = vec![
+            item(RebaseAction::Pick, to_one),
+            item(RebaseAction::Drop, back),
+            item(RebaseAction::Pick, again),
+        ];
+        repo.reset(
+            &repo.find_object(again, None).unwrap(),
+            git2::ResetType::Hard,
+            None,
+        )
+        .unwrap();
+        start_rebase_internal(&repo, &base.to_string(), &plan).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("to one"));
+        assert_eq!(head.parent_id(0).unwrap(), base);
+    }
+
+    #[test]
+    fn test_conflict_stops_and_abort_restores() {
+        let (dir, repo) = setup_repo();
+        let base = commit_file(&repo, "f.txt", "base\n", "base");
+        let to_b = commit_file(&repo, "f.txt", "b\n", "to b");
+        let to_c = commit_file(&repo, "f.txt", "c\n", "to c");
+
+        // Applying "to c" directly onto the base conflicts with its missing parent
+        let plan = vec![
+            item(RebaseAction::Pick, to_c),
+            item(RebaseAction::Pick, to_b),
+        ];
+        let result = start_rebase_internal(&repo, &base.to_string(), &plan).unwrap();
+        assert_eq!(result.reason, RebaseStopReason::Conflicts);
+        assert_eq!(result.conflicted_files, vec!["f.txt".to_string()]);
+        assert_eq!(result.stopped_oid, Some(to_c.to_string()));
+        assert_eq!(repo.state(), git2::RepositoryState::RebaseMerge);
+
+        // Continuing with conflicts left is refused
+        assert!(matches!(
+            continue_rebase_internal(&repo),
+            Err(GitError::UnresolvedConflicts(_))
+        ));
+
+        abort_rebase_internal(&repo).unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().target(), Some(to_c));
+        assert_eq!(fs::read_to_string(dir.path().join("f.txt")).unwrap(), "c\n");
+    }
+
+    #[test]
+    fn test_continue_after_resolving_conflict() {
+        let (dir, repo) = setup_repo();
+        let base = commit_file(&repo, "f.txt", "base\n", "base");
+        let _to_b = commit_file(&repo, "f.txt", "b\n", "to b");
+        let to_c = commit_file(&repo, "f.txt", "c\n", "to c");
+
+        let plan = vec![item(RebaseAction::Pick, to_c)];
+        let result = start_rebase_internal(&repo, &base.to_string(), &plan).unwrap();
+        assert_eq!(result.reason, RebaseStopReason::Conflicts);
+
+        fs::write(dir.path().join("f.txt"), "resolved\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("f.txt")).unwrap();
+        index.write().unwrap();
+
+        let result = continue_rebase_internal(&repo).unwrap();
+        assert_eq!(result.reason, RebaseStopReason::Completed);
+
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.message(), Some("to c"));
+        assert_eq!(commit.parent_id(0).unwrap(), base);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_squash_first_is_rejected() {
+        let (_dir, repo) = setup_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        let add_b = commit_file(&repo, "b.txt", "b\n", "add b");
+
+        let result = start_rebase_internal(
+            &repo,
+            &base.to_string(),
+            &[item(RebaseAction::Squash, add_b)],
+        );
+        assert!(matches!(result, Err(GitError::InvalidRebasePlan(_))));
+        assert!(!rebase_dir(&repo).exists());
+    }
+
+    #[test]
+    fn test_merge_commit_is_rejected_before_starting() {
+        let (_dir, repo) = setup_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        let add_b = commit_file(&repo, "b.txt", "b\n", "add b");
+        repo.set_head_detached(base).unwrap();
+        let add_c = commit_file(&repo, "c.txt", "c\n", "add c");
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("b.txt")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parents = [
+            &repo.find_commit(add_c).unwrap(),
+            &repo.find_commit(add_b).unwrap(),
+        ];
+        let merge = repo
+            .commit(Some("HEAD"), &sig, &sig, "merge", &tree, &parents)
+            .unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+
+        let result = start_rebase_internal(
+            &repo,
+            &base.to_string(),
+            &[
+                item(RebaseAction::Pick, add_c),
+                item(RebaseAction::Pick, merge),
+            ],
+        );
+        assert!(matches!(result, Err(GitError::InvalidRebasePlan(_))));
+        assert!(!rebase_dir(&repo).exists());
+        assert_eq!(repo.head().unwrap().target(), Some(merge));
+    }
+
+    #[test]
+    fn test_squash_signs_rewritten_commit() {
+        let (dir, repo) = setup_repo();
+        let base = commit_file(&repo, "a.txt", "a\n", "base");
+        let add_b = commit_file(&repo, "b.txt", "b\n", "add b");
+        let fix_b = commit_file(&repo, "b.txt", "b2\n", "fix b");
+
+        if configure_ssh_signing(&repo, dir.path()).is_none() {
+            return;
+        }
+
+        let plan = vec![
+            item(RebaseAction::Pick, add_b),
+            item(RebaseAction::Squash, fix_b),
+        ];
+        start_rebase_internal(&repo, &base.to_string(), &plan).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_id(0).unwrap(), base);
+        assert_eq!(head.message(), Some("add b\n\nfix b"));
+        let (signature, _) = repo.extract_signature(&head.id(), None).unwrap();
+        assert!(signature.as_str().unwrap().contains("BEGIN SSH SIGNATURE"));
+    }
+}
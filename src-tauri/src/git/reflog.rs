@@ -0,0 +1,499 @@
+//! Reflog browsing and recovery of lost commits.
+//!
+//! `list_reflog` shows the history of any ref. `find_lost_commits` scans the
+//! object database for commits that no branch, tag, remote or stash entry
+//! reaches any more (reset-away work, deleted branches, dropped stashes), so
+//! they can be turned back into a branch with `recover_lost_commit`.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+use crate::git::branch::BranchInfo;
+use crate::git::error::GitError;
+use crate::git::history::CommitSummary;
+use crate::git::repository::RepositoryState;
+use crate::git::signing::{CommitSignature, verify_commit_signatures};
+use crate::git::undo::record_operation;
+
+/// Kind of change a reflog entry records, parsed from its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ReflogAction {
+    Commit,
+    Amend,
+    Reset,
+    Checkout,
+    Merge,
+    Rebase,
+    Pull,
+    CherryPick,
+    Branch,
+    Clone,
+    Other,
+}
+
+/// A single reflog entry.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReflogEntry {
+    /// Position in the reflog (0 = newest), as in `HEAD@{n}`
+    pub index: u32,
+    /// OID the ref pointed to before the change
+    pub old_oid: String,
+    /// OID the ref pointed to after the change
+    pub new_oid: String,
+    /// Parsed kind of change
+    pub action: ReflogAction,
+    /// Raw reflog message
+    pub message: String,
+    /// Who made the change
+    pub committer_name: String,
+    /// Unix timestamp in milliseconds
+    pub timestamp_ms: f64,
+    /// The commit the ref pointed to afterwards (None if it no longer exists)
+    pub commit: Option<CommitSummary>,
+}
+
+/// Why a commit is considered lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum LostCommitKind {
+    /// A regular commit no ref reaches
+    Commit,
+    /// A stash that was dropped or cleared
+    Stash,
+}
+
+/// A commit that is no longer reachable from any ref.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LostCommit {
+    pub commit: CommitSummary,
+    pub kind: LostCommitKind,
+    /// Reflog that still mentions the commit (e.g. "HEAD"), if any
+    pub found_in_reflog: Option<String>,
+}
+
+/// Classify a reflog message such as `commit: Fix bug` or `checkout: moving from a to b`.
+pub(crate) fn classify_reflog_message(msg: &str) -> ReflogAction {
+    if msg.starts_with("commit (amend):") {
+        ReflogAction::Amend
+    } else if msg.starts_with("commit") {
+        ReflogAction::Commit
+    } else if msg.starts_with("reset:") {
+        ReflogAction::Reset
+    } else if msg.starts_with("checkout:") {
+        ReflogAction::Checkout
+    } else if msg.starts_with("merge") {
+        ReflogAction::Merge
+    } else if msg.starts_with("rebase") {
+        ReflogAction::Rebase
+    } else if msg.starts_with("pull") {
+        ReflogAction::Pull
+    } else if msg.starts_with("cherry-pick") {
+        ReflogAction::CherryPick
+    } else if msg.starts_with("branch:") {
+        ReflogAction::Branch
+    } else if msg.starts_with("clone:") {
+        ReflogAction::Clone
+    } else {
+        ReflogAction::Other
+    }
+}
+
+/// Resolve a user-supplied ref name ("HEAD", "main", "origin/main", "refs/stash").
+fn resolve_ref_name(repo: &git2::Repository, name: &str) -> Result<String, GitError> {
+    if name == "HEAD" || repo.find_reference(name).is_ok() {
+        return Ok(name.to_string());
+    }
+
+    let reference = repo
+        .resolve_reference_from_short_name(name)
+        .map_err(|_| GitError::NotFound(format!("Reference not found: {}", name)))?;
+    reference
+        .name()
+        .map(|n| n.to_string())
+        .ok_or_else(|| GitError::Internal("Reference name is not valid UTF-8".to_string()))
+}
+
+pub fn list_reflog_internal(
+    repo: &git2::Repository,
+    ref_name: &str,
+    limit: usize,
+) -> Result<Vec<ReflogEntry>, GitError> {
+    let full_name = resolve_ref_name(repo, ref_name)?;
+    let reflog = repo.reflog(&full_name)?;
+    let oids: Vec<git2::Oid> = reflog.iter().take(limit).map(|e| e.id_new()).collect();
+    let signatures = verify_commit_signatures(repo, &oids);
+
+    Ok(reflog
+        .iter()
+        .take(limit)
+        .enumerate()
+        .map(|(index, entry)| {
+            let message = entry.message().unwrap_or("").to_string();
+            let committer = entry.committer();
+
+            ReflogEntry {
+                index: index as u32,
+                old_oid: entry.id_old().to_string(),
+                new_oid: entry.id_new().to_string(),
+                action: classify_reflog_message(&message),
+                committer_name: committer.name().unwrap_or("Unknown").to_string(),
+                timestamp_ms: (committer.when().seconds() as f64) * 1000.0,
+                commit: repo.find_commit(entry.id_new()).ok().map(|c| {
+                    let signature = signatures
+                        .get(&c.id())
+                        .cloned()
+                        .unwrap_or_else(CommitSignature::unsigned);
+                    CommitSummary::from_commit(&c, signature)
+                }),
+                message,
+            }
+        })
+        .collect())
+}
+
+/// Stash commits are merges of HEAD and an "index on ..." commit.
+fn is_stash_commit(commit: &git2::Commit) -> bool {
+    let message = commit.message().unwrap_or("");
+    if commit.parent_count() < 2 || !(message.starts_with("WIP on ") || message.starts_with("On "))
+    {
+        return false;
+    }
+
+    commit
+        .parent(1)
+        .map(|p| p.message().unwrap_or("").starts_with("index on "))
+        .unwrap_or(false)
+}
+
+/// Every commit reachable from a ref, detached HEAD or listed stash.
+fn reachable_commits(repo: &git2::Repository) -> Result<HashSet<git2::Oid>, GitError> {
+    let mut revwalk = repo.revwalk()?;
+
+    for reference in repo.references()?.flatten() {
+        if let Ok(commit) = reference.peel_to_commit() {
+            revwalk.push(commit.id())?;
+        }
+    }
+    if let Ok(head) = repo.head()
+        && let Some(oid) = head.target()
+    {
+        revwalk.push(oid)?;
+    }
+    if repo.find_reference("refs/stash").is_ok() {
+        for entry in repo.reflog("refs/stash")?.iter() {
+            revwalk.push(entry.id_new())?;
+        }
+    }
+
+    let mut reachable = HashSet::new();
+    for oid in revwalk {
+        reachable.insert(oid?);
+    }
+    Ok(reachable)
+}
+
+/// Map of commit OID to the first reflog (HEAD, then branches) that mentions it.
+fn reflog_mentions(repo: &git2::Repository) -> HashMap<git2::Oid, String> {
+    let mut names = vec!["HEAD".to_string()];
+    if let Ok(references) = repo.references_glob("refs/heads/*") {
+        names.extend(
+            references
+                .flatten()
+                .filter_map(|r| r.name().map(|n| n.to_string())),
+        );
+    }
+
+    let mut mentions = HashMap::new();
+    for name in names {
+        let Ok(reflog) = repo.reflog(&name) else {
+            continue;
+        };
+        for entry in reflog.iter() {
+            for oid in [entry.id_new(), entry.id_old()] {
+                mentions.entry(oid).or_insert_with(|| name.clone());
+            }
+        }
+    }
+    mentions
+}
+
+pub fn find_lost_commits_internal(
+    repo: &git2::Repository,
+    limit: usize,
+) -> Result<Vec<LostCommit>, GitError> {
+    let reachable = reachable_commits(repo)?;
+
+    let odb = repo.odb()?;
+    let mut all_oids = Vec::new();
+    odb.foreach(|oid| {
+        all_oids.push(*oid);
+        true
+    })?;
+
+    let mut unreachable = Vec::new();
+    for oid in all_oids {
+        if reachable.contains(&oid) {
+            continue;
+        }
+        if let Ok((_, git2::ObjectType::Commit)) = odb.read_header(oid) {
+            unreachable.push(repo.find_commit(oid)?);
+        }
+    }
+
+    // Only report tips: a lost commit whose child is also lost is recovered with it
+    let lost_parents: HashSet<git2::Oid> = unreachable
+        .iter()
+        .flat_map(|c| c.parent_ids().collect::<Vec<_>>())
+        .collect();
+    let mut tips: Vec<git2::Commit> = unreachable
+        .into_iter()
+        .filter(|c| !lost_parents.contains(&c.id()))
+        .collect();
+    tips.sort_by_key(|c| std::cmp::Reverse(c.committer().when().seconds()));
+
+    let mentions = reflog_mentions(repo);
+    tips.truncate(limit);
+    let summaries = CommitSummary::from_commits(repo, &tips);
+
+    Ok(tips
+        .iter()
+        .zip(summaries)
+        .map(|(commit, summary)| LostCommit {
+            commit: summary,
+            kind: if is_stash_commit(commit) {
+                LostCommitKind::Stash
+            } else {
+                LostCommitKind::Commit
+            },
+            found_in_reflog: mentions.get(&commit.id()).cloned(),
+        })
+        .collect())
+}
+
+pub fn recover_lost_commit_internal(
+    repo: &git2::Repository,
+    oid: &str,
+    branch_name: &str,
+) -> Result<BranchInfo, GitError> {
+    if !git2::Branch::name_is_valid(branch_name)? {
+        return Err(GitError::InvalidBranchName(branch_name.to_string()));
+    }
+    if repo
+        .find_branch(branch_name, git2::BranchType::Local)
+        .is_ok()
+    {
+        return Err(GitError::BranchAlreadyExists(branch_name.to_string()));
+    }
+
+    let oid = git2::Oid::from_str(oid)
+        .map_err(|_| GitError::NotFound(format!("Invalid commit OID: {}", oid)))?;
+    let commit = repo.find_commit(oid)?;
+    repo.branch(branch_name, &commit, false)?;
+
+    Ok(BranchInfo {
+        name: branch_name.to_string(),
+        is_head: false,
+        last_commit_oid: format!("{:.7}", commit.id()),
+        last_commit_message: commit.summary().unwrap_or("").to_string(),
+        is_merged: None,
+        is_remote: false,
+        remote_name: None,
+        description: None,
+    })
+}
+
+/// List the reflog of a ref, newest first.
+///
+/// `ref_name` may be "HEAD", a full ref name or a short branch name.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_reflog(
+    ref_name: String,
+    limit: Option<u32>,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<ReflogEntry>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    let limit = limit.unwrap_or(100) as usize;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        list_reflog_internal(&repo, &ref_name, limit)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Scan for commits no ref reaches any more, including dropped stashes.
+///
+/// Only the tip of each lost line of history is returned, newest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn find_lost_commits(
+    limit: Option<u32>,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<LostCommit>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    let limit = limit.unwrap_or(50) as usize;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        find_lost_commits_internal(&repo, limit)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Create a branch pointing at a lost commit.
+#[tauri::command]
+#[specta::specta]
+pub async fn recover_lost_commit(
+    oid: String,
+    branch_name: String,
+    state: State<'_, RepositoryState>,
+) -> Result<BranchInfo, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        format!("Recover {:.7} as '{}'", oid, branch_name),
+        move || {
+            let repo = git2::Repository::open(&repo_path)?;
+            recover_lost_commit_internal(&repo, &oid, &branch_name)
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::signing::{SignatureStatus, create_commit_signed};
+    use crate::git::test_support::{commit_file, configure_ssh_signing, setup_repo};
+    use std::fs;
+
+    #[test]
+    fn test_classify_reflog_message() {
+        assert_eq!(
+            classify_reflog_message("commit: Fix bug"),
+            ReflogAction::Commit
+        );
+        assert_eq!(
+            classify_reflog_message("commit (initial): Init"),
+            ReflogAction::Commit
+        );
+        assert_eq!(
+            classify_reflog_message("commit (amend): Fix"),
+            ReflogAction::Amend
+        );
+        assert_eq!(
+            classify_reflog_message("checkout: moving from main to dev"),
+            ReflogAction::Checkout
+        );
+        assert_eq!(
+            classify_reflog_message("something else"),
+            ReflogAction::Other
+        );
+    }
+
+    #[test]
+    fn test_list_reflog_by_short_name() {
+        let (_dir, repo) = setup_repo();
+        commit_file(&repo, "a.txt", "first", "first");
+        let second = commit_file(&repo, "b.txt", "second", "second");
+
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        let entries = list_reflog_internal(&repo, &branch, 10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].new_oid, second.to_string());
+        assert_eq!(entries[0].action, ReflogAction::Commit);
+        assert_eq!(
+            entries[0].commit.as_ref().unwrap().message_subject,
+            "second"
+        );
+
+        assert_eq!(list_reflog_internal(&repo, "HEAD", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_repeated_commit_keeps_its_signature() {
+        let (dir, repo) = setup_repo();
+        if configure_ssh_signing(&repo, dir.path()).is_none() {
+            return;
+        }
+        let sig = repo.signature().unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let signed =
+            create_commit_signed(&repo, Some("HEAD"), &sig, &sig, "signed", &tree, &[]).unwrap();
+        let next = commit_file(&repo, "a.txt", "next", "next");
+        // Checking out the signed commit again logs it a second time
+        repo.set_head_detached(signed).unwrap();
+
+        let entries = list_reflog_internal(&repo, "HEAD", 10).unwrap();
+        let statuses: Vec<_> = entries
+            .iter()
+            .filter(|e| e.new_oid == signed.to_string())
+            .map(|e| e.commit.as_ref().unwrap().signature.status)
+            .collect();
+        assert_eq!(statuses.len(), 2);
+        assert_ne!(statuses[0], SignatureStatus::Unsigned);
+        assert_eq!(statuses[0], statuses[1]);
+        assert!(entries.iter().any(|e| e.new_oid == next.to_string()));
+    }
+
+    #[test]
+    fn test_finds_reset_away_commit_and_recovers_it() {
+        let (_dir, repo) = setup_repo();
+        let first = commit_file(&repo, "a.txt", "first", "first");
+        commit_file(&repo, "b.txt", "second", "second");
+        let third = commit_file(&repo, "c.txt", "third", "third");
+
+        let target = repo.find_commit(first).unwrap();
+        repo.reset(target.as_object(), git2::ResetType::Hard, None)
+            .unwrap();
+
+        let lost = find_lost_commits_internal(&repo, 10).unwrap();
+        assert_eq!(lost.len(), 1);
+        assert_eq!(lost[0].commit.oid, third.to_string());
+        assert_eq!(lost[0].kind, LostCommitKind::Commit);
+        assert_eq!(lost[0].found_in_reflog.as_deref(), Some("HEAD"));
+
+        let branch = recover_lost_commit_internal(&repo, &third.to_string(), "rescued").unwrap();
+        assert_eq!(branch.last_commit_message, "third");
+        assert!(find_lost_commits_internal(&repo, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_finds_dropped_stash() {
+        let (dir, mut repo) = setup_repo();
+        commit_file(&repo, "a.txt", "first", "first");
+        fs::write(dir.path().join("a.txt"), "changed").unwrap();
+
+        let sig = repo.signature().unwrap();
+        let stash = repo.stash_save(&sig, "wip", None).unwrap();
+        assert!(find_lost_commits_internal(&repo, 10).unwrap().is_empty());
+
+        repo.stash_drop(0).unwrap();
+        let lost = find_lost_commits_internal(&repo, 10).unwrap();
+        assert_eq!(lost.len(), 1);
+        assert_eq!(lost[0].commit.oid, stash.to_string());
+        assert_eq!(lost[0].kind, LostCommitKind::Stash);
+    }
+}
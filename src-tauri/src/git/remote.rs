@@ -1,16 +1,32 @@
-//! Remote operations: push, pull, fetch with progress streaming.
+//! Remote operations: management of configured remotes, and push, pull,
+//! fetch with progress streaming.
 //!
 //! Uses Tauri Channels for real-time progress events.
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use tauri::ipc::Channel;
+use std::sync::{Mutex, PoisonError};
 use tauri::State;
+use tauri::ipc::Channel;
 
-use crate::git::credentials::create_credentials_callback;
-use crate::git::error::GitError;
 use crate::git::RepositoryState;
+use crate::git::cancellation::{CancellationToken, OperationRegistry};
+use crate::git::credentials::CredentialSession;
+use crate::git::error::GitError;
+use crate::git::hooks::{HookEvent, run_hook};
+use crate::git::merge::commit_merge;
+use crate::git::network::{NetworkSettings, create_certificate_check};
+use crate::git::rebase::{
+    RebaseStopReason, committer_signature, get_rebase_todo_internal, has_uncommitted_changes,
+    start_rebase_internal,
+};
+use crate::git::signing::create_commit_signed;
+use crate::git::stash::{stash_pop_internal, stash_save_internal};
+use crate::git::undo::record_operation;
+use crate::github::remote::parse_github_url;
 
 /// Progress events for remote sync operations.
 /// Uses tagged enum serialization for frontend type safety.
@@ -37,9 +53,29 @@ pub enum SyncProgress {
         current: u32,
         total: u32,
     },
+    HookOutput {
+        hook: String,
+        line: String,
+    },
+    /// Transfer progress of one remote while several are fetched at once
+    RemoteTransferring {
+        remote: String,
+        current: u32,
+        total: u32,
+        bytes: u32,
+    },
+    /// One remote finished while several are fetched at once
+    RemoteFinished {
+        remote: String,
+        success: bool,
+    },
     Finished {
         operation: String,
     },
+    /// The operation was cancelled through `cancel_operation`
+    Cancelled {
+        operation: String,
+    },
     Error {
         message: String,
     },
@@ -52,6 +88,48 @@ pub struct SyncResult {
     pub success: bool,
     pub message: String,
     pub commits_transferred: u32,
+    /// Per-ref results reported by the remote (push only)
+    pub ref_updates: Vec<RefUpdateResult>,
+    /// Remote-tracking refs and tags deleted by pruning (fetch only)
+    pub pruned_refs: Vec<String>,
+}
+
+/// Which tags a fetch downloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TagFollowing {
+    /// Tags pointing at fetched commits (git's default)
+    Auto,
+    /// All tags (`--tags`)
+    All,
+    /// No tags (`--no-tags`)
+    None,
+}
+
+/// How to fetch; unset fields fall back to the repository's configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FetchOptions {
+    /// Delete remote-tracking refs that no longer exist on the remote
+    /// (`fetch.prune` / `remote.<name>.prune` when unset)
+    pub prune: Option<bool>,
+    /// Also delete local tags that no longer exist on the remote
+    /// (`fetch.pruneTags` / `remote.<name>.pruneTags` when unset)
+    pub prune_tags: Option<bool>,
+    /// Tag following (`remote.<name>.tagOpt` when unset)
+    pub tags: Option<TagFollowing>,
+    /// Limit history to this many commits from the remote tips (`--depth`)
+    pub depth: Option<u32>,
+    /// Fetch the complete history of a shallow repository (`--unshallow`)
+    pub unshallow: bool,
+}
+
+/// Outcome of fetching one remote as part of [`fetch_all`].
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteFetchResult {
+    pub remote: String,
+    pub result: SyncResult,
 }
 
 /// Information about a configured remote.
@@ -60,6 +138,32 @@ pub struct SyncResult {
 pub struct RemoteInfo {
     pub name: String,
     pub url: String,
+    /// Separate push URL (`remote.<name>.pushurl`), if configured
+    pub push_url: Option<String>,
+    /// Fetch refspecs (`remote.<name>.fetch`)
+    pub fetch_refspecs: Vec<String>,
+    /// GitHub owner, when the URL points at github.com
+    pub github_owner: Option<String>,
+    /// GitHub repository name, when the URL points at github.com
+    pub github_repo: Option<String>,
+}
+
+impl RemoteInfo {
+    fn from_remote(name: &str, remote: &git2::Remote) -> Self {
+        let url = remote.url().unwrap_or("").to_string();
+        let github = parse_github_url(&url);
+        Self {
+            name: name.to_string(),
+            push_url: remote.pushurl().map(str::to_string),
+            fetch_refspecs: remote
+                .fetch_refspecs()
+                .map(|specs| specs.iter().flatten().map(str::to_string).collect())
+                .unwrap_or_default(),
+            github_owner: github.as_ref().map(|(owner, _)| owner.clone()),
+            github_repo: github.map(|(_, repo)| repo),
+            url,
+        }
+    }
 }
 
 /// Helper to get repository path or return error.
@@ -83,10 +187,7 @@ pub async fn get_remotes(state: State<'_, RepositoryState>) -> Result<Vec<Remote
         let mut result = Vec::new();
         for name in remotes.iter().flatten() {
             if let Ok(remote) = repo.find_remote(name) {
-                result.push(RemoteInfo {
-                    name: name.to_string(),
-                    url: remote.url().unwrap_or("").to_string(),
-                });
+                result.push(RemoteInfo::from_remote(name, &remote));
             }
         }
 
@@ -96,6 +197,296 @@ pub async fn get_remotes(state: State<'_, RepositoryState>) -> Result<Vec<Remote
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
+/// Check that a remote URL is something git can talk to.
+///
+/// Accepts URLs with a transport scheme, scp-like `user@host:path` and
+/// existing local paths.
+fn validate_remote_url(url: &str) -> Result<(), GitError> {
+    let url = url.trim();
+    if url.is_empty() || url.chars().any(char::is_whitespace) {
+        return Err(GitError::InvalidUrl(url.to_string()));
+    }
+
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let known = matches!(scheme, "https" | "http" | "ssh" | "git" | "file")
+            || scheme.starts_with("git+")
+            || scheme.ends_with("+ssh");
+        if known && !rest.is_empty() {
+            return Ok(());
+        }
+        return Err(GitError::InvalidUrl(url.to_string()));
+    }
+
+    // scp-like syntax: [user@]host:path (a colon before any slash)
+    if let Some((host, path)) = url.split_once(':')
+        && !host.is_empty()
+        && !host.contains('/')
+        && !path.is_empty()
+        && host.len() > 1
+    {
+        return Ok(());
+    }
+
+    if std::path::Path::new(url).exists() {
+        return Ok(());
+    }
+
+    Err(GitError::InvalidUrl(url.to_string()))
+}
+
+fn validate_remote_name(name: &str) -> Result<(), GitError> {
+    if git2::Remote::is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(GitError::OperationFailed(format!(
+            "Invalid remote name: {}",
+            name
+        )))
+    }
+}
+
+fn find_remote<'r>(repo: &'r git2::Repository, name: &str) -> Result<git2::Remote<'r>, GitError> {
+    repo.find_remote(name)
+        .map_err(|_| GitError::RemoteNotFound(name.to_string()))
+}
+
+pub fn add_remote_internal(
+    repo: &git2::Repository,
+    name: &str,
+    url: &str,
+) -> Result<RemoteInfo, GitError> {
+    validate_remote_name(name)?;
+    validate_remote_url(url)?;
+    if repo.find_remote(name).is_ok() {
+        return Err(GitError::OperationFailed(format!(
+            "Remote already exists: {}",
+            name
+        )));
+    }
+
+    let remote = repo.remote(name, url.trim())?;
+    Ok(RemoteInfo::from_remote(name, &remote))
+}
+
+/// Remove a remote along with its remote-tracking branches and config.
+pub fn remove_remote_internal(repo: &git2::Repository, name: &str) -> Result<(), GitError> {
+    find_remote(repo, name)?;
+    repo.remote_delete(name)?;
+    Ok(())
+}
+
+/// A renamed remote and the refspecs libgit2 could not rename.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedRemote {
+    pub remote: RemoteInfo,
+    /// Non-default fetch refspecs left unchanged, which may need editing by hand
+    pub problems: Vec<String>,
+}
+
+/// Rename a remote, moving its remote-tracking branches and upstream config.
+pub fn rename_remote_internal(
+    repo: &git2::Repository,
+    old_name: &str,
+    new_name: &str,
+) -> Result<RenamedRemote, GitError> {
+    validate_remote_name(new_name)?;
+    find_remote(repo, old_name)?;
+    if repo.find_remote(new_name).is_ok() {
+        return Err(GitError::OperationFailed(format!(
+            "Remote already exists: {}",
+            new_name
+        )));
+    }
+
+    // Non-default refspecs are left untouched by libgit2; they are reported, not fatal
+    let problems = repo
+        .remote_rename(old_name, new_name)?
+        .iter()
+        .flatten()
+        .map(str::to_string)
+        .collect();
+    let remote = find_remote(repo, new_name)?;
+    Ok(RenamedRemote {
+        remote: RemoteInfo::from_remote(new_name, &remote),
+        problems,
+    })
+}
+
+/// Set the fetch URL, or the push URL when `push` is true (`None` clears a push URL).
+pub fn set_remote_url_internal(
+    repo: &git2::Repository,
+    name: &str,
+    url: Option<&str>,
+    push: bool,
+) -> Result<RemoteInfo, GitError> {
+    find_remote(repo, name)?;
+    if let Some(url) = url {
+        validate_remote_url(url)?;
+    }
+
+    match (url.map(str::trim), push) {
+        (Some(url), false) => repo.remote_set_url(name, url)?,
+        (None, false) => {
+            return Err(GitError::InvalidUrl(
+                "A remote needs a fetch URL".to_string(),
+            ));
+        }
+        (url, true) => repo.remote_set_pushurl(name, url)?,
+    }
+
+    let remote = find_remote(repo, name)?;
+    Ok(RemoteInfo::from_remote(name, &remote))
+}
+
+/// Replace a remote's fetch refspecs with a single one.
+///
+/// `None` restores the default `+refs/heads/*:refs/remotes/<name>/*`.
+pub fn set_remote_fetch_refspec_internal(
+    repo: &git2::Repository,
+    name: &str,
+    refspec: Option<&str>,
+) -> Result<RemoteInfo, GitError> {
+    find_remote(repo, name)?;
+    let refspec = refspec
+        .map(|r| r.trim().to_string())
+        .unwrap_or_else(|| format!("+refs/heads/*:refs/remotes/{}/*", name));
+
+    // A refspec is "[+]<src>:<dst>"; validate it before touching the config
+    let (src, dst) = refspec
+        .trim_start_matches('+')
+        .split_once(':')
+        .ok_or_else(|| GitError::OperationFailed(format!("Invalid refspec: {}", refspec)))?;
+    if src.is_empty() || dst.is_empty() || !dst.starts_with("refs/") {
+        return Err(GitError::OperationFailed(format!(
+            "Invalid refspec: {}",
+            refspec
+        )));
+    }
+
+    let mut config = repo.config()?;
+    let key = format!("remote.{}.fetch", name);
+    match config.remove_multivar(&key, ".*") {
+        Ok(()) => {}
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    repo.remote_add_fetch(name, &refspec)?;
+
+    let remote = find_remote(repo, name)?;
+    Ok(RemoteInfo::from_remote(name, &remote))
+}
+
+/// Add a new remote.
+#[tauri::command]
+#[specta::specta]
+pub async fn add_remote(
+    name: String,
+    url: String,
+    state: State<'_, RepositoryState>,
+) -> Result<RemoteInfo, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        add_remote_internal(&repo, &name, &url)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Remove a remote and its remote-tracking branches.
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_remote(
+    name: String,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = get_repo_path(&state).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        remove_remote_internal(&repo, &name)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Rename a remote.
+///
+/// Fetch refspecs that do not follow the default pattern are kept as they
+/// are and listed in `problems`.
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_remote(
+    old_name: String,
+    new_name: String,
+    state: State<'_, RepositoryState>,
+) -> Result<RenamedRemote, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        rename_remote_internal(&repo, &old_name, &new_name)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Change a remote's fetch URL.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_remote_url(
+    name: String,
+    url: String,
+    state: State<'_, RepositoryState>,
+) -> Result<RemoteInfo, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        set_remote_url_internal(&repo, &name, Some(&url), false)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Set a separate push URL for a remote; `None` pushes to the fetch URL again.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_remote_push_url(
+    name: String,
+    url: Option<String>,
+    state: State<'_, RepositoryState>,
+) -> Result<RemoteInfo, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        set_remote_url_internal(&repo, &name, url.as_deref(), true)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Replace a remote's fetch refspecs; `None` restores the default refspec.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_remote_fetch_refspec(
+    name: String,
+    refspec: Option<String>,
+    state: State<'_, RepositoryState>,
+) -> Result<RemoteInfo, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        set_remote_fetch_refspec_internal(&repo, &name, refspec.as_deref())
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
 /// Fetch from a remote without merging.
 ///
 /// Downloads objects and refs from the remote but does not modify
@@ -105,9 +496,14 @@ pub async fn get_remotes(state: State<'_, RepositoryState>) -> Result<Vec<Remote
 pub async fn fetch_from_remote(
     remote: String,
     on_progress: Channel<SyncProgress>,
+    options: Option<FetchOptions>,
+    operation_id: Option<String>,
     state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
 ) -> Result<SyncResult, GitError> {
     let repo_path = get_repo_path(&state).await?;
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
 
     on_progress
         .send(SyncProgress::Started {
@@ -119,296 +515,1980 @@ pub async fn fetch_from_remote(
     let progress_channel = on_progress.clone();
     let result = tokio::task::spawn_blocking(move || {
         let repo = git2::Repository::open(&repo_path)?;
+        let outcome = fetch_remote(
+            &repo,
+            &remote_name,
+            &options.unwrap_or_default(),
+            &|progress| {
+                let _ = progress_channel.send(progress);
+            },
+            &cancel,
+        )?;
 
-        let mut remote_obj = repo
-            .find_remote(&remote_name)
-            .map_err(|_| GitError::RemoteNotFound(remote_name.clone()))?;
-
-        let mut callbacks = git2::RemoteCallbacks::new();
-
-        // Transfer progress
-        let progress_transfer = progress_channel.clone();
-        callbacks.transfer_progress(move |stats| {
-            let _ = progress_transfer.send(SyncProgress::Transferring {
-                current: stats.received_objects() as u32,
-                total: stats.total_objects() as u32,
-                bytes: stats.received_bytes() as u32,
-            });
-            true
-        });
-
-        // Credentials
-        callbacks.credentials(create_credentials_callback());
-
-        let mut opts = git2::FetchOptions::new();
-        opts.remote_callbacks(callbacks);
-
-        // Fetch all branches (empty refspec = default)
-        remote_obj
-            .fetch(&[] as &[&str], Some(&mut opts), None)
-            .map_err(|e| {
-                if e.class() == git2::ErrorClass::Net {
-                    GitError::NetworkError(e.message().to_string())
-                } else if e.class() == git2::ErrorClass::Ssh {
-                    GitError::AuthenticationFailed(e.message().to_string())
-                } else {
-                    GitError::OperationFailed(e.message().to_string())
-                }
-            })?;
-
-        let stats = remote_obj.stats();
         Ok::<_, GitError>(SyncResult {
             success: true,
             message: format!("Fetched from {}", remote_name),
-            commits_transferred: stats.received_objects() as u32,
+            commits_transferred: outcome.objects_received,
+            ref_updates: Vec::new(),
+            pruned_refs: outcome.pruned_refs,
         })
     })
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))??;
-
-    on_progress
-        .send(SyncProgress::Finished {
-            operation: "fetch".to_string(),
-        })
-        .ok();
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?;
 
-    Ok(result)
+    send_outcome(&on_progress, "fetch", &result);
+    result
 }
 
-/// Push current branch to a remote.
+/// Fetch every configured remote in parallel.
 ///
-/// Sends local commits to the remote repository.
+/// Each remote downloads on its own thread with its own repository handle,
+/// but refs are updated one remote at a time so tag refs never race; a
+/// failing remote does not stop the others and is reported in its result.
+pub fn fetch_all_internal(
+    repo_path: &std::path::Path,
+    options: &FetchOptions,
+    on_progress: &(dyn Fn(SyncProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<Vec<RemoteFetchResult>, GitError> {
+    let repo = git2::Repository::open(repo_path)?;
+    let names: Vec<String> = repo
+        .remotes()?
+        .iter()
+        .flatten()
+        .map(str::to_string)
+        .collect();
+
+    let ref_lock = &Mutex::new(());
+    let results = std::thread::scope(|scope| {
+        let handles: Vec<_> = names
+            .iter()
+            .map(|name| {
+                scope.spawn(move || {
+                    let forward = |progress| match progress {
+                        SyncProgress::Transferring {
+                            current,
+                            total,
+                            bytes,
+                        } => on_progress(SyncProgress::RemoteTransferring {
+                            remote: name.clone(),
+                            current,
+                            total,
+                            bytes,
+                        }),
+                        other => on_progress(other),
+                    };
+                    let outcome = git2::Repository::open(repo_path)
+                        .map_err(GitError::from)
+                        .and_then(|repo| {
+                            fetch_remote_with(
+                                &repo,
+                                name,
+                                options,
+                                &forward,
+                                cancel,
+                                Some(ref_lock),
+                            )
+                        });
+
+                    on_progress(SyncProgress::RemoteFinished {
+                        remote: name.clone(),
+                        success: outcome.is_ok(),
+                    });
+
+                    let result = match outcome {
+                        Ok(outcome) => SyncResult {
+                            success: true,
+                            message: format!("Fetched from {}", name),
+                            commits_transferred: outcome.objects_received,
+                            ref_updates: Vec::new(),
+                            pruned_refs: outcome.pruned_refs,
+                        },
+                        Err(e) => SyncResult {
+                            success: false,
+                            message: e.to_string(),
+                            commits_transferred: 0,
+                            ref_updates: Vec::new(),
+                            pruned_refs: Vec::new(),
+                        },
+                    };
+                    RemoteFetchResult {
+                        remote: name.clone(),
+                        result,
+                    }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .map_err(|_| GitError::Internal("Fetch thread panicked".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    cancel.check()?;
+
+    Ok(results)
+}
+
+/// Fetch all remotes in parallel, optionally pruning stale refs.
 #[tauri::command]
 #[specta::specta]
-pub async fn push_to_remote(
-    remote: String,
+pub async fn fetch_all(
     on_progress: Channel<SyncProgress>,
+    options: Option<FetchOptions>,
+    operation_id: Option<String>,
     state: State<'_, RepositoryState>,
-) -> Result<SyncResult, GitError> {
+    registry: State<'_, OperationRegistry>,
+) -> Result<Vec<RemoteFetchResult>, GitError> {
     let repo_path = get_repo_path(&state).await?;
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
 
     on_progress
         .send(SyncProgress::Started {
-            operation: "push".to_string(),
+            operation: "fetch".to_string(),
         })
         .ok();
 
-    let remote_name = remote.clone();
     let progress_channel = on_progress.clone();
-    let result = tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
+    let results = tokio::task::spawn_blocking(move || {
+        fetch_all_internal(
+            &repo_path,
+            &options.unwrap_or_default(),
+            &|progress| {
+                let _ = progress_channel.send(progress);
+            },
+            &cancel,
+        )
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?;
+
+    send_outcome(&on_progress, "fetch", &results);
+    results
+}
+
+/// What to push; every field is optional.
+///
+/// The default pushes the current branch to the same-named remote branch,
+/// like a plain `git push <remote> <branch>`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PushOptions {
+    /// Local branch to push (defaults to the current branch)
+    pub branch: Option<String>,
+    /// Remote branch name to push to (defaults to the local branch name)
+    pub remote_branch: Option<String>,
+    /// Don't push a branch, only the tags and deletions below
+    pub skip_branch: bool,
+    /// Force the branch update, but only if the remote branch still points
+    /// where our remote-tracking ref says (`--force-with-lease`)
+    pub force_with_lease: bool,
+    /// Record the remote branch as the upstream of the pushed branch
+    pub set_upstream: bool,
+    /// Push every local tag
+    pub all_tags: bool,
+    /// Push these tags
+    pub tags: Vec<String>,
+    /// Delete these branches on the remote
+    pub delete_branches: Vec<String>,
+    /// Delete these tags on the remote
+    pub delete_tags: Vec<String>,
+}
+
+/// Outcome of one remote ref update reported by the server.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RefUpdateResult {
+    /// Full remote ref name (e.g. `refs/heads/main`)
+    pub remote_ref: String,
+    /// Local ref pushed to it; `None` for deletions
+    pub local_ref: Option<String>,
+    pub success: bool,
+    /// Rejection reason from the server when `success` is false
+    pub message: Option<String>,
+}
+
+/// One ref update planned for a push.
+struct PlannedUpdate {
+    local_ref: Option<String>,
+    local_oid: git2::Oid,
+    remote_ref: String,
+    force: bool,
+}
+
+impl PlannedUpdate {
+    fn refspec(&self) -> String {
+        format!(
+            "{}{}:{}",
+            if self.force { "+" } else { "" },
+            self.local_ref.as_deref().unwrap_or(""),
+            self.remote_ref
+        )
+    }
+}
+
+fn map_push_error(e: git2::Error) -> GitError {
+    if e.class() == git2::ErrorClass::Net {
+        GitError::NetworkError(e.message().to_string())
+    } else if e.class() == git2::ErrorClass::Ssh {
+        GitError::AuthenticationFailed(e.message().to_string())
+    } else if e.message().contains("rejected") {
+        GitError::PushRejected(e.message().to_string())
+    } else {
+        GitError::OperationFailed(e.message().to_string())
+    }
+}
+
+/// Push branches, tags and deletions to a remote.
+///
+/// Runs the `pre-push` hook, enforces `force_with_lease` against the
+/// remote's current value during negotiation, and reports each ref update.
+pub fn push_internal(
+    repo: &git2::Repository,
+    remote_name: &str,
+    options: &PushOptions,
+    on_progress: &(dyn Fn(SyncProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<SyncResult, GitError> {
+    let mut remote_obj = repo
+        .find_remote(remote_name)
+        .map_err(|_| GitError::RemoteNotFound(remote_name.to_string()))?;
+
+    let mut planned: Vec<PlannedUpdate> = Vec::new();
+    let mut pushed_branch: Option<(String, String)> = None;
 
-        let mut remote_obj = repo
-            .find_remote(&remote_name)
-            .map_err(|_| GitError::RemoteNotFound(remote_name.clone()))?;
+    if !options.skip_branch {
+        let branch_name = match &options.branch {
+            Some(name) => name.clone(),
+            None => {
+                let head = repo.head()?;
+                if !head.is_branch() {
+                    return Err(GitError::OperationFailed(
+                        "Cannot determine current branch".to_string(),
+                    ));
+                }
+                head.shorthand()
+                    .ok_or_else(|| {
+                        GitError::OperationFailed("Cannot determine current branch".to_string())
+                    })?
+                    .to_string()
+            }
+        };
+        let remote_branch = options
+            .remote_branch
+            .clone()
+            .unwrap_or_else(|| branch_name.clone());
+        let local_ref = format!("refs/heads/{}", branch_name);
+        let local_oid = repo
+            .refname_to_id(&local_ref)
+            .map_err(|_| GitError::BranchNotFound(branch_name.clone()))?;
 
-        // Get current branch name
-        let head = repo.head()?;
-        let branch_name = head.shorthand().ok_or_else(|| {
-            GitError::OperationFailed("Cannot determine current branch".to_string())
-        })?;
+        planned.push(PlannedUpdate {
+            local_ref: Some(local_ref),
+            local_oid,
+            remote_ref: format!("refs/heads/{}", remote_branch),
+            force: options.force_with_lease,
+        });
+        pushed_branch = Some((branch_name, remote_branch));
+    }
 
-        // Build refspec for current branch
-        let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+    let mut tags: Vec<String> = options.tags.clone();
+    if options.all_tags {
+        tags.extend(repo.tag_names(None)?.iter().flatten().map(str::to_string));
+    }
+    tags.sort();
+    tags.dedup();
+    for tag in tags {
+        let tag_ref = format!("refs/tags/{}", tag);
+        let local_oid = repo
+            .refname_to_id(&tag_ref)
+            .map_err(|_| GitError::TagNotFound(tag.clone()))?;
+        planned.push(PlannedUpdate {
+            local_ref: Some(tag_ref.clone()),
+            local_oid,
+            remote_ref: tag_ref,
+            force: false,
+        });
+    }
+
+    for branch in &options.delete_branches {
+        planned.push(PlannedUpdate {
+            local_ref: None,
+            local_oid: git2::Oid::zero(),
+            remote_ref: format!("refs/heads/{}", branch),
+            force: false,
+        });
+    }
+    for tag in &options.delete_tags {
+        planned.push(PlannedUpdate {
+            local_ref: None,
+            local_oid: git2::Oid::zero(),
+            remote_ref: format!("refs/tags/{}", tag),
+            force: false,
+        });
+    }
 
+    if planned.is_empty() {
+        return Err(GitError::OperationFailed("Nothing to push".to_string()));
+    }
+
+    // What we believe the remote has, from our remote-tracking refs
+    let tracking_oid = |remote_ref: &str| {
+        remote_ref
+            .strip_prefix("refs/heads/")
+            .and_then(|branch| {
+                repo.refname_to_id(&format!("refs/remotes/{}/{}", remote_name, branch))
+                    .ok()
+            })
+            .unwrap_or_else(git2::Oid::zero)
+    };
+
+    // pre-push gets "<local ref> <local sha> <remote ref> <remote sha>" per update
+    let push_lines: String = planned
+        .iter()
+        .map(|u| {
+            format!(
+                "{} {} {} {}\n",
+                u.local_ref.as_deref().unwrap_or("(delete)"),
+                u.local_oid,
+                u.remote_ref,
+                tracking_oid(&u.remote_ref)
+            )
+        })
+        .collect();
+    let url = remote_obj
+        .pushurl()
+        .or_else(|| remote_obj.url())
+        .unwrap_or_default()
+        .to_string();
+    run_hook(
+        repo,
+        "pre-push",
+        &[remote_name, &url],
+        &[],
+        Some(&push_lines),
+        &|event| {
+            if let HookEvent::Output { hook, line } = event {
+                on_progress(SyncProgress::HookOutput { hook, line });
+            }
+        },
+    )?;
+
+    // Remote ref -> expected value for leased updates
+    let leases: HashMap<String, git2::Oid> = planned
+        .iter()
+        .filter(|u| u.force)
+        .map(|u| (u.remote_ref.clone(), tracking_oid(&u.remote_ref)))
+        .collect();
+    // Everything the remote is known to have, taken before the push moves the tracking refs
+    let known_remote_oids: Vec<git2::Oid> = repo
+        .references_glob(&format!("refs/remotes/{}/*", remote_name))?
+        .flatten()
+        .filter_map(|r| r.target())
+        .collect();
+    let stale_refs: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    // Remote ref -> value on the remote before the push
+    let remote_before: RefCell<HashMap<String, git2::Oid>> = RefCell::new(HashMap::new());
+    let statuses: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+
+    {
         let mut callbacks = git2::RemoteCallbacks::new();
 
-        // Pack progress
-        let progress_pack = progress_channel.clone();
-        callbacks.pack_progress(move |stage, current, total| match stage {
-            git2::PackBuilderStage::AddingObjects => {
-                let _ = progress_pack.send(SyncProgress::Counting {
-                    current: current as u32,
-                    total: total as u32,
-                });
+        callbacks.pack_progress(|stage, current, total| match stage {
+            git2::PackBuilderStage::AddingObjects => on_progress(SyncProgress::Counting {
+                current: current as u32,
+                total: total as u32,
+            }),
+            git2::PackBuilderStage::Deltafication => on_progress(SyncProgress::Compressing {
+                current: current as u32,
+                total: total as u32,
+            }),
+        });
+
+        callbacks.sideband_progress(|_| !cancel.is_cancelled());
+
+        // Pack building cannot be interrupted, so the last chance to stop
+        // before objects are sent is the negotiation step
+        callbacks.push_negotiation(|updates| {
+            if cancel.is_cancelled() {
+                return Err(git2::Error::from_str("cancelled"));
             }
-            git2::PackBuilderStage::Deltafication => {
-                let _ = progress_pack.send(SyncProgress::Compressing {
-                    current: current as u32,
-                    total: total as u32,
-                });
+            for update in updates {
+                let Some(dst) = update.dst_refname() else {
+                    continue;
+                };
+                remote_before
+                    .borrow_mut()
+                    .insert(dst.to_string(), update.src());
+                if let Some(expected) = leases.get(dst)
+                    && *expected != update.src()
+                {
+                    stale_refs.borrow_mut().push(dst.to_string());
+                }
             }
+            if stale_refs.borrow().is_empty() {
+                Ok(())
+            } else {
+                Err(git2::Error::from_str("stale info"))
+            }
+        });
+
+        callbacks.push_update_reference(|refname, status| {
+            statuses
+                .borrow_mut()
+                .insert(refname.to_string(), status.map(str::to_string));
+            Ok(())
         });
 
-        // Credentials
-        callbacks.credentials(create_credentials_callback());
+        let credentials = CredentialSession::new();
+        callbacks.credentials(credentials.callback());
+        let url = remote_obj.pushurl().or(remote_obj.url()).unwrap_or("");
+        callbacks.certificate_check(create_certificate_check(url));
 
+        let network = NetworkSettings::load(Some(repo));
+        network.apply_ca_info()?;
         let mut opts = git2::PushOptions::new();
         opts.remote_callbacks(callbacks);
+        opts.proxy_options(network.proxy_options());
 
-        remote_obj.push(&[&refspec], Some(&mut opts)).map_err(|e| {
-            if e.class() == git2::ErrorClass::Net {
-                GitError::NetworkError(e.message().to_string())
-            } else if e.class() == git2::ErrorClass::Ssh {
-                GitError::AuthenticationFailed(e.message().to_string())
-            } else if e.message().contains("rejected") {
-                GitError::PushRejected(e.message().to_string())
-            } else {
-                GitError::OperationFailed(e.message().to_string())
+        let refspecs: Vec<String> = planned.iter().map(PlannedUpdate::refspec).collect();
+        cancel.check()?;
+        let result = remote_obj.push(&refspecs, Some(&mut opts));
+        credentials.finish(&result);
+        if let Err(e) = result {
+            cancel.check()?;
+            let stale = stale_refs.borrow();
+            if !stale.is_empty() {
+                return Err(GitError::PushRejected(format!(
+                    "{} (stale info): the remote changed since the last fetch",
+                    stale.join(", ")
+                )));
             }
-        })?;
+            return Err(map_push_error(e));
+        }
+    }
 
-        Ok::<_, GitError>(SyncResult {
-            success: true,
-            message: format!("Pushed {} to {}", branch_name, remote_name),
-            commits_transferred: 0,
+    let statuses = statuses.into_inner();
+    let remote_before = remote_before.into_inner();
+    let ref_updates: Vec<RefUpdateResult> = planned
+        .iter()
+        .map(|u| {
+            let message = statuses.get(&u.remote_ref).cloned().flatten();
+            RefUpdateResult {
+                remote_ref: u.remote_ref.clone(),
+                local_ref: u.local_ref.clone(),
+                success: message.is_none(),
+                message,
+            }
         })
-    })
-    .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))??;
+        .collect();
 
-    on_progress
-        .send(SyncProgress::Finished {
-            operation: "push".to_string(),
+    // Commits the remote didn't have: new tips minus what the remote pointed at
+    let mut commits_transferred = 0u32;
+    if let Ok(mut revwalk) = repo.revwalk() {
+        for update in planned.iter().filter(|u| u.local_ref.is_some()) {
+            let _ = revwalk.push(update.local_oid);
+        }
+        for oid in remote_before.values().chain(&known_remote_oids) {
+            if !oid.is_zero() {
+                let _ = revwalk.hide(*oid);
+            }
+        }
+        commits_transferred = revwalk.filter_map(Result::ok).count() as u32;
+    }
+
+    let branch_ok = |remote_ref: &str| {
+        ref_updates
+            .iter()
+            .any(|r| r.remote_ref == remote_ref && r.success)
+    };
+    if options.set_upstream
+        && let Some((branch, remote_branch)) = &pushed_branch
+        && branch_ok(&format!("refs/heads/{}", remote_branch))
+    {
+        let mut config = repo.config()?;
+        config.set_str(&format!("branch.{}.remote", branch), remote_name)?;
+        config.set_str(
+            &format!("branch.{}.merge", branch),
+            &format!("refs/heads/{}", remote_branch),
+        )?;
+    }
+
+    let rejected: Vec<String> = ref_updates
+        .iter()
+        .filter(|r| !r.success)
+        .map(|r| {
+            format!(
+                "{} ({})",
+                r.remote_ref,
+                r.message.as_deref().unwrap_or("rejected")
+            )
         })
-        .ok();
+        .collect();
+    let message = if !rejected.is_empty() {
+        format!("Rejected by {}: {}", remote_name, rejected.join(", "))
+    } else if let Some((branch, remote_branch)) = &pushed_branch {
+        if branch == remote_branch {
+            format!("Pushed {} to {}", branch, remote_name)
+        } else {
+            format!("Pushed {} to {}/{}", branch, remote_name, remote_branch)
+        }
+    } else {
+        format!("Updated {} ref(s) on {}", ref_updates.len(), remote_name)
+    };
 
-    Ok(result)
+    Ok(SyncResult {
+        success: rejected.is_empty(),
+        message,
+        commits_transferred,
+        ref_updates,
+        pruned_refs: Vec::new(),
+    })
 }
 
-/// Pull from a remote (fetch + merge).
+/// Push to a remote.
 ///
-/// Downloads objects from the remote and merges the tracking branch
-/// into the current branch. Fast-forward merges are preferred.
+/// Without `options` this pushes the current branch to the same-named
+/// remote branch. See [`PushOptions`] for force-with-lease, upstream
+/// tracking, tags, deletions and renamed remote branches.
 #[tauri::command]
 #[specta::specta]
-pub async fn pull_from_remote(
+pub async fn push_to_remote(
+    remote: String,
+    on_progress: Channel<SyncProgress>,
+    options: Option<PushOptions>,
+    operation_id: Option<String>,
+    state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<SyncResult, GitError> {
+    run_push(
+        remote,
+        options.unwrap_or_default(),
+        on_progress,
+        operation_id,
+        state,
+        registry,
+    )
+    .await
+}
+
+/// Run [`push_internal`] as a cancellable operation, streaming progress and
+/// the outcome to `on_progress`.
+pub(crate) async fn run_push(
     remote: String,
+    options: PushOptions,
     on_progress: Channel<SyncProgress>,
+    operation_id: Option<String>,
     state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
 ) -> Result<SyncResult, GitError> {
     let repo_path = get_repo_path(&state).await?;
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
 
     on_progress
         .send(SyncProgress::Started {
-            operation: "pull".to_string(),
+            operation: "push".to_string(),
         })
         .ok();
 
-    let remote_name = remote.clone();
     let progress_channel = on_progress.clone();
     let result = tokio::task::spawn_blocking(move || {
         let repo = git2::Repository::open(&repo_path)?;
+        push_internal(
+            &repo,
+            &remote,
+            &options,
+            &|progress| {
+                let _ = progress_channel.send(progress);
+            },
+            &cancel,
+        )
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?;
 
-        // Get current branch name
-        let head = repo.head()?;
-        let branch_name = head
-            .shorthand()
-            .ok_or_else(|| {
-                GitError::OperationFailed("Cannot determine current branch".to_string())
-            })?
-            .to_string();
+    send_outcome(&on_progress, "push", &result);
+    result
+}
 
-        // Step 1: Fetch
-        {
-            let mut remote_obj = repo
-                .find_remote(&remote_name)
-                .map_err(|_| GitError::RemoteNotFound(remote_name.clone()))?;
+/// How pulled changes are integrated into the current branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PullStrategy {
+    /// Only fast-forward; fail if the branches have diverged
+    FfOnly,
+    /// Fast-forward when possible, otherwise create a merge commit
+    Merge,
+    /// Replay local commits on top of the upstream branch
+    Rebase,
+    /// Like rebase, but keep local merge commits
+    RebaseMerges,
+}
+
+/// Pull settings; unset fields fall back to git config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PullOptions {
+    /// Strategy (defaults to `branch.<name>.rebase`, `pull.rebase` and `pull.ff`)
+    pub strategy: Option<PullStrategy>,
+    /// Stash local changes before integrating and re-apply them afterwards
+    /// (defaults to `rebase.autoStash` / `merge.autoStash`)
+    pub autostash: Option<bool>,
+}
+
+/// Strategy configured for a branch, and whether `pull.ff=false` forbids fast-forwards.
+fn configured_pull_strategy(config: &git2::Config, branch: &str) -> (PullStrategy, bool) {
+    let no_ff = config
+        .get_string("pull.ff")
+        .map(|v| v == "false")
+        .unwrap_or(false);
+
+    let rebase = config
+        .get_string(&format!("branch.{}.rebase", branch))
+        .or_else(|_| config.get_string("pull.rebase"))
+        .map(|v| v.to_lowercase());
+    let strategy = match rebase.as_deref() {
+        Ok("true" | "interactive" | "i") => PullStrategy::Rebase,
+        Ok("merges" | "m" | "preserve" | "p") => PullStrategy::RebaseMerges,
+        _ if config.get_string("pull.ff").as_deref() == Ok("only") => PullStrategy::FfOnly,
+        _ => PullStrategy::Merge,
+    };
+
+    (strategy, no_ff)
+}
 
-            let mut callbacks = git2::RemoteCallbacks::new();
+/// What a single remote fetch changed.
+pub(crate) struct FetchOutcome {
+    pub(crate) objects_received: u32,
+    pub(crate) pruned_refs: Vec<String>,
+}
+
+/// Read a per-remote boolean (`remote.<name>.<key>`), falling back to `fetch.<key>`.
+fn remote_config_bool(repo: &git2::Repository, remote_name: &str, key: &str) -> Option<bool> {
+    let config = repo.config().ok()?.snapshot().ok()?;
+    config
+        .get_bool(&format!("remote.{}.{}", remote_name, key))
+        .or_else(|_| config.get_bool(&format!("fetch.{}", key)))
+        .ok()
+}
+
+fn map_fetch_error(e: git2::Error) -> GitError {
+    if e.class() == git2::ErrorClass::Net {
+        GitError::NetworkError(e.message().to_string())
+    } else if e.class() == git2::ErrorClass::Ssh || e.code() == git2::ErrorCode::Auth {
+        GitError::AuthenticationFailed(e.message().to_string())
+    } else {
+        GitError::OperationFailed(e.message().to_string())
+    }
+}
+
+/// Send the closing progress event: `Finished` on success, `Cancelled` if
+/// the operation was cancelled. Other errors are reported by the result.
+pub(crate) fn send_outcome<T>(
+    on_progress: &Channel<SyncProgress>,
+    operation: &str,
+    result: &Result<T, GitError>,
+) {
+    let operation = operation.to_string();
+    let event = match result {
+        Ok(_) => SyncProgress::Finished { operation },
+        Err(GitError::Cancelled) => SyncProgress::Cancelled { operation },
+        Err(_) => return,
+    };
+    on_progress.send(event).ok();
+}
 
-            let progress_transfer = progress_channel.clone();
-            callbacks.transfer_progress(move |stats| {
-                let _ = progress_transfer.send(SyncProgress::Transferring {
-                    current: stats.received_objects() as u32,
-                    total: stats.total_objects() as u32,
-                    bytes: stats.received_bytes() as u32,
-                });
-                true
-            });
+/// Fetch a remote's configured refspecs, streaming transfer progress.
+///
+/// Honours the remote's prune and tag-following options.
+pub(crate) fn fetch_remote(
+    repo: &git2::Repository,
+    remote_name: &str,
+    options: &FetchOptions,
+    on_progress: &(dyn Fn(SyncProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<FetchOutcome, GitError> {
+    fetch_remote_with(repo, remote_name, options, on_progress, cancel, None)
+}
 
-            callbacks.credentials(create_credentials_callback());
+/// Download a remote's objects, then update its refs.
+///
+/// With `ref_lock` set (several remotes fetched at once) the download runs
+/// unlocked but refs are only updated while holding the lock, so tag refs
+/// and packed-refs are never written concurrently, and FETCH_HEAD is not
+/// written since each remote would overwrite it.
+fn fetch_remote_with(
+    repo: &git2::Repository,
+    remote_name: &str,
+    options: &FetchOptions,
+    on_progress: &(dyn Fn(SyncProgress) + Sync),
+    cancel: &CancellationToken,
+    ref_lock: Option<&Mutex<()>>,
+) -> Result<FetchOutcome, GitError> {
+    let mut remote_obj = repo
+        .find_remote(remote_name)
+        .map_err(|_| GitError::RemoteNotFound(remote_name.to_string()))?;
 
-            let mut opts = git2::FetchOptions::new();
-            opts.remote_callbacks(callbacks);
+    let prune = options
+        .prune
+        .or_else(|| remote_config_bool(repo, remote_name, "prune"))
+        .unwrap_or(false);
+    let prune_tags = prune
+        && options
+            .prune_tags
+            .or_else(|| remote_config_bool(repo, remote_name, "pruneTags"))
+            .unwrap_or(false);
 
+    // Pruning works on the active refspecs, so --prune-tags is the configured
+    // refspecs plus an explicit tag refspec, exactly like git
+    let mut refspecs: Vec<String> = Vec::new();
+    if prune_tags {
+        refspecs.extend(
             remote_obj
-                .fetch(&[] as &[&str], Some(&mut opts), None)
-                .map_err(|e| {
-                    if e.class() == git2::ErrorClass::Net {
-                        GitError::NetworkError(e.message().to_string())
-                    } else if e.class() == git2::ErrorClass::Ssh {
-                        GitError::AuthenticationFailed(e.message().to_string())
-                    } else {
-                        GitError::OperationFailed(e.message().to_string())
-                    }
-                })?;
+                .fetch_refspecs()?
+                .iter()
+                .flatten()
+                .map(str::to_string),
+        );
+        refspecs.push("+refs/tags/*:refs/tags/*".to_string());
+    }
+
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.transfer_progress(|stats| {
+        on_progress(SyncProgress::Transferring {
+            current: stats.received_objects() as u32,
+            total: stats.total_objects() as u32,
+            bytes: stats.received_bytes() as u32,
+        });
+        !cancel.is_cancelled()
+    });
+    let credentials = CredentialSession::new();
+    callbacks.credentials(credentials.callback());
+    callbacks.certificate_check(create_certificate_check(remote_obj.url().unwrap_or("")));
+
+    let network = NetworkSettings::load(Some(repo));
+    network.apply_ca_info()?;
+    let autotag = match options.tags {
+        Some(TagFollowing::Auto) => git2::AutotagOption::Auto,
+        Some(TagFollowing::All) => git2::AutotagOption::All,
+        Some(TagFollowing::None) => git2::AutotagOption::None,
+        None => git2::AutotagOption::Unspecified,
+    };
+    let mut opts = git2::FetchOptions::new();
+    opts.remote_callbacks(callbacks);
+    opts.proxy_options(network.proxy_options());
+    if options.unshallow {
+        // GIT_FETCH_DEPTH_UNSHALLOW
+        opts.depth(i32::MAX);
+    } else if let Some(depth) = options.depth {
+        opts.depth(depth.clamp(1, i32::MAX as u32 - 1) as i32);
+    }
+    opts.download_tags(autotag);
+
+    cancel.check()?;
+    let result = remote_obj.download(&refspecs, Some(&mut opts));
+    credentials.finish(&result);
+    remote_obj.disconnect().ok();
+    if let Err(e) = result {
+        cancel.check()?;
+        return Err(map_fetch_error(e));
+    }
+    drop(opts);
+    cancel.check()?;
+
+    let _guard = ref_lock.map(|lock| lock.lock().unwrap_or_else(PoisonError::into_inner));
+    let mut update_flags = git2::RemoteUpdateFlags::empty();
+    update_flags.set(
+        git2::RemoteUpdateFlags::UPDATE_FETCHHEAD,
+        ref_lock.is_none(),
+    );
+    remote_obj.update_tips(
+        None,
+        update_flags,
+        autotag,
+        Some(&format!("fetch {}", remote_name)),
+    )?;
+
+    let pruned_refs = RefCell::new(Vec::new());
+    if prune {
+        let mut prune_callbacks = git2::RemoteCallbacks::new();
+        prune_callbacks.update_tips(|refname, _old, _new| {
+            pruned_refs.borrow_mut().push(refname.to_string());
+            true
+        });
+        remote_obj.prune(Some(prune_callbacks))?;
+    }
+
+    Ok(FetchOutcome {
+        objects_received: remote_obj.stats().received_objects() as u32,
+        pruned_refs: pruned_refs.into_inner(),
+    })
+}
+
+/// Remote a history fetch should use: the current branch's upstream remote,
+/// else `origin`, else the only remote.
+fn default_fetch_remote(repo: &git2::Repository) -> Result<String, GitError> {
+    let upstream_remote = repo
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.name().map(str::to_string))
+        .and_then(|name| repo.branch_upstream_remote(&name).ok())
+        .and_then(|buf| buf.as_str().map(str::to_string));
+    if let Some(remote) = upstream_remote {
+        return Ok(remote);
+    }
+
+    let remotes = repo.remotes()?;
+    let names: Vec<&str> = remotes.iter().flatten().collect();
+    match names.as_slice() {
+        _ if names.contains(&"origin") => Ok("origin".to_string()),
+        [only] => Ok(only.to_string()),
+        _ => Err(GitError::RemoteNotFound("origin".to_string())),
+    }
+}
+
+/// Commits reachable from HEAD along first parents, i.e. the current depth
+/// of a shallow clone.
+pub(crate) fn shallow_depth(repo: &git2::Repository) -> Result<u32, GitError> {
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.simplify_first_parent()?;
+    Ok(walk.count() as u32)
+}
+
+/// Extend a shallow repository by `by` commits, or fetch its complete
+/// history when `by` is `None`.
+pub fn deepen_internal(
+    repo: &git2::Repository,
+    by: Option<u32>,
+    on_progress: &(dyn Fn(SyncProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<SyncResult, GitError> {
+    if !repo.is_shallow() {
+        return Err(GitError::OperationFailed(
+            "Repository already has its complete history".to_string(),
+        ));
+    }
+
+    let remote_name = default_fetch_remote(repo)?;
+    let options = match by {
+        Some(by) => FetchOptions {
+            depth: Some(shallow_depth(repo)?.saturating_add(by.max(1))),
+            ..Default::default()
+        },
+        None => FetchOptions {
+            unshallow: true,
+            ..Default::default()
+        },
+    };
+    let outcome = fetch_remote(repo, &remote_name, &options, on_progress, cancel)?;
+
+    let message = if repo.is_shallow() {
+        format!("History now has {} commits", shallow_depth(repo)?)
+    } else {
+        "Fetched complete history".to_string()
+    };
+    Ok(SyncResult {
+        success: true,
+        message,
+        commits_transferred: outcome.objects_received,
+        ref_updates: Vec::new(),
+        pruned_refs: outcome.pruned_refs,
+    })
+}
+
+/// Fetch `by` more commits of history into a shallow repository (`git fetch --deepen`).
+#[tauri::command]
+#[specta::specta]
+pub async fn deepen_repository(
+    by: u32,
+    on_progress: Channel<SyncProgress>,
+    operation_id: Option<String>,
+    state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<SyncResult, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
+
+    let progress_channel = on_progress.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        deepen_internal(
+            &repo,
+            Some(by),
+            &|progress| {
+                let _ = progress_channel.send(progress);
+            },
+            &cancel,
+        )
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?;
+
+    send_outcome(&on_progress, "fetch", &result);
+    result
+}
+
+/// Fetch the complete history of a shallow repository (`git fetch --unshallow`).
+#[tauri::command]
+#[specta::specta]
+pub async fn unshallow_repository(
+    on_progress: Channel<SyncProgress>,
+    operation_id: Option<String>,
+    state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<SyncResult, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
+
+    let progress_channel = on_progress.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        deepen_internal(
+            &repo,
+            None,
+            &|progress| {
+                let _ = progress_channel.send(progress);
+            },
+            &cancel,
+        )
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?;
+
+    send_outcome(&on_progress, "fetch", &result);
+    result
+}
+
+/// Move `branch_ref` (the checked-out branch) to `target`, updating the working tree safely.
+fn fast_forward(
+    repo: &git2::Repository,
+    branch_ref: &str,
+    target: git2::Oid,
+    reflog: &str,
+) -> Result<(), GitError> {
+    let commit = repo.find_commit(target)?;
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(git2::build::CheckoutBuilder::new().safe()),
+    )?;
+    repo.reference(branch_ref, target, true, reflog)?;
+    repo.set_head(branch_ref)?;
+    Ok(())
+}
+
+/// Replay `upstream..local` onto `upstream` in memory, recreating merge commits.
+///
+/// Returns the new tip, or `None` if any step conflicts (nothing is changed then).
+fn rebase_merges(
+    repo: &git2::Repository,
+    local: git2::Oid,
+    upstream: git2::Oid,
+) -> Result<Option<git2::Oid>, GitError> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(local)?;
+    revwalk.hide(upstream)?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    let commits: Vec<git2::Oid> = revwalk.collect::<Result<_, _>>()?;
+
+    let committer = committer_signature(repo)?;
+    let mut rewritten: HashMap<git2::Oid, git2::Oid> = HashMap::new();
+
+    for oid in commits {
+        let commit = repo.find_commit(oid)?;
+        if commit.parent_count() > 2 {
+            return Err(GitError::UnsupportedOperation(format!(
+                "rebasing octopus merge {:.7}",
+                oid
+            )));
         }
 
-        // Step 2: Find the remote tracking branch
-        let remote_branch = format!("{}/{}", remote_name, branch_name);
-        let fetch_head = repo
-            .find_reference(&format!("refs/remotes/{}", remote_branch))
-            .map_err(|_| {
-                GitError::OperationFailed(format!("No tracking branch found for {}", remote_branch))
-            })?;
-
-        let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
-
-        // Step 3: Merge analysis
-        let (analysis, _preference) = repo.merge_analysis(&[&fetch_commit])?;
-
-        if analysis.is_up_to_date() {
-            return Ok(SyncResult {
-                success: true,
-                message: "Already up to date".to_string(),
-                commits_transferred: 0,
-            });
+        // Parents inside the range map to their rewrites; the fork point becomes upstream
+        let parents: Vec<git2::Commit> = commit
+            .parent_ids()
+            .enumerate()
+            .map(|(i, parent)| {
+                let new_parent = match rewritten.get(&parent) {
+                    Some(new) => *new,
+                    None if i == 0 => upstream,
+                    None => parent,
+                };
+                repo.find_commit(new_parent)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut index = if parents.len() == 2 {
+            repo.merge_commits(&parents[0], &parents[1], None)?
+        } else {
+            repo.cherrypick_commit(&commit, &parents[0], 0, None)?
+        };
+        if index.has_conflicts() {
+            return Ok(None);
         }
 
-        if analysis.is_fast_forward() {
-            // Fast-forward: just update HEAD
-            let refname = format!("refs/heads/{}", branch_name);
-            let mut reference = repo.find_reference(&refname)?;
-            reference.set_target(fetch_commit.id(), "pull: fast-forward")?;
-            repo.set_head(&refname)?;
-            repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
-
-            return Ok(SyncResult {
-                success: true,
-                message: format!("Fast-forwarded {} to {}", branch_name, remote_name),
-                commits_transferred: 1,
-            });
+        let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+        // Drop commits whose changes are already upstream
+        if parents.len() == 1 && tree.id() == parents[0].tree_id() {
+            rewritten.insert(oid, parents[0].id());
+            continue;
         }
 
-        if analysis.is_normal() {
-            // Normal merge required - attempt it
-            repo.merge(&[&fetch_commit], None, None)?;
-
-            // Check for conflicts
-            let index = repo.index()?;
-            if index.has_conflicts() {
-                return Ok(SyncResult {
-                    success: false,
-                    message:
-                        "Merge conflicts detected. Please resolve conflicts and commit manually."
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        let new_oid = create_commit_signed(
+            repo,
+            None,
+            &commit.author(),
+            &committer,
+            commit.message().unwrap_or(""),
+            &tree,
+            &parent_refs,
+        )?;
+        rewritten.insert(oid, new_oid);
+    }
+
+    Ok(Some(rewritten.get(&local).copied().unwrap_or(upstream)))
+}
+
+/// How the fetched upstream was integrated.
+enum Integration {
+    UpToDate,
+    FastForward,
+    Merged,
+    Rebased,
+    /// Stopped; the message explains what the user needs to do
+    Stopped(String),
+}
+
+/// Fetch and integrate the upstream of the current branch.
+pub fn pull_internal(
+    repo: &mut git2::Repository,
+    remote_name: &str,
+    options: &PullOptions,
+    on_progress: &(dyn Fn(SyncProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<SyncResult, GitError> {
+    // Get current branch name
+    let head = repo.head()?;
+    if !head.is_branch() {
+        return Err(GitError::OperationFailed(
+            "Cannot determine current branch".to_string(),
+        ));
+    }
+    let branch_ref = head
+        .name()
+        .ok_or_else(|| GitError::OperationFailed("Cannot determine current branch".to_string()))?
+        .to_string();
+    let branch_name = branch_ref.trim_start_matches("refs/heads/").to_string();
+    let local_oid = head
+        .target()
+        .ok_or_else(|| GitError::Internal("HEAD has no target".to_string()))?;
+    drop(head);
+
+    let config = repo.config()?.snapshot()?;
+    let (configured, no_ff) = configured_pull_strategy(&config, &branch_name);
+    let strategy = options.strategy.unwrap_or(configured);
+    let autostash_key = match strategy {
+        PullStrategy::Rebase | PullStrategy::RebaseMerges => "rebase.autoStash",
+        _ => "merge.autoStash",
+    };
+    let autostash = options
+        .autostash
+        .unwrap_or_else(|| config.get_bool(autostash_key).unwrap_or(false));
+
+    fetch_remote(
+        repo,
+        remote_name,
+        &FetchOptions::default(),
+        on_progress,
+        cancel,
+    )?;
+    // Nothing has been touched locally yet; past this point the pull completes
+    cancel.check()?;
+
+    // The configured upstream if it lives on this remote, else <remote>/<branch>
+    let upstream_ref = repo
+        .branch_upstream_name(&branch_ref)
+        .ok()
+        .and_then(|name| name.as_str().map(str::to_string))
+        .filter(|name| name.starts_with(&format!("refs/remotes/{}/", remote_name)))
+        .unwrap_or_else(|| format!("refs/remotes/{}/{}", remote_name, branch_name));
+    let upstream_oid = repo.refname_to_id(&upstream_ref).map_err(|_| {
+        GitError::OperationFailed(format!(
+            "No tracking branch found for {}",
+            upstream_ref.trim_start_matches("refs/remotes/")
+        ))
+    })?;
+    let upstream_name = upstream_ref.trim_start_matches("refs/remotes/").to_string();
+
+    // Commits we are about to receive
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(upstream_oid)?;
+    revwalk.hide(local_oid)?;
+    let received = revwalk.count() as u32;
+
+    let stashed = if received > 0 && autostash && has_uncommitted_changes(repo)? {
+        stash_save_internal(repo, Some("autostash"), false)?;
+        true
+    } else {
+        false
+    };
+
+    let annotated = repo.find_annotated_commit(upstream_oid)?;
+    let (analysis, _) = repo.merge_analysis(&[&annotated])?;
+    let can_fast_forward =
+        analysis.is_fast_forward() && !(no_ff && strategy == PullStrategy::Merge);
+
+    let integration = if analysis.is_up_to_date() {
+        Integration::UpToDate
+    } else if can_fast_forward {
+        fast_forward(
+            repo,
+            &branch_ref,
+            upstream_oid,
+            &format!("pull: fast-forward to {}", upstream_name),
+        )?;
+        Integration::FastForward
+    } else {
+        match strategy {
+            PullStrategy::FfOnly => Integration::Stopped(format!(
+                "{} and {} have diverged; not possible to fast-forward",
+                branch_name, upstream_name
+            )),
+            PullStrategy::Merge => {
+                // The merge commit is written from the index, so anything
+                // already staged would end up in it
+                if has_uncommitted_changes(repo)? {
+                    return Err(GitError::DirtyWorkingDirectory);
+                }
+                let mut checkout = git2::build::CheckoutBuilder::new();
+                checkout.safe();
+                repo.merge(&[&annotated], None, Some(&mut checkout))?;
+
+                if repo.index()?.has_conflicts() {
+                    Integration::Stopped(
+                        "Merge conflicts detected. Resolve them and continue the merge."
+                            .to_string(),
+                    )
+                } else {
+                    let local = repo.find_commit(local_oid)?;
+                    let theirs = repo.find_commit(upstream_oid)?;
+                    commit_merge(
+                        repo,
+                        &format!("Merge branch '{}' of {}", branch_name, remote_name),
+                        &[&local, &theirs],
+                        &|event| {
+                            if let HookEvent::Output { hook, line } = event {
+                                on_progress(SyncProgress::HookOutput { hook, line });
+                            }
+                        },
+                    )?;
+                    Integration::Merged
+                }
+            }
+            PullStrategy::Rebase => {
+                let todo = get_rebase_todo_internal(repo, &upstream_oid.to_string())?;
+                let progress = start_rebase_internal(repo, &upstream_oid.to_string(), &todo)?;
+                if progress.reason == RebaseStopReason::Completed {
+                    Integration::Rebased
+                } else {
+                    Integration::Stopped(format!(
+                        "Rebase stopped at step {}/{} with conflicts. Resolve them and continue the rebase.",
+                        progress.current_step, progress.total_steps
+                    ))
+                }
+            }
+            PullStrategy::RebaseMerges => {
+                if has_uncommitted_changes(repo)? {
+                    return Err(GitError::DirtyWorkingDirectory);
+                }
+                match rebase_merges(repo, local_oid, upstream_oid)? {
+                    Some(new_tip) => {
+                        fast_forward(
+                            repo,
+                            &branch_ref,
+                            new_tip,
+                            &format!("pull --rebase=merges: onto {}", upstream_name),
+                        )?;
+                        Integration::Rebased
+                    }
+                    None => Integration::Stopped(
+                        "Rebasing merge commits produced conflicts; nothing was changed. Use merge or rebase instead."
                             .to_string(),
-                    commits_transferred: 0,
-                });
+                    ),
+                }
             }
+        }
+    };
 
-            // No conflicts - but we don't auto-commit
-            // User should review and commit the merge
-            return Ok(SyncResult {
-                success: true,
-                message: "Merged successfully. Please review and commit the merge.".to_string(),
-                commits_transferred: 1,
-            });
+    drop(annotated);
+
+    let mut message = match &integration {
+        Integration::UpToDate => "Already up to date".to_string(),
+        Integration::FastForward => format!("Fast-forwarded {} to {}", branch_name, upstream_name),
+        Integration::Merged => format!("Merged {} into {}", upstream_name, branch_name),
+        Integration::Rebased => format!("Rebased {} onto {}", branch_name, upstream_name),
+        Integration::Stopped(reason) => reason.clone(),
+    };
+
+    let stopped = matches!(integration, Integration::Stopped(_));
+    if stashed {
+        let restored = !stopped && stash_pop_internal(repo, 0).is_ok();
+        if !restored {
+            message
+                .push_str("\nYour local changes were stashed as stash@{0}; apply them when ready.");
         }
+    }
 
-        // Unborn or other edge case
-        Err(GitError::OperationFailed(
-            "Cannot merge: unhandled merge scenario".to_string(),
-        ))
+    Ok(SyncResult {
+        success: !stopped,
+        message,
+        commits_transferred: if stopped { 0 } else { received },
+        ref_updates: Vec::new(),
+        pruned_refs: Vec::new(),
     })
-    .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))??;
+}
+
+/// Pull from a remote (fetch + integrate).
+///
+/// Downloads objects from the remote and integrates the upstream branch
+/// using `options.strategy`, defaulting to `pull.rebase` / `pull.ff`.
+/// A dirty working tree can be stashed and re-applied with `autostash`.
+#[tauri::command]
+#[specta::specta]
+pub async fn pull_from_remote(
+    remote: String,
+    on_progress: Channel<SyncProgress>,
+    options: Option<PullOptions>,
+    operation_id: Option<String>,
+    state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<SyncResult, GitError> {
+    let repo_path = get_repo_path(&state).await?;
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
 
     on_progress
-        .send(SyncProgress::Finished {
+        .send(SyncProgress::Started {
             operation: "pull".to_string(),
         })
         .ok();
 
-    Ok(result)
+    let remote_name = remote.clone();
+    let progress_channel = on_progress.clone();
+    let result = record_operation(
+        repo_path.clone(),
+        format!("Pull from {}", remote),
+        move || {
+            let mut repo = git2::Repository::open(&repo_path)?;
+            pull_internal(
+                &mut repo,
+                &remote_name,
+                &options.unwrap_or_default(),
+                &|progress| {
+                    let _ = progress_channel.send(progress);
+                },
+                &cancel,
+            )
+        },
+    )
+    .await;
+
+    send_outcome(&on_progress, "pull", &result);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_file, init_repo};
+    use tempfile::TempDir;
+
+    /// A work repository with one commit on `main` and a bare `origin` it can push to.
+    fn setup_repos() -> (TempDir, git2::Repository, git2::Repository) {
+        let dir = TempDir::new().unwrap();
+        let remote = git2::Repository::init_bare(dir.path().join("origin.git")).unwrap();
+        let repo = init_repo(&dir.path().join("work"));
+        repo.remote("origin", dir.path().join("origin.git").to_str().unwrap())
+            .unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        commit_file(&repo, "a.txt", "one", "First");
+        (dir, repo, remote)
+    }
+
+    fn push(repo: &git2::Repository, options: PushOptions) -> Result<SyncResult, GitError> {
+        push_internal(
+            repo,
+            "origin",
+            &options,
+            &|_| {},
+            &CancellationToken::default(),
+        )
+    }
+
+    fn fetch(repo: &git2::Repository, options: &FetchOptions) -> Result<FetchOutcome, GitError> {
+        fetch_remote(
+            repo,
+            "origin",
+            options,
+            &|_| {},
+            &CancellationToken::default(),
+        )
+    }
+
+    #[test]
+    fn test_push_to_renamed_branch_sets_upstream() {
+        let (_dir, repo, remote) = setup_repos();
+
+        let result = push(
+            &repo,
+            PushOptions {
+                remote_branch: Some("feature/x".to_string()),
+                set_upstream: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.commits_transferred, 1);
+        assert_eq!(result.ref_updates.len(), 1);
+        assert_eq!(result.ref_updates[0].remote_ref, "refs/heads/feature/x");
+        assert_eq!(
+            remote.refname_to_id("refs/heads/feature/x").unwrap(),
+            repo.refname_to_id("refs/heads/main").unwrap()
+        );
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(config.get_str("branch.main.remote").unwrap(), "origin");
+        assert_eq!(
+            config.get_str("branch.main.merge").unwrap(),
+            "refs/heads/feature/x"
+        );
+    }
+
+    #[test]
+    fn test_force_with_lease_rejects_stale_tracking_ref() {
+        let (_dir, repo, remote) = setup_repos();
+        push(&repo, PushOptions::default()).unwrap();
+
+        // Someone else pushes to main; our tracking ref is now stale
+        let sig = repo.signature().unwrap();
+        let base = remote
+            .find_commit(remote.refname_to_id("refs/heads/main").unwrap())
+            .unwrap();
+        let tree = base.tree().unwrap();
+        let theirs = remote
+            .commit(
+                Some("refs/heads/main"),
+                &sig,
+                &sig,
+                "Theirs",
+                &tree,
+                &[&base],
+            )
+            .unwrap();
+
+        // Rewrite local history
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let amended = head
+            .amend(None, None, None, None, Some("Rewritten"), None)
+            .unwrap();
+        repo.reference("refs/heads/main", amended, true, "")
+            .unwrap();
+
+        let lease = PushOptions {
+            force_with_lease: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            push(&repo, lease.clone()),
+            Err(GitError::PushRejected(_))
+        ));
+        assert_eq!(remote.refname_to_id("refs/heads/main").unwrap(), theirs);
+
+        // After fetching, the lease matches and the force push goes through
+        repo.find_remote("origin")
+            .unwrap()
+            .fetch(&[] as &[&str], None, None)
+            .unwrap();
+        assert!(push(&repo, lease).unwrap().success);
+        assert_eq!(remote.refname_to_id("refs/heads/main").unwrap(), amended);
+    }
+
+    #[test]
+    fn test_push_tags_and_delete_branch() {
+        let (_dir, repo, remote) = setup_repos();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v1.0", head.as_object(), false)
+            .unwrap();
+        repo.tag_lightweight("v2.0", head.as_object(), false)
+            .unwrap();
+        push(
+            &repo,
+            PushOptions {
+                remote_branch: Some("old".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let result = push(
+            &repo,
+            PushOptions {
+                skip_branch: true,
+                tags: vec!["v1.0".to_string()],
+                delete_branches: vec!["old".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.ref_updates.len(), 2);
+        assert!(remote.find_reference("refs/tags/v1.0").is_ok());
+        assert!(remote.find_reference("refs/tags/v2.0").is_err());
+        assert!(remote.find_reference("refs/heads/old").is_err());
+
+        let all = PushOptions {
+            skip_branch: true,
+            all_tags: true,
+            ..Default::default()
+        };
+        assert!(push(&repo, all).unwrap().success);
+        assert!(remote.find_reference("refs/tags/v2.0").is_ok());
+
+        let delete = PushOptions {
+            skip_branch: true,
+            delete_tags: vec!["v1.0".to_string()],
+            ..Default::default()
+        };
+        assert!(push(&repo, delete).unwrap().success);
+        assert!(remote.find_reference("refs/tags/v1.0").is_err());
+        assert!(repo.find_reference("refs/tags/v1.0").is_ok());
+    }
+
+    /// A second clone of origin, to make upstream changes.
+    fn other_clone(dir: &TempDir) -> git2::Repository {
+        let other = git2::build::RepoBuilder::new()
+            .branch("main")
+            .clone(
+                dir.path().join("origin.git").to_str().unwrap(),
+                &dir.path().join("other"),
+            )
+            .unwrap();
+        let mut config = other.config().unwrap();
+        config.set_str("user.name", "Other User").unwrap();
+        config.set_str("user.email", "other@example.com").unwrap();
+        other
+    }
+
+    fn pull(
+        repo: &mut git2::Repository,
+        strategy: Option<PullStrategy>,
+        autostash: Option<bool>,
+    ) -> SyncResult {
+        pull_internal(
+            repo,
+            "origin",
+            &PullOptions {
+                strategy,
+                autostash,
+            },
+            &|_| {},
+            &CancellationToken::default(),
+        )
+        .unwrap()
+    }
+
+    /// Work and origin diverged: origin has `b.txt` (two commits), work has `c.txt`.
+    fn diverge(dir: &TempDir, repo: &git2::Repository) {
+        push(repo, PushOptions::default()).unwrap();
+        let other = other_clone(dir);
+        commit_file(&other, "b.txt", "b", "Upstream 1");
+        commit_file(&other, "b.txt", "bb", "Upstream 2");
+        push(&other, PushOptions::default()).unwrap();
+        commit_file(repo, "c.txt", "c", "Local");
+    }
+
+    #[test]
+    fn test_pull_ff_only_refuses_divergent_history() {
+        let (dir, mut repo, _remote) = setup_repos();
+        diverge(&dir, &repo);
+        let before = repo.head().unwrap().target().unwrap();
+
+        let result = pull(&mut repo, Some(PullStrategy::FfOnly), None);
+
+        assert!(!result.success);
+        assert_eq!(result.commits_transferred, 0);
+        assert_eq!(repo.head().unwrap().target().unwrap(), before);
+    }
+
+    #[test]
+    fn test_pull_rebase_from_config() {
+        let (dir, mut repo, _remote) = setup_repos();
+        diverge(&dir, &repo);
+        repo.config()
+            .unwrap()
+            .set_str("pull.rebase", "true")
+            .unwrap();
+
+        let result = pull(&mut repo, None, None);
+
+        assert!(result.success, "{}", result.message);
+        assert_eq!(result.commits_transferred, 2);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.summary(), Some("Local"));
+        assert_eq!(head.parent_count(), 1);
+        assert_eq!(
+            head.parent_id(0).unwrap(),
+            repo.refname_to_id("refs/remotes/origin/main").unwrap()
+        );
+        assert!(repo.head().unwrap().is_branch());
+    }
+
+    #[test]
+    fn test_pull_merge_with_autostash() {
+        let (dir, mut repo, _remote) = setup_repos();
+        diverge(&dir, &repo);
+        std::fs::write(repo.workdir().unwrap().join("a.txt"), "dirty").unwrap();
+
+        let result = pull(&mut repo, Some(PullStrategy::Merge), Some(true));
+
+        assert!(result.success, "{}", result.message);
+        assert_eq!(result.commits_transferred, 2);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+        assert_eq!(
+            std::fs::read_to_string(repo.workdir().unwrap().join("a.txt")).unwrap(),
+            "dirty"
+        );
+        assert!(repo.workdir().unwrap().join("b.txt").exists());
+        assert!(repo.refname_to_id("refs/stash").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pull_merge_runs_merge_hooks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (dir, mut repo, _remote) = setup_repos();
+        diverge(&dir, &repo);
+        let hooks = repo.path().join("hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        for (name, script) in [
+            ("pre-merge-commit", "echo checked"),
+            ("commit-msg", "echo 'Signed-off-by: Hook' >> \"$1\""),
+            ("post-merge", "touch merged"),
+        ] {
+            let path = hooks.join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let output = std::sync::Mutex::new(Vec::new());
+        let result = pull_internal(
+            &mut repo,
+            "origin",
+            &PullOptions {
+                strategy: Some(PullStrategy::Merge),
+                autostash: None,
+            },
+            &|progress| {
+                if let SyncProgress::HookOutput { hook, line } = progress {
+                    output.lock().unwrap().push((hook, line));
+                }
+            },
+            &CancellationToken::default(),
+        )
+        .unwrap();
+
+        assert!(result.success, "{}", result.message);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2);
+        assert!(head.message().unwrap().ends_with("Signed-off-by: Hook\n"));
+        assert!(repo.workdir().unwrap().join("merged").exists());
+        assert_eq!(
+            output.into_inner().unwrap(),
+            vec![("pre-merge-commit".to_string(), "checked".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_pull_merge_refuses_staged_changes() {
+        let (dir, mut repo, _remote) = setup_repos();
+        diverge(&dir, &repo);
+        let before = repo.head().unwrap().target().unwrap();
+        std::fs::write(repo.workdir().unwrap().join("e.txt"), "staged").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("e.txt")).unwrap();
+        index.write().unwrap();
+
+        let result = pull_internal(
+            &mut repo,
+            "origin",
+            &PullOptions {
+                strategy: Some(PullStrategy::Merge),
+                autostash: Some(false),
+            },
+            &|_| {},
+            &CancellationToken::default(),
+        );
+
+        assert!(matches!(result, Err(GitError::DirtyWorkingDirectory)));
+        assert_eq!(repo.head().unwrap().target().unwrap(), before);
+        assert!(
+            repo.index()
+                .unwrap()
+                .get_path(std::path::Path::new("e.txt"), 0)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_pull_rebase_merges_keeps_local_merge() {
+        let (dir, mut repo, _remote) = setup_repos();
+        diverge(&dir, &repo);
+
+        // Merge a local topic branch into main
+        {
+            let local = repo.head().unwrap().peel_to_commit().unwrap();
+            let base = local.parent(0).unwrap();
+            repo.branch("topic", &base, false).unwrap();
+            repo.set_head("refs/heads/topic").unwrap();
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+                .unwrap();
+            let topic = commit_file(&repo, "d.txt", "d", "Topic");
+            repo.set_head("refs/heads/main").unwrap();
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+                .unwrap();
+            let topic_commit = repo.find_commit(topic).unwrap();
+            let mut index = repo.merge_commits(&local, &topic_commit, None).unwrap();
+            let tree = repo.find_tree(index.write_tree_to(&repo).unwrap()).unwrap();
+            let sig = repo.signature().unwrap();
+            repo.commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "Merge topic",
+                &tree,
+                &[&local, &topic_commit],
+            )
+            .unwrap();
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+                .unwrap();
+        }
+
+        let result = pull(&mut repo, Some(PullStrategy::RebaseMerges), None);
+
+        assert!(result.success, "{}", result.message);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.summary(), Some("Merge topic"));
+        assert_eq!(head.parent_count(), 2);
+        let upstream = repo.refname_to_id("refs/remotes/origin/main").unwrap();
+        assert!(repo.graph_descendant_of(head.id(), upstream).unwrap());
+        for file in ["b.txt", "c.txt", "d.txt"] {
+            assert!(repo.workdir().unwrap().join(file).exists(), "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_add_remote_validates_and_flags_github() {
+        let (_dir, repo, _remote) = setup_repos();
+
+        assert!(matches!(
+            add_remote_internal(&repo, "fork", "not a url"),
+            Err(GitError::InvalidUrl(_))
+        ));
+        assert!(add_remote_internal(&repo, "origin", "https://example.com/x.git").is_err());
+
+        let info =
+            add_remote_internal(&repo, "upstream", "git@github.com:acme/widgets.git").unwrap();
+        assert_eq!(info.github_owner.as_deref(), Some("acme"));
+        assert_eq!(info.github_repo.as_deref(), Some("widgets"));
+        assert_eq!(
+            info.fetch_refspecs,
+            vec!["+refs/heads/*:refs/remotes/upstream/*".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_rename_and_remove_remote() {
+        let (_dir, repo, _remote) = setup_repos();
+        push(&repo, PushOptions::default()).unwrap();
+        assert!(repo.find_reference("refs/remotes/origin/main").is_ok());
+
+        let renamed = rename_remote_internal(&repo, "origin", "upstream").unwrap();
+        assert_eq!(renamed.remote.name, "upstream");
+        assert!(renamed.problems.is_empty());
+        assert!(repo.find_reference("refs/remotes/upstream/main").is_ok());
+        assert!(repo.find_reference("refs/remotes/origin/main").is_err());
+
+        remove_remote_internal(&repo, "upstream").unwrap();
+        assert!(repo.find_remote("upstream").is_err());
+        assert!(repo.find_reference("refs/remotes/upstream/main").is_err());
+        assert!(matches!(
+            remove_remote_internal(&repo, "upstream"),
+            Err(GitError::RemoteNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_rename_reports_non_default_refspecs() {
+        let (_dir, repo, _remote) = setup_repos();
+        repo.config()
+            .unwrap()
+            .set_str("remote.origin.fetch", "+refs/heads/main:refs/mirror/main")
+            .unwrap();
+
+        let renamed = rename_remote_internal(&repo, "origin", "upstream").unwrap();
+        assert_eq!(
+            renamed.problems,
+            vec!["+refs/heads/main:refs/mirror/main".to_string()]
+        );
+        assert_eq!(
+            renamed.remote.fetch_refspecs,
+            vec!["+refs/heads/main:refs/mirror/main".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_set_urls_and_fetch_refspec() {
+        let (_dir, repo, _remote) = setup_repos();
+
+        let info = set_remote_url_internal(
+            &repo,
+            "origin",
+            Some("ssh://git@example.com/team/app.git"),
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            info.push_url.as_deref(),
+            Some("ssh://git@example.com/team/app.git")
+        );
+        assert!(info.url.ends_with("origin.git"));
+
+        let info = set_remote_url_internal(&repo, "origin", None, true).unwrap();
+        assert_eq!(info.push_url, None);
+
+        let info = set_remote_fetch_refspec_internal(
+            &repo,
+            "origin",
+            Some("+refs/heads/main:refs/remotes/origin/main"),
+        )
+        .unwrap();
+        assert_eq!(
+            info.fetch_refspecs,
+            vec!["+refs/heads/main:refs/remotes/origin/main".to_string()]
+        );
+
+        let info = set_remote_fetch_refspec_internal(&repo, "origin", None).unwrap();
+        assert_eq!(
+            info.fetch_refspecs,
+            vec!["+refs/heads/*:refs/remotes/origin/*".to_string()]
+        );
+        assert!(set_remote_fetch_refspec_internal(&repo, "origin", Some("garbage")).is_err());
+    }
+
+    #[test]
+    fn test_fetch_prune_reports_deleted_refs() {
+        let (_dir, repo, remote) = setup_repos();
+        push(&repo, PushOptions::default()).unwrap();
+        let main = repo.refname_to_id("refs/heads/main").unwrap();
+        remote
+            .reference("refs/heads/stale", main, true, "")
+            .unwrap();
+        remote.reference("refs/tags/v1", main, true, "").unwrap();
+        fetch(&repo, &FetchOptions::default()).unwrap();
+        assert!(repo.find_reference("refs/remotes/origin/stale").is_ok());
+        assert!(repo.find_reference("refs/tags/v1").is_ok());
+
+        remote
+            .find_reference("refs/heads/stale")
+            .unwrap()
+            .delete()
+            .unwrap();
+        remote
+            .find_reference("refs/tags/v1")
+            .unwrap()
+            .delete()
+            .unwrap();
+
+        // Without prune nothing is deleted
+        let outcome = fetch(&repo, &FetchOptions::default()).unwrap();
+        assert!(outcome.pruned_refs.is_empty());
+        assert!(repo.find_reference("refs/remotes/origin/stale").is_ok());
+
+        let options = FetchOptions {
+            prune: Some(true),
+            prune_tags: Some(true),
+            ..Default::default()
+        };
+        let mut pruned = fetch(&repo, &options).unwrap().pruned_refs;
+        pruned.sort();
+        assert_eq!(pruned, vec!["refs/remotes/origin/stale", "refs/tags/v1"]);
+        assert!(repo.find_reference("refs/remotes/origin/main").is_ok());
+    }
+
+    #[test]
+    fn test_fetch_all_fetches_every_remote() {
+        let (dir, repo, remote) = setup_repos();
+        push(&repo, PushOptions::default()).unwrap();
+        let main = repo.refname_to_id("refs/heads/main").unwrap();
+        remote.reference("refs/tags/v1", main, true, "").unwrap();
+        git2::Repository::init_bare(dir.path().join("mirror.git")).unwrap();
+        repo.remote("mirror", dir.path().join("mirror.git").to_str().unwrap())
+            .unwrap();
+        let to_mirror = PushOptions {
+            remote_branch: Some("other".to_string()),
+            ..Default::default()
+        };
+        push_internal(
+            &repo,
+            "mirror",
+            &to_mirror,
+            &|_| {},
+            &CancellationToken::default(),
+        )
+        .unwrap();
+        repo.find_reference("refs/remotes/mirror/other")
+            .unwrap()
+            .delete()
+            .unwrap();
+        repo.remote("broken", dir.path().join("missing.git").to_str().unwrap())
+            .unwrap();
+
+        let finished = std::sync::Mutex::new(Vec::new());
+        let options = FetchOptions {
+            tags: Some(TagFollowing::None),
+            ..Default::default()
+        };
+        let mut results = fetch_all_internal(
+            repo.path(),
+            &options,
+            &|progress| {
+                if let SyncProgress::RemoteFinished { remote, success } = progress {
+                    finished.lock().unwrap().push((remote, success));
+                }
+            },
+            &CancellationToken::default(),
+        )
+        .unwrap();
+        results.sort_by(|a, b| a.remote.cmp(&b.remote));
+
+        let summary: Vec<(&str, bool)> = results
+            .iter()
+            .map(|r| (r.remote.as_str(), r.result.success))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("broken", false), ("mirror", true), ("origin", true)]
+        );
+        assert_eq!(finished.into_inner().unwrap().len(), 3);
+        assert!(repo.find_reference("refs/remotes/origin/main").is_ok());
+        assert!(repo.find_reference("refs/remotes/mirror/other").is_ok());
+        assert!(repo.find_reference("refs/tags/v1").is_err());
+    }
+
+    #[test]
+    fn test_fetch_all_follows_tags_from_every_remote() {
+        let (dir, repo, remote) = setup_repos();
+        push(&repo, PushOptions::default()).unwrap();
+        let main = repo.refname_to_id("refs/heads/main").unwrap();
+        let mut tagged = vec!["refs/tags/origin-v1".to_string()];
+        remote.reference(&tagged[0], main, true, "").unwrap();
+        for name in ["mirror", "backup"] {
+            let path = dir.path().join(format!("{}.git", name));
+            let bare = git2::Repository::init_bare(&path).unwrap();
+            repo.remote(name, path.to_str().unwrap()).unwrap();
+            push_internal(
+                &repo,
+                name,
+                &PushOptions::default(),
+                &|_| {},
+                &CancellationToken::default(),
+            )
+            .unwrap();
+            let tag = format!("refs/tags/{}-v1", name);
+            bare.reference(&tag, main, true, "").unwrap();
+            tagged.push(tag);
+        }
+
+        let options = FetchOptions {
+            tags: Some(TagFollowing::All),
+            ..Default::default()
+        };
+        let results = fetch_all_internal(
+            repo.path(),
+            &options,
+            &|_| {},
+            &CancellationToken::default(),
+        )
+        .unwrap();
+
+        assert!(results.iter().all(|r| r.result.success));
+        for tag in &tagged {
+            assert!(repo.find_reference(tag).is_ok(), "missing {}", tag);
+        }
+        // Each remote would clobber FETCH_HEAD, so a fetch-all leaves it empty
+        let fetch_head = std::fs::read(repo.path().join("FETCH_HEAD")).unwrap_or_default();
+        assert!(fetch_head.is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_fetch_and_push_leave_refs_untouched() {
+        let (_dir, repo, remote) = setup_repos();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        assert!(matches!(
+            push_internal(&repo, "origin", &PushOptions::default(), &|_| {}, &cancel),
+            Err(GitError::Cancelled)
+        ));
+        assert!(remote.find_reference("refs/heads/main").is_err());
+
+        push(&repo, PushOptions::default()).unwrap();
+        let main = repo.refname_to_id("refs/heads/main").unwrap();
+        remote
+            .reference("refs/heads/other", main, true, "")
+            .unwrap();
+        assert!(matches!(
+            fetch_remote(&repo, "origin", &FetchOptions::default(), &|_| {}, &cancel),
+            Err(GitError::Cancelled)
+        ));
+        assert!(repo.find_reference("refs/remotes/origin/other").is_err());
+    }
+
+    #[test]
+    fn test_shallow_depth_and_deepen_guard() {
+        let (dir, repo, _remote) = setup_repos();
+        commit_file(&repo, "b.txt", "two", "Second");
+        commit_file(&repo, "c.txt", "three", "Third");
+        assert!(matches!(
+            deepen_internal(&repo, Some(1), &|_| {}, &CancellationToken::default()),
+            Err(GitError::OperationFailed(_))
+        ));
+
+        // libgit2 cannot fetch shallow over a local path, so let git create the clone
+        let url = format!("file://{}", repo.workdir().unwrap().display());
+        let cloned = std::process::Command::new("git")
+            .args(["clone", "--quiet", "--depth=2", &url])
+            .arg(dir.path().join("shallow"))
+            .status();
+        if !cloned.map(|s| s.success()).unwrap_or(false) {
+            return;
+        }
+        let shallow = git2::Repository::open(dir.path().join("shallow")).unwrap();
+        assert!(shallow.is_shallow());
+        assert_eq!(shallow_depth(&shallow).unwrap(), 2);
+        assert_eq!(default_fetch_remote(&shallow).unwrap(), "origin");
+    }
 }
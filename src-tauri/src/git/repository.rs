@@ -5,6 +5,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::git::error::GitError;
+use crate::git::operation::{detect_operation, OperationState};
 
 /// Repository status information sent to frontend.
 ///
@@ -21,6 +22,10 @@ pub struct RepoStatus {
     pub repo_path: String,
     /// Repository display name (folder name)
     pub repo_name: String,
+    /// Merge, rebase, cherry-pick, revert or bisect in progress (if any)
+    pub operation: Option<OperationState>,
+    /// History is truncated (shallow clone) and can be deepened
+    pub is_shallow: bool,
 }
 
 /// Application state holding the current repository path.
@@ -109,9 +114,7 @@ impl RepositoryState {
             // Check if working directory is dirty
             // Use fast options - don't recurse into untracked directories deeply
             let mut opts = git2::StatusOptions::new();
-            opts.include_untracked(true)
-                .recurse_untracked_dirs(false)
-                .exclude_submodules(true);
+            opts.include_untracked(true).recurse_untracked_dirs(false);
 
             let statuses = repo.statuses(Some(&mut opts))?;
             let is_dirty = !statuses.is_empty();
@@ -123,11 +126,15 @@ impl RepositoryState {
                 .unwrap_or("unknown")
                 .to_string();
 
+            let operation = detect_operation(&repo)?;
+
             Ok(RepoStatus {
                 branch_name,
                 is_dirty,
                 repo_path: path.display().to_string(),
                 repo_name,
+                operation,
+                is_shallow: repo.is_shallow(),
             })
         })
         .await
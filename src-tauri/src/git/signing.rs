@@ -0,0 +1,823 @@
+//! Commit and tag signing and signature verification.
+//!
+//! libgit2 creates unsigned objects, so signing follows git: when
+//! `commit.gpgsign` / `tag.gpgsign` is enabled the object buffer is built
+//! first, signed by the program selected through `gpg.format` (gpg, gpgsm or
+//! ssh-keygen) with `user.signingkey`, and written together with the
+//! signature. Verification runs the same programs, with SSH signatures
+//! checked against `gpg.ssh.allowedSignersFile`.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::git::error::GitError;
+
+/// Outcome of verifying a commit signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SignatureStatus {
+    /// Valid signature from a trusted key or allowed signer
+    Good,
+    /// Signature does not match, or the key is expired or revoked
+    Bad,
+    /// Signed, but the key is not in the keyring / allowed signers
+    UnknownKey,
+    /// No signature
+    Unsigned,
+}
+
+/// Signature information attached to commits in history, details and the graph.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSignature {
+    pub status: SignatureStatus,
+    /// Signer identity (gpg user id or ssh principal), when known
+    pub signer: Option<String>,
+    /// Key id (gpg long key id or ssh key fingerprint), when known
+    pub key_id: Option<String>,
+}
+
+impl CommitSignature {
+    pub(crate) fn unsigned() -> Self {
+        Self {
+            status: SignatureStatus::Unsigned,
+            signer: None,
+            key_id: None,
+        }
+    }
+
+    fn new(status: SignatureStatus, signer: Option<String>, key_id: Option<String>) -> Self {
+        Self {
+            status,
+            signer,
+            key_id,
+        }
+    }
+}
+
+/// Signature backend selected by `gpg.format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SigningFormat {
+    OpenPgp,
+    Ssh,
+    X509,
+}
+
+/// Resolved signing settings for one kind of object.
+#[derive(Debug, Clone)]
+pub(crate) struct SigningConfig {
+    pub format: SigningFormat,
+    /// `user.signingkey`: key id for gpg/gpgsm, key file or literal public key for ssh
+    pub key: Option<String>,
+    /// Signing program (`gpg.<format>.program`, falling back to `gpg.program` for openpgp)
+    pub program: String,
+}
+
+impl SigningConfig {
+    /// Settings for signing commits, or `None` when `commit.gpgsign` is off.
+    pub(crate) fn for_commits(repo: &git2::Repository) -> Result<Option<Self>, GitError> {
+        Self::load(repo, "commit.gpgsign")
+    }
+
+    /// Settings for signing annotated tags, or `None` when `tag.gpgsign` is off.
+    pub(crate) fn for_tags(repo: &git2::Repository) -> Result<Option<Self>, GitError> {
+        Self::load(repo, "tag.gpgsign")
+    }
+
+    fn load(repo: &git2::Repository, enabled_key: &str) -> Result<Option<Self>, GitError> {
+        let config = repo.config()?;
+        if !config.get_bool(enabled_key).unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let format = match config.get_string("gpg.format") {
+            Err(_) => SigningFormat::OpenPgp,
+            Ok(value) => match value.to_lowercase().as_str() {
+                "openpgp" => SigningFormat::OpenPgp,
+                "ssh" => SigningFormat::Ssh,
+                "x509" => SigningFormat::X509,
+                other => {
+                    return Err(GitError::SigningFailed(format!(
+                        "unsupported gpg.format '{}'",
+                        other
+                    )));
+                }
+            },
+        };
+
+        let program = signing_program(&config, format);
+
+        let key = config
+            .get_string("user.signingkey")
+            .ok()
+            .filter(|k| !k.trim().is_empty());
+
+        Ok(Some(Self {
+            format,
+            key,
+            program,
+        }))
+    }
+
+    /// Produce a detached, armored signature of `payload`.
+    ///
+    /// `signer` is used as the key id for gpg/gpgsm when `user.signingkey`
+    /// is not set, like git does.
+    pub(crate) fn sign(&self, payload: &str, signer: &git2::Signature) -> Result<String, GitError> {
+        match self.format {
+            SigningFormat::OpenPgp | SigningFormat::X509 => {
+                let key = self.key.clone().unwrap_or_else(|| {
+                    format!(
+                        "{} <{}>",
+                        signer.name().unwrap_or(""),
+                        signer.email().unwrap_or("")
+                    )
+                });
+                let (signature, status) =
+                    run_signer(&self.program, &["--status-fd=2", "-bsau", &key], payload)?;
+                if !status.contains("[GNUPG:] SIG_CREATED ") {
+                    return Err(GitError::SigningFailed(format!(
+                        "{} did not create a signature\n{}",
+                        self.program,
+                        status.trim_end()
+                    )));
+                }
+                Ok(signature)
+            }
+            SigningFormat::Ssh => {
+                let key = self.key.as_deref().ok_or_else(|| {
+                    GitError::SigningFailed(
+                        "user.signingkey must be set for SSH signing".to_string(),
+                    )
+                })?;
+                self.sign_ssh(key, payload)
+            }
+        }
+    }
+
+    fn sign_ssh(&self, key: &str, payload: &str) -> Result<String, GitError> {
+        let literal = key
+            .strip_prefix("key::")
+            .or_else(|| key.starts_with("ssh-").then_some(key));
+
+        match literal {
+            Some(public_key) => {
+                // A literal public key: the private half lives in ssh-agent
+                let key_file =
+                    TempFile::new("pub", &format!("{}\n", public_key.trim())).map_err(|e| {
+                        GitError::SigningFailed(format!("Could not write signing key: {}", e))
+                    })?;
+                run_signer(
+                    &self.program,
+                    &["-Y", "sign", "-n", "git", "-U", "-f", &key_file.arg()],
+                    payload,
+                )
+                .map(|(signature, _)| signature)
+            }
+            None => {
+                let key_path = expand_home(key).to_string_lossy().to_string();
+                run_signer(
+                    &self.program,
+                    &["-Y", "sign", "-n", "git", "-f", &key_path],
+                    payload,
+                )
+                .map(|(signature, _)| signature)
+            }
+        }
+    }
+}
+
+/// Program used for a signature format (`gpg.<format>.program`; `gpg.program` for openpgp).
+fn signing_program(config: &git2::Config, format: SigningFormat) -> String {
+    match format {
+        SigningFormat::OpenPgp => config
+            .get_string("gpg.openpgp.program")
+            .or_else(|_| config.get_string("gpg.program"))
+            .unwrap_or_else(|_| "gpg".to_string()),
+        SigningFormat::X509 => config
+            .get_string("gpg.x509.program")
+            .unwrap_or_else(|_| "gpgsm".to_string()),
+        SigningFormat::Ssh => config
+            .get_string("gpg.ssh.program")
+            .unwrap_or_else(|_| "ssh-keygen".to_string()),
+    }
+}
+
+/// Temporary file handed to gpg/ssh-keygen, removed on drop.
+///
+/// Created with a random name, exclusively and readable only by the user, so
+/// another user cannot plant a symlink in its place.
+struct TempFile(tempfile::NamedTempFile);
+
+impl TempFile {
+    fn new(extension: &str, contents: &str) -> std::io::Result<Self> {
+        let mut file = tempfile::Builder::new()
+            .prefix(".flowforge-")
+            .suffix(&format!(".{}", extension))
+            .tempfile()?;
+        file.write_all(contents.as_bytes())?;
+        file.flush()?;
+        Ok(Self(file))
+    }
+
+    fn arg(&self) -> String {
+        self.0.path().to_string_lossy().to_string()
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Run a program with `payload` on stdin and capture its output.
+fn run_with_input(program: &str, args: &[&str], payload: &str) -> std::io::Result<Output> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take();
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            if let Some(pipe) = stdin.as_mut() {
+                let _ = pipe.write_all(payload.as_bytes());
+            }
+        });
+        child.wait_with_output()
+    })
+}
+
+/// Run a signing program with `payload` on stdin; returns (stdout, stderr).
+fn run_signer(program: &str, args: &[&str], payload: &str) -> Result<(String, String), GitError> {
+    let output = run_with_input(program, args, payload)
+        .map_err(|e| GitError::SigningFailed(format!("Could not run {}: {}", program, e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    if !output.status.success() || stdout.trim().is_empty() {
+        return Err(GitError::SigningFailed(format!(
+            "{} failed to sign the data\n{}",
+            program,
+            stderr.trim_end()
+        )));
+    }
+
+    Ok((stdout, stderr))
+}
+
+/// Format a signature the way it appears in commit and tag headers.
+fn format_ident(sig: &git2::Signature) -> String {
+    let offset = sig.when().offset_minutes();
+    let sign = if offset < 0 { '-' } else { '+' };
+    format!(
+        "{} <{}> {} {}{:02}{:02}",
+        sig.name().unwrap_or(""),
+        sig.email().unwrap_or(""),
+        sig.when().seconds(),
+        sign,
+        offset.abs() / 60,
+        offset.abs() % 60
+    )
+}
+
+/// Point `update_ref` at a newly written commit, with a `git commit` style reflog entry.
+fn update_ref_for_commit(
+    repo: &git2::Repository,
+    update_ref: &str,
+    oid: git2::Oid,
+    message: &str,
+    parent_count: usize,
+) -> Result<(), GitError> {
+    let summary = message.lines().next().unwrap_or("");
+    let log_message = match parent_count {
+        0 => format!("commit (initial): {}", summary),
+        1 => format!("commit: {}", summary),
+        _ => format!("commit (merge): {}", summary),
+    };
+
+    // Resolve HEAD (and other symbolic refs) to the branch they point at,
+    // which may not exist yet on an unborn branch
+    let target = match repo.find_reference(update_ref) {
+        Ok(reference) => reference.symbolic_target().map(str::to_string),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    match target {
+        Some(branch_ref) => {
+            repo.reference(&branch_ref, oid, true, &log_message)?;
+        }
+        None if update_ref == "HEAD" => repo.set_head_detached(oid)?,
+        None => {
+            repo.reference(update_ref, oid, true, &log_message)?;
+        }
+    }
+    Ok(())
+}
+
+/// Create a commit, signing it when `commit.gpgsign` is enabled.
+///
+/// Drop-in replacement for `Repository::commit`; signing failures surface as
+/// `SigningFailed` and leave no ref updated.
+pub(crate) fn create_commit_signed(
+    repo: &git2::Repository,
+    update_ref: Option<&str>,
+    author: &git2::Signature,
+    committer: &git2::Signature,
+    message: &str,
+    tree: &git2::Tree,
+    parents: &[&git2::Commit],
+) -> Result<git2::Oid, GitError> {
+    let Some(signing) = SigningConfig::for_commits(repo)? else {
+        return Ok(repo.commit(update_ref, author, committer, message, tree, parents)?);
+    };
+
+    let buffer = repo.commit_create_buffer(author, committer, message, tree, parents)?;
+    let content = buffer
+        .as_str()
+        .ok_or_else(|| GitError::SigningFailed("Commit buffer is not valid UTF-8".to_string()))?;
+    let signature = signing.sign(content, committer)?;
+    let oid = repo.commit_signed(content, &signature, None)?;
+
+    if let Some(update_ref) = update_ref {
+        update_ref_for_commit(repo, update_ref, oid, message, parents.len())?;
+    }
+    Ok(oid)
+}
+
+/// Create an annotated tag, signing it when `tag.gpgsign` is enabled.
+///
+/// Drop-in replacement for `Repository::tag` without `force`; the signature
+/// is appended to the tag message as git does.
+pub(crate) fn create_tag_signed(
+    repo: &git2::Repository,
+    name: &str,
+    target: &git2::Object,
+    tagger: &git2::Signature,
+    message: &str,
+) -> Result<git2::Oid, GitError> {
+    let Some(signing) = SigningConfig::for_tags(repo)? else {
+        return Ok(repo.tag(name, target, tagger, message, false)?);
+    };
+
+    let kind = target
+        .kind()
+        .map(|k| k.str())
+        .ok_or_else(|| GitError::OperationFailed("Unknown tag target type".to_string()))?;
+    let mut content = format!(
+        "object {}\ntype {}\ntag {}\ntagger {}\n\n{}",
+        target.id(),
+        kind,
+        name,
+        format_ident(tagger),
+        message
+    );
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+
+    let signature = signing.sign(&content, tagger)?;
+    content.push_str(&signature);
+
+    let oid = repo
+        .odb()?
+        .write(git2::ObjectType::Tag, content.as_bytes())?;
+    repo.reference(&format!("refs/tags/{}", name), oid, false, "tag: signed")?;
+    Ok(oid)
+}
+
+/// Programs and trust settings used to verify signatures.
+#[derive(Clone, PartialEq)]
+struct Verifier {
+    gpg_program: String,
+    x509_program: String,
+    ssh_program: String,
+    allowed_signers: Option<PathBuf>,
+    /// Modification time and size of the allowed signers file, so edits to it
+    /// invalidate cached results
+    allowed_signers_version: Option<(SystemTime, u64)>,
+}
+
+impl Verifier {
+    fn load(repo: &git2::Repository) -> Result<Self, GitError> {
+        let config = repo.config()?;
+        let allowed_signers = config.get_path("gpg.ssh.allowedSignersFile").ok();
+        let allowed_signers_version = allowed_signers
+            .as_deref()
+            .and_then(|path| std::fs::metadata(path).ok())
+            .and_then(|meta| Some((meta.modified().ok()?, meta.len())));
+        Ok(Self {
+            gpg_program: signing_program(&config, SigningFormat::OpenPgp),
+            x509_program: signing_program(&config, SigningFormat::X509),
+            ssh_program: signing_program(&config, SigningFormat::Ssh),
+            allowed_signers,
+            allowed_signers_version,
+        })
+    }
+
+    fn verify(&self, signature: &str, payload: &str) -> CommitSignature {
+        let Ok(sig_file) = TempFile::new("sig", signature) else {
+            return CommitSignature::new(SignatureStatus::UnknownKey, None, None);
+        };
+
+        if signature.contains("-----BEGIN SSH SIGNATURE-----") {
+            self.verify_ssh(&sig_file, payload)
+        } else if signature.contains("-----BEGIN SIGNED MESSAGE-----") {
+            verify_gpg(&self.x509_program, &sig_file, payload)
+        } else {
+            verify_gpg(&self.gpg_program, &sig_file, payload)
+        }
+    }
+
+    fn verify_ssh(&self, sig_file: &TempFile, payload: &str) -> CommitSignature {
+        let sig_arg = sig_file.arg();
+
+        if let Some(allowed) = self.allowed_signers.as_deref().filter(|p| p.is_file()) {
+            let allowed_arg = allowed.to_string_lossy().to_string();
+            let principal = Command::new(&self.ssh_program)
+                .args(["-Y", "find-principals", "-f", &allowed_arg, "-s", &sig_arg])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+                .and_then(|o| {
+                    String::from_utf8_lossy(&o.stdout)
+                        .lines()
+                        .next()
+                        .map(|l| l.trim().to_string())
+                })
+                .filter(|p| !p.is_empty());
+
+            if let Some(principal) = principal {
+                let args = [
+                    "-Y",
+                    "verify",
+                    "-n",
+                    "git",
+                    "-f",
+                    &allowed_arg,
+                    "-I",
+                    &principal,
+                    "-s",
+                    &sig_arg,
+                ];
+                return match run_with_input(&self.ssh_program, &args, payload) {
+                    Ok(output) => {
+                        let status = if output.status.success() {
+                            SignatureStatus::Good
+                        } else {
+                            SignatureStatus::Bad
+                        };
+                        CommitSignature::new(status, Some(principal), ssh_key_id(&output))
+                    }
+                    Err(_) => CommitSignature::new(SignatureStatus::UnknownKey, None, None),
+                };
+            }
+        }
+
+        // Not an allowed signer: the signature can still be checked for integrity
+        let args = ["-Y", "check-novalidate", "-n", "git", "-s", &sig_arg];
+        match run_with_input(&self.ssh_program, &args, payload) {
+            Ok(output) if output.status.success() => {
+                CommitSignature::new(SignatureStatus::UnknownKey, None, ssh_key_id(&output))
+            }
+            Ok(output) => CommitSignature::new(SignatureStatus::Bad, None, ssh_key_id(&output)),
+            Err(_) => CommitSignature::new(SignatureStatus::UnknownKey, None, None),
+        }
+    }
+}
+
+/// Key fingerprint from ssh-keygen output ("... with ED25519 key SHA256:...").
+fn ssh_key_id(output: &Output) -> Option<String> {
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    text.split_whitespace()
+        .find(|word| word.starts_with("SHA256:"))
+        .map(str::to_string)
+}
+
+/// Verify an OpenPGP or X.509 signature through gpg's machine-readable status lines.
+fn verify_gpg(program: &str, sig_file: &TempFile, payload: &str) -> CommitSignature {
+    let args = [
+        "--status-fd=1",
+        "--keyid-format=long",
+        "--verify",
+        &sig_file.arg(),
+        "-",
+    ];
+    let Ok(output) = run_with_input(program, &args, payload) else {
+        return CommitSignature::new(SignatureStatus::UnknownKey, None, None);
+    };
+    parse_gpg_status(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_gpg_status(status: &str) -> CommitSignature {
+    let mut result = CommitSignature::new(SignatureStatus::UnknownKey, None, None);
+
+    for line in status.lines() {
+        let Some(rest) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let mut parts = rest.splitn(3, ' ');
+        let keyword = parts.next().unwrap_or("");
+        let key_id = parts.next().map(str::to_string);
+
+        let status = match keyword {
+            "GOODSIG" => SignatureStatus::Good,
+            "BADSIG" | "EXPSIG" | "EXPKEYSIG" | "REVKEYSIG" => SignatureStatus::Bad,
+            "ERRSIG" | "NO_PUBKEY" => SignatureStatus::UnknownKey,
+            _ => continue,
+        };
+        // Only the *SIG lines carry the user id; ERRSIG is followed by algorithm fields
+        let signer = match keyword {
+            "ERRSIG" | "NO_PUBKEY" => None,
+            _ => parts.next().map(str::to_string),
+        };
+        // A bad signature wins over anything else reported for the same signature
+        if result.status != SignatureStatus::Bad {
+            result = CommitSignature::new(status, signer.or(result.signer), key_id);
+        }
+    }
+
+    result
+}
+
+/// Verified signatures kept across all repositories before the cache is emptied.
+const MAX_CACHED_SIGNATURES: usize = 10_000;
+
+/// A repository's verified signatures and the settings they were checked with.
+struct CachedSignatures {
+    verifier: Verifier,
+    signatures: HashMap<git2::Oid, CommitSignature>,
+}
+
+/// Verified signatures by repository. Only definitive results are cached;
+/// unknown keys are re-checked since keys may be imported later, and a
+/// repository's results are dropped when its verification settings change.
+static VERIFIED: Mutex<Option<HashMap<PathBuf, CachedSignatures>>> = Mutex::new(None);
+
+fn cached(repo_key: &Path, verifier: &Verifier, oid: git2::Oid) -> Option<CommitSignature> {
+    VERIFIED
+        .lock()
+        .ok()?
+        .as_ref()?
+        .get(repo_key)
+        .filter(|cached| cached.verifier == *verifier)?
+        .signatures
+        .get(&oid)
+        .cloned()
+}
+
+fn store(repo_key: &Path, verifier: &Verifier, oid: git2::Oid, signature: &CommitSignature) {
+    if !matches!(
+        signature.status,
+        SignatureStatus::Good | SignatureStatus::Bad
+    ) {
+        return;
+    }
+    let Ok(mut cache) = VERIFIED.lock() else {
+        return;
+    };
+    let cache = cache.get_or_insert_with(HashMap::new);
+    let total: usize = cache.values().map(|c| c.signatures.len()).sum();
+    if total >= MAX_CACHED_SIGNATURES {
+        cache.clear();
+    }
+    let entry = cache
+        .entry(repo_key.to_path_buf())
+        .or_insert_with(|| CachedSignatures {
+            verifier: verifier.clone(),
+            signatures: HashMap::new(),
+        });
+    if entry.verifier != *verifier {
+        entry.verifier = verifier.clone();
+        entry.signatures.clear();
+    }
+    entry.signatures.insert(oid, signature.clone());
+}
+
+/// Verify the signature of a single commit.
+pub(crate) fn verify_commit_signature(repo: &git2::Repository, oid: git2::Oid) -> CommitSignature {
+    verify_commit_signatures(repo, &[oid])
+        .remove(&oid)
+        .unwrap_or_else(CommitSignature::unsigned)
+}
+
+/// Verify the signatures of many commits.
+///
+/// Unsigned commits are detected without spawning anything; signed ones are
+/// verified in parallel since each check runs an external program.
+pub(crate) fn verify_commit_signatures(
+    repo: &git2::Repository,
+    oids: &[git2::Oid],
+) -> HashMap<git2::Oid, CommitSignature> {
+    let repo_key = repo.commondir().to_path_buf();
+    let verifier = Verifier::load(repo).ok();
+    let mut results = HashMap::with_capacity(oids.len());
+    let mut pending = Vec::new();
+
+    for &oid in oids {
+        if let Some(signature) = verifier
+            .as_ref()
+            .and_then(|verifier| cached(&repo_key, verifier, oid))
+        {
+            results.insert(oid, signature);
+            continue;
+        }
+        match repo.extract_signature(&oid, None) {
+            Ok((signature, payload)) => pending.push((
+                oid,
+                String::from_utf8_lossy(&signature).to_string(),
+                String::from_utf8_lossy(&payload).to_string(),
+            )),
+            Err(_) => {
+                results.insert(oid, CommitSignature::unsigned());
+            }
+        }
+    }
+
+    if pending.is_empty() {
+        return results;
+    }
+
+    let Some(verifier) = verifier else {
+        for (oid, _, _) in pending {
+            results.insert(
+                oid,
+                CommitSignature::new(SignatureStatus::UnknownKey, None, None),
+            );
+        }
+        return results;
+    };
+
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(pending.len());
+    let chunk_size = pending.len().div_ceil(workers);
+    let verified: Vec<(git2::Oid, CommitSignature)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = pending
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let verifier = &verifier;
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(oid, signature, payload)| {
+                            (*oid, verifier.verify(signature, payload))
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    });
+
+    for (oid, signature) in verified {
+        store(&repo_key, &verifier, oid, &signature);
+        results.insert(oid, signature);
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{configure_ssh_signing, setup_repo};
+
+    fn empty_tree(repo: &git2::Repository) -> git2::Tree<'_> {
+        let oid = repo.index().unwrap().write_tree().unwrap();
+        repo.find_tree(oid).unwrap()
+    }
+
+    #[test]
+    fn test_unsigned_when_gpgsign_disabled() {
+        let (_dir, repo) = setup_repo();
+        let sig = repo.signature().unwrap();
+        let tree = empty_tree(&repo);
+
+        let oid =
+            create_commit_signed(&repo, Some("HEAD"), &sig, &sig, "Initial", &tree, &[]).unwrap();
+
+        assert_eq!(repo.head().unwrap().target(), Some(oid));
+        assert!(repo.extract_signature(&oid, None).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_format_is_signing_error() {
+        let (_dir, repo) = setup_repo();
+        let mut config = repo.config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "pgp2").unwrap();
+
+        assert!(matches!(
+            SigningConfig::for_commits(&repo),
+            Err(GitError::SigningFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_ssh_signed_commit_and_tag() {
+        let (dir, repo) = setup_repo();
+        if configure_ssh_signing(&repo, dir.path()).is_none() {
+            return;
+        }
+        let sig = repo.signature().unwrap();
+        let tree = empty_tree(&repo);
+
+        let oid =
+            create_commit_signed(&repo, Some("HEAD"), &sig, &sig, "Signed\n", &tree, &[]).unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(oid));
+        let (signature, _) = repo.extract_signature(&oid, None).unwrap();
+        assert!(signature.as_str().unwrap().contains("BEGIN SSH SIGNATURE"));
+
+        let commit = repo.find_commit(oid).unwrap();
+        let tag_oid =
+            create_tag_signed(&repo, "v1.0.0", commit.as_object(), &sig, "Release").unwrap();
+        let tag = repo.find_tag(tag_oid).unwrap();
+        assert_eq!(tag.name(), Some("v1.0.0"));
+        assert_eq!(tag.target_id(), oid);
+        assert!(tag.message().unwrap().contains("BEGIN SSH SIGNATURE"));
+        assert!(repo.find_reference("refs/tags/v1.0.0").is_ok());
+    }
+
+    #[test]
+    fn test_verify_ssh_signature_against_allowed_signers() {
+        let (dir, repo) = setup_repo();
+        let Some(key) = configure_ssh_signing(&repo, dir.path()) else {
+            return;
+        };
+        let sig = repo.signature().unwrap();
+        let tree = empty_tree(&repo);
+        let unsigned = repo
+            .commit(None, &sig, &sig, "Unsigned", &tree, &[])
+            .unwrap();
+        let signed = create_commit_signed(&repo, None, &sig, &sig, "Signed", &tree, &[]).unwrap();
+
+        assert_eq!(
+            verify_commit_signature(&repo, unsigned).status,
+            SignatureStatus::Unsigned
+        );
+
+        // Without allowed signers the key is unknown but its fingerprint is reported
+        let result = verify_commit_signature(&repo, signed);
+        assert_eq!(result.status, SignatureStatus::UnknownKey);
+        assert!(result.key_id.unwrap().starts_with("SHA256:"));
+
+        let public_key = std::fs::read_to_string(key.with_extension("pub")).unwrap();
+        let allowed = dir.path().join("allowed_signers");
+        std::fs::write(&allowed, format!("test@example.com {}", public_key)).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("gpg.ssh.allowedSignersFile", allowed.to_str().unwrap())
+            .unwrap();
+
+        let result = verify_commit_signature(&repo, signed);
+        assert_eq!(result.status, SignatureStatus::Good);
+        assert_eq!(result.signer.as_deref(), Some("test@example.com"));
+
+        // A cached result does not outlive the signer being removed
+        std::fs::write(&allowed, "").unwrap();
+        assert_ne!(
+            verify_commit_signature(&repo, signed).status,
+            SignatureStatus::Good
+        );
+    }
+
+    #[test]
+    fn test_parse_gpg_status() {
+        let good = parse_gpg_status(
+            "[GNUPG:] NEWSIG\n[GNUPG:] GOODSIG 0123456789ABCDEF Test User <test@example.com>\n",
+        );
+        assert_eq!(good.status, SignatureStatus::Good);
+        assert_eq!(good.key_id.as_deref(), Some("0123456789ABCDEF"));
+        assert_eq!(good.signer.as_deref(), Some("Test User <test@example.com>"));
+
+        let unknown = parse_gpg_status(
+            "[GNUPG:] ERRSIG 0123456789ABCDEF 1 10 00 1700000000 9 -\n[GNUPG:] NO_PUBKEY 0123456789ABCDEF\n",
+        );
+        assert_eq!(unknown.status, SignatureStatus::UnknownKey);
+        assert_eq!(unknown.key_id.as_deref(), Some("0123456789ABCDEF"));
+        assert_eq!(unknown.signer, None);
+
+        let bad = parse_gpg_status("[GNUPG:] BADSIG 0123456789ABCDEF Test User\n");
+        assert_eq!(bad.status, SignatureStatus::Bad);
+    }
+}
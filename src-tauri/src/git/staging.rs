@@ -7,6 +7,7 @@ use tauri::State;
 use crate::git::diff::extract_hunks_from_diff;
 use crate::git::error::GitError;
 use crate::git::repository::RepositoryState;
+use crate::git::submodule::{SubmoduleChange, staged_submodule_change, unstaged_submodule_change};
 
 /// Status of a file in the working directory or index.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -28,6 +29,8 @@ pub struct FileChange {
     pub status: FileStatus,
     pub additions: Option<i32>,
     pub deletions: Option<i32>,
+    /// Pointer change when the path is a submodule
+    pub submodule: Option<SubmoduleChange>,
 }
 
 /// Complete staging status showing staged, unstaged, and untracked files.
@@ -67,10 +70,17 @@ pub async fn get_staging_status(
         let mut opts = git2::StatusOptions::new();
         opts.include_untracked(true)
             .recurse_untracked_dirs(true)
-            .exclude_submodules(true)
             .include_ignored(false);
 
         let statuses = repo.statuses(Some(&mut opts))?;
+        let submodule_paths: HashSet<String> = repo
+            .submodules()
+            .map(|subs| {
+                subs.iter()
+                    .map(|s| s.path().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
 
         let mut staged = Vec::new();
         let mut unstaged = Vec::new();
@@ -102,11 +112,16 @@ pub async fn get_staging_status(
                     FileStatus::Modified
                 };
 
+                let submodule = submodule_paths
+                    .contains(&file_path)
+                    .then(|| staged_submodule_change(&repo, &file_path))
+                    .flatten();
                 staged.push(FileChange {
                     path: file_path.clone(),
                     status: file_status,
                     additions: None,
                     deletions: None,
+                    submodule,
                 });
             }
 
@@ -129,11 +144,16 @@ pub async fn get_staging_status(
                     FileStatus::Modified
                 };
 
+                let submodule = submodule_paths
+                    .contains(&file_path)
+                    .then(|| unstaged_submodule_change(&repo, &file_path))
+                    .flatten();
                 unstaged.push(FileChange {
                     path: file_path.clone(),
                     status: file_status,
                     additions: None,
                     deletions: None,
+                    submodule,
                 });
             }
 
@@ -144,6 +164,7 @@ pub async fn get_staging_status(
                     status: FileStatus::Untracked,
                     additions: None,
                     deletions: None,
+                    submodule: None,
                 });
             }
 
@@ -154,6 +175,7 @@ pub async fn get_staging_status(
                     status: FileStatus::Conflicted,
                     additions: None,
                     deletions: None,
+                    submodule: None,
                 });
             }
         }
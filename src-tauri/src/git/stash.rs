@@ -1,10 +1,13 @@
 use git2::StashFlags;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::path::{Path, PathBuf};
 use tauri::State;
 
+use crate::git::diff::{FileDiff, tree_file_diffs};
 use crate::git::error::GitError;
 use crate::git::repository::RepositoryState;
+use crate::git::undo::record_operation;
 
 /// A stash entry representing saved work.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -18,6 +21,19 @@ pub struct StashEntry {
     pub oid: String,
 }
 
+/// What to stash besides tracked changes, and which paths.
+#[derive(Debug, Clone, Default)]
+pub struct StashOptions {
+    /// Only stash changes to these pathspecs (`git stash push -- <paths>`)
+    pub paths: Vec<String>,
+    /// Leave staged changes in place (`--keep-index`)
+    pub keep_index: bool,
+    /// Also stash untracked files (`--include-untracked`)
+    pub include_untracked: bool,
+    /// Also stash untracked and ignored files (`--all`)
+    pub include_ignored: bool,
+}
+
 /// List all stash entries in the repository.
 #[tauri::command]
 #[specta::specta]
@@ -47,12 +63,261 @@ pub async fn list_stashes(state: State<'_, RepositoryState>) -> Result<Vec<Stash
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
+/// Stash the working tree and index; returns the stash commit OID.
+pub fn stash_save_internal(
+    repo: &mut git2::Repository,
+    message: Option<&str>,
+    include_untracked: bool,
+) -> Result<git2::Oid, GitError> {
+    let options = StashOptions {
+        include_untracked,
+        ..Default::default()
+    };
+    stash_save_with_options(repo, message, &options)
+}
+
+/// Stash with `git stash push` options; returns the stash commit OID.
+pub fn stash_save_with_options(
+    repo: &mut git2::Repository,
+    message: Option<&str>,
+    options: &StashOptions,
+) -> Result<git2::Oid, GitError> {
+    let signature = repo.signature().map_err(|e| {
+        GitError::SignatureError(format!(
+            "Could not determine stash author. Please configure git: {}",
+            e.message()
+        ))
+    })?;
+
+    let mut flags = StashFlags::DEFAULT;
+    if options.keep_index {
+        flags |= StashFlags::KEEP_INDEX;
+    }
+    if options.include_untracked || options.include_ignored {
+        flags |= StashFlags::INCLUDE_UNTRACKED;
+    }
+    if options.include_ignored {
+        flags |= StashFlags::INCLUDE_IGNORED;
+    }
+
+    let message = message.unwrap_or("WIP");
+    let result = if options.paths.is_empty() {
+        repo.stash_save(&signature, message, Some(flags))
+    } else {
+        // libgit2 would stash every untracked file and reset the whole tree,
+        // so untracked files are refused and only the paths are reset here
+        if flags.contains(StashFlags::INCLUDE_UNTRACKED) {
+            return Err(GitError::UnsupportedOperation(
+                "stashing untracked files together with paths".to_string(),
+            ));
+        }
+        let mut save_options = git2::StashSaveOptions::new(signature.clone());
+        save_options.flags(Some(flags | StashFlags::KEEP_ALL));
+        for path in &options.paths {
+            save_options.pathspec(path.as_str());
+        }
+        repo.stash_save_ext(Some(&mut save_options)).and_then(|oid| {
+            set_stash_message(repo, &signature, oid, message)?;
+            reset_paths(repo, &options.paths, options.keep_index)?;
+            Ok(oid)
+        })
+    };
+    result.map_err(|e| {
+        if e.message().contains("no local changes to save") {
+            GitError::NothingToStash
+        } else {
+            GitError::from(e)
+        }
+    })
+}
+
+/// Replace the newest stash's reflog message, which `git stash list` shows.
+///
+/// git2 cannot pass a message along with pathspecs, so the entry gets the
+/// message `stash_save` would have written ("On <branch>: <message>").
+fn set_stash_message(
+    repo: &git2::Repository,
+    signature: &git2::Signature,
+    oid: git2::Oid,
+    message: &str,
+) -> Result<(), git2::Error> {
+    let branch = repo
+        .head()
+        .ok()
+        .and_then(|head| head.shorthand().map(str::to_string))
+        .unwrap_or_else(|| "(no branch)".to_string());
+    let mut reflog = repo.reflog("refs/stash")?;
+    reflog.remove(0, false)?;
+    reflog.append(oid, signature, Some(&format!("On {}: {}", branch, message)))?;
+    reflog.write()
+}
+
+/// Drop the stashed changes to `paths` from the working tree, and from the
+/// index unless `keep_index`.
+fn reset_paths(
+    repo: &git2::Repository,
+    paths: &[String],
+    keep_index: bool,
+) -> Result<(), git2::Error> {
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    for path in paths {
+        checkout.path(path);
+    }
+    if keep_index {
+        return repo.checkout_index(None, Some(&mut checkout));
+    }
+
+    // Files added to the index exist only in the stash now
+    let head = repo.head()?.peel_to_commit()?;
+    let mut diff_opts = git2::DiffOptions::new();
+    for path in paths {
+        diff_opts.pathspec(path);
+    }
+    let added: Vec<PathBuf> = repo
+        .diff_tree_to_index(Some(&head.tree()?), None, Some(&mut diff_opts))?
+        .deltas()
+        .filter(|delta| delta.status() == git2::Delta::Added)
+        .filter_map(|delta| delta.new_file().path().map(Path::to_path_buf))
+        .collect();
+
+    repo.reset_default(Some(head.as_object()), paths)?;
+    repo.checkout_head(Some(&mut checkout))?;
+    if let Some(workdir) = repo.workdir() {
+        for path in added {
+            let _ = std::fs::remove_file(workdir.join(path));
+        }
+    }
+    Ok(())
+}
+
+fn map_stash_error(e: git2::Error, index: u32) -> GitError {
+    if e.message().contains("does not exist") {
+        GitError::StashNotFound(index)
+    } else {
+        GitError::from(e)
+    }
+}
+
+/// Apply a stash, also restoring what was staged when `restore_index` is set
+/// (`git stash apply --index`).
+pub fn stash_apply_internal(
+    repo: &mut git2::Repository,
+    index: u32,
+    restore_index: bool,
+) -> Result<(), GitError> {
+    let mut options = git2::StashApplyOptions::new();
+    if restore_index {
+        options.reinstantiate_index();
+    }
+    repo.stash_apply(index as usize, Some(&mut options))
+        .map_err(|e| map_stash_error(e, index))
+}
+
+/// Apply a stash and remove it; the stash is kept if applying fails.
+pub fn stash_pop_internal(repo: &mut git2::Repository, index: u32) -> Result<(), GitError> {
+    stash_pop_with_index(repo, index, false)
+}
+
+fn stash_pop_with_index(
+    repo: &mut git2::Repository,
+    index: u32,
+    restore_index: bool,
+) -> Result<(), GitError> {
+    let mut options = git2::StashApplyOptions::new();
+    if restore_index {
+        options.reinstantiate_index();
+    }
+    repo.stash_pop(index as usize, Some(&mut options))
+        .map_err(|e| map_stash_error(e, index))
+}
+
+/// Commit of the stash at `index`.
+fn stash_commit_oid(repo: &mut git2::Repository, index: u32) -> Result<git2::Oid, GitError> {
+    let mut found = None;
+    repo.stash_foreach(|i, _, oid| {
+        if i == index as usize {
+            found = Some(*oid);
+        }
+        found.is_none()
+    })?;
+    found.ok_or(GitError::StashNotFound(index))
+}
+
+/// Changes recorded in a stash (`git stash show -p --include-untracked`):
+/// the working tree against the commit it was made on, then untracked files.
+pub fn stash_diff_internal(
+    repo: &mut git2::Repository,
+    index: u32,
+    context_lines: u32,
+) -> Result<Vec<FileDiff>, GitError> {
+    let oid = stash_commit_oid(repo, index)?;
+    let stash = repo.find_commit(oid)?;
+    let base_tree = stash.parent(0)?.tree()?;
+
+    let mut files = tree_file_diffs(repo, Some(&base_tree), &stash.tree()?, context_lines)?;
+    // The third parent holds untracked (and ignored) files, if any were stashed
+    if stash.parent_count() > 2 {
+        let untracked = stash.parent(2)?.tree()?;
+        files.extend(tree_file_diffs(repo, None, &untracked, context_lines)?);
+    }
+    Ok(files)
+}
+
+/// Create a branch at the commit a stash was made on, check it out and pop
+/// the stash there with its index (`git stash branch`).
+///
+/// The stash is kept if it does not apply cleanly.
+pub fn stash_branch_internal(
+    repo: &mut git2::Repository,
+    index: u32,
+    branch_name: &str,
+) -> Result<(), GitError> {
+    if !git2::Branch::name_is_valid(branch_name)? {
+        return Err(GitError::InvalidBranchName(branch_name.to_string()));
+    }
+    if repo
+        .find_branch(branch_name, git2::BranchType::Local)
+        .is_ok()
+    {
+        return Err(GitError::BranchAlreadyExists(branch_name.to_string()));
+    }
+
+    let oid = stash_commit_oid(repo, index)?;
+    let base = repo.find_commit(oid)?.parent(0)?;
+
+    // Check out before moving HEAD so a refused checkout changes nothing
+    repo.checkout_tree(
+        base.as_object(),
+        Some(git2::build::CheckoutBuilder::new().safe()),
+    )
+    .map_err(|e| {
+        if e.message().contains("conflict") || e.message().contains("overwrite") {
+            GitError::DirtyWorkingDirectory
+        } else {
+            GitError::from(e)
+        }
+    })?;
+    repo.branch(branch_name, &base, false)?;
+    repo.set_head(&format!("refs/heads/{}", branch_name))?;
+    drop(base);
+
+    stash_pop_with_index(repo, index, true)
+}
+
 /// Save current changes to stash.
+///
+/// `paths` limits the stash to those pathspecs (not combinable with
+/// untracked files); `keep_index` leaves staged changes in place;
+/// `include_ignored` stashes ignored files as well as untracked ones (`--all`).
 #[tauri::command]
 #[specta::specta]
 pub async fn stash_save(
     message: Option<String>,
     include_untracked: bool,
+    keep_index: Option<bool>,
+    include_ignored: Option<bool>,
+    paths: Option<Vec<String>>,
     state: State<'_, RepositoryState>,
 ) -> Result<String, GitError> {
     let repo_path = state
@@ -60,90 +325,110 @@ pub async fn stash_save(
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
+    let options = StashOptions {
+        paths: paths.unwrap_or_default(),
+        keep_index: keep_index.unwrap_or(false),
+        include_untracked,
+        include_ignored: include_ignored.unwrap_or(false),
+    };
+    record_operation(repo_path.clone(), "Stash changes".to_string(), move || {
         let mut repo = git2::Repository::open(&repo_path)?;
-
-        let signature = repo.signature().map_err(|e| {
-            GitError::SignatureError(format!(
-                "Could not determine stash author. Please configure git: {}",
-                e.message()
-            ))
-        })?;
-
-        let flags = if include_untracked {
-            StashFlags::INCLUDE_UNTRACKED
-        } else {
-            StashFlags::DEFAULT
-        };
-
-        let oid = repo
-            .stash_save(&signature, message.as_deref().unwrap_or("WIP"), Some(flags))
-            .map_err(|e| {
-                if e.message().contains("no local changes to save") {
-                    GitError::NothingToStash
-                } else {
-                    GitError::from(e)
-                }
-            })?;
-
+        let oid = stash_save_with_options(&mut repo, message.as_deref(), &options)?;
         Ok(oid.to_string())
     })
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// Apply a stash by index without removing it.
+///
+/// With `restore_index`, changes that were staged are staged again.
 #[tauri::command]
 #[specta::specta]
-pub async fn stash_apply(index: u32, state: State<'_, RepositoryState>) -> Result<(), GitError> {
+pub async fn stash_apply(
+    index: u32,
+    restore_index: Option<bool>,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
     let repo_path = state
         .get_path()
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
+    record_operation(repo_path.clone(), format!("Apply stash@{{{}}}", index), move || {
         let mut repo = git2::Repository::open(&repo_path)?;
-
-        repo.stash_apply(index as usize, None).map_err(|e| {
-            if e.message().contains("does not exist") {
-                GitError::StashNotFound(index)
-            } else {
-                GitError::from(e)
-            }
-        })?;
-
-        Ok(())
+        stash_apply_internal(&mut repo, index, restore_index.unwrap_or(false))
     })
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// Apply a stash by index and remove it from the stash list.
+///
+/// With `restore_index`, changes that were staged are staged again.
 #[tauri::command]
 #[specta::specta]
-pub async fn stash_pop(index: u32, state: State<'_, RepositoryState>) -> Result<(), GitError> {
+pub async fn stash_pop(
+    index: u32,
+    restore_index: Option<bool>,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
     let repo_path = state
         .get_path()
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
+    record_operation(repo_path.clone(), format!("Pop stash@{{{}}}", index), move || {
         let mut repo = git2::Repository::open(&repo_path)?;
+        stash_pop_with_index(&mut repo, index, restore_index.unwrap_or(false))
+    })
+    .await
+}
 
-        repo.stash_pop(index as usize, None).map_err(|e| {
-            if e.message().contains("does not exist") {
-                GitError::StashNotFound(index)
-            } else {
-                GitError::from(e)
-            }
-        })?;
+/// Get the changes in a stash as per-file diffs, untracked files included.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_stash_diff(
+    index: u32,
+    context_lines: Option<u32>,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<FileDiff>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-        Ok(())
+    tokio::task::spawn_blocking(move || {
+        let mut repo = git2::Repository::open(&repo_path)?;
+        stash_diff_internal(&mut repo, index, context_lines.unwrap_or(3))
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
+/// Create and check out a branch from the commit a stash was made on, then
+/// pop the stash onto it.
+#[tauri::command]
+#[specta::specta]
+pub async fn stash_branch(
+    index: u32,
+    branch_name: String,
+    state: State<'_, RepositoryState>,
+) -> Result<(), GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    record_operation(
+        repo_path.clone(),
+        format!("Branch '{}' from stash@{{{}}}", branch_name, index),
+        move || {
+            let mut repo = git2::Repository::open(&repo_path)?;
+            stash_branch_internal(&mut repo, index, &branch_name)
+        },
+    )
+    .await
+}
+
 /// Drop a stash by index without applying it.
 #[tauri::command]
 #[specta::specta]
@@ -153,19 +438,162 @@ pub async fn stash_drop(index: u32, state: State<'_, RepositoryState>) -> Result
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
+    record_operation(repo_path.clone(), format!("Drop stash@{{{}}}", index), move || {
         let mut repo = git2::Repository::open(&repo_path)?;
 
-        repo.stash_drop(index as usize).map_err(|e| {
-            if e.message().contains("does not exist") {
-                GitError::StashNotFound(index)
-            } else {
-                GitError::from(e)
-            }
-        })?;
+        repo.stash_drop(index as usize)
+            .map_err(|e| map_stash_error(e, index))?;
 
         Ok(())
     })
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{self, commit_index, stage_file};
+    use tempfile::TempDir;
+
+    fn write(repo: &git2::Repository, name: &str, content: &str) {
+        std::fs::write(repo.workdir().unwrap().join(name), content).unwrap();
+    }
+
+    /// A repository with `a.txt` and `b.txt` committed.
+    fn setup_repo() -> (TempDir, git2::Repository) {
+        let (dir, repo) = test_support::setup_repo();
+        stage_file(&repo, "a.txt", "a\n");
+        stage_file(&repo, "b.txt", "b\n");
+        commit_index(&repo, "Initial");
+        (dir, repo)
+    }
+
+    fn read(repo: &git2::Repository, name: &str) -> String {
+        std::fs::read_to_string(repo.workdir().unwrap().join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_partial_stash_and_diff() {
+        let (_dir, mut repo) = setup_repo();
+        write(&repo, "a.txt", "a changed\n");
+        write(&repo, "b.txt", "b changed\n");
+        write(&repo, "new.txt", "new\n");
+
+        let mut options = StashOptions {
+            paths: vec!["a.txt".to_string(), "new.txt".to_string()],
+            include_untracked: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            stash_save_with_options(&mut repo, Some("only a"), &options),
+            Err(GitError::UnsupportedOperation(_))
+        ));
+        options.include_untracked = false;
+        stage_file(&repo, "new.txt", "new\n");
+        stash_save_with_options(&mut repo, Some("only a"), &options).unwrap();
+        assert_eq!(read(&repo, "a.txt"), "a\n");
+        assert_eq!(read(&repo, "b.txt"), "b changed\n");
+        assert!(!repo.workdir().unwrap().join("new.txt").exists());
+        assert!(repo.status_file(Path::new("new.txt")).is_err());
+
+        let mut messages = Vec::new();
+        repo.stash_foreach(|_, message, _| {
+            messages.push(message.to_string());
+            true
+        })
+        .unwrap();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        assert_eq!(messages, vec![format!("On {}: only a", branch)]);
+
+        let diff = stash_diff_internal(&mut repo, 0, 3).unwrap();
+        let files: Vec<_> = diff
+            .iter()
+            .map(|f| (f.path.as_str(), f.old_content.as_str(), f.new_content.as_str()))
+            .collect();
+        assert_eq!(
+            files,
+            vec![("a.txt", "a\n", "a changed\n"), ("new.txt", "", "new\n")]
+        );
+        assert_eq!(diff[0].hunks.len(), 1);
+        assert!(matches!(
+            stash_diff_internal(&mut repo, 1, 3),
+            Err(GitError::StashNotFound(1))
+        ));
+    }
+
+    #[test]
+    fn test_stash_diff_includes_untracked_files() {
+        let (_dir, mut repo) = setup_repo();
+        write(&repo, "b.txt", "b changed\n");
+        write(&repo, "new.txt", "new\n");
+        stash_save_internal(&mut repo, None, true).unwrap();
+
+        let paths: Vec<_> = stash_diff_internal(&mut repo, 0, 3)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.path)
+            .collect();
+        assert_eq!(paths, vec!["b.txt", "new.txt"]);
+    }
+
+    #[test]
+    fn test_keep_index_and_apply_with_index() {
+        let (_dir, mut repo) = setup_repo();
+        stage_file(&repo, "a.txt", "a staged\n");
+        write(&repo, "b.txt", "b unstaged\n");
+
+        let options = StashOptions {
+            keep_index: true,
+            ..Default::default()
+        };
+        stash_save_with_options(&mut repo, None, &options).unwrap();
+        // Staged changes stay, unstaged ones are stashed
+        assert_eq!(read(&repo, "a.txt"), "a staged\n");
+        assert_eq!(read(&repo, "b.txt"), "b\n");
+
+        {
+            let head = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.reset(head.as_object(), git2::ResetType::Hard, None)
+                .unwrap();
+        }
+        stash_apply_internal(&mut repo, 0, true).unwrap();
+        let status = repo.status_file(Path::new("a.txt")).unwrap();
+        assert!(status.contains(git2::Status::INDEX_MODIFIED));
+        let status = repo.status_file(Path::new("b.txt")).unwrap();
+        assert_eq!(status, git2::Status::WT_MODIFIED);
+    }
+
+    #[test]
+    fn test_stash_branch_checks_out_base_and_pops() {
+        let (_dir, mut repo) = setup_repo();
+        let base = repo.head().unwrap().target().unwrap();
+        write(&repo, "a.txt", "stashed\n");
+        stash_save_internal(&mut repo, Some("work"), false).unwrap();
+
+        // Move on so the stash no longer applies on top of HEAD's parent
+        stage_file(&repo, "a.txt", "later\n");
+        {
+            let tree = repo
+                .find_tree(repo.index().unwrap().write_tree().unwrap())
+                .unwrap();
+            let sig = repo.signature().unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "Later", &tree, &[&parent])
+                .unwrap();
+        }
+
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        assert!(matches!(
+            stash_branch_internal(&mut repo, 0, &current),
+            Err(GitError::BranchAlreadyExists(_))
+        ));
+        stash_branch_internal(&mut repo, 0, "from-stash").unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("from-stash"));
+        assert_eq!(repo.head().unwrap().target(), Some(base));
+        assert_eq!(read(&repo, "a.txt"), "stashed\n");
+        assert!(matches!(
+            stash_commit_oid(&mut repo, 0),
+            Err(GitError::StashNotFound(0))
+        ));
+    }
 }
@@ -0,0 +1,672 @@
+//! Submodule operations: list, init, update, sync and branch tracking.
+//!
+//! Also describes submodule pointer changes ("old sha -> new sha (N commits)")
+//! for the staging area and commit diffs.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use tauri::State;
+use tauri::ipc::Channel;
+
+use crate::git::cancellation::{CancellationToken, OperationRegistry};
+use crate::git::credentials::CredentialSession;
+use crate::git::error::GitError;
+use crate::git::network::{NetworkSettings, create_certificate_check};
+use crate::git::repository::RepositoryState;
+
+/// Where a submodule's checkout stands relative to the superproject.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SubmoduleState {
+    /// Not initialized or not checked out
+    Uninitialized,
+    /// Checked out at the recorded commit with no local changes
+    UpToDate,
+    /// Checked out at a different commit than the one recorded in the index
+    Modified,
+    /// At the recorded commit, but with uncommitted or untracked changes inside
+    Dirty,
+}
+
+/// A submodule of the open repository.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmoduleInfo {
+    pub name: String,
+    pub path: String,
+    pub url: Option<String>,
+    /// Branch tracked by `update --remote` (`submodule.<name>.branch`)
+    pub branch: Option<String>,
+    /// Commit recorded in HEAD
+    pub head_oid: Option<String>,
+    /// Commit recorded in the index
+    pub index_oid: Option<String>,
+    /// Commit checked out in the submodule
+    pub workdir_oid: Option<String>,
+    pub state: SubmoduleState,
+}
+
+/// A change of the commit a submodule points to.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmoduleChange {
+    pub old_oid: Option<String>,
+    pub new_oid: Option<String>,
+    /// Commits in the new pointer that the old one lacks (when both are available locally)
+    pub commits_ahead: Option<u32>,
+    /// Commits in the old pointer that the new one lacks
+    pub commits_behind: Option<u32>,
+    /// The submodule has uncommitted or untracked changes
+    pub dirty: bool,
+    /// One-line description, e.g. "1a2b3c4 -> 5d6e7f8 (3 commits)"
+    pub summary: String,
+}
+
+/// Progress events for submodule updates.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", tag = "event", content = "data")]
+pub enum SubmoduleProgress {
+    Updating {
+        path: String,
+    },
+    Transferring {
+        path: String,
+        current: u32,
+        total: u32,
+        bytes: u32,
+    },
+    Updated {
+        path: String,
+    },
+    /// The update was cancelled through `cancel_operation`
+    Cancelled,
+}
+
+const GITLINK_MODE: i32 = 0o160000;
+
+fn short(oid: git2::Oid) -> String {
+    format!("{:.7}", oid)
+}
+
+fn submodule_state(status: git2::SubmoduleStatus) -> SubmoduleState {
+    use git2::SubmoduleStatus as S;
+
+    if status.contains(S::WD_UNINITIALIZED) || !status.contains(S::IN_WD) {
+        SubmoduleState::Uninitialized
+    } else if status.intersects(S::WD_MODIFIED | S::INDEX_ADDED | S::INDEX_MODIFIED) {
+        SubmoduleState::Modified
+    } else if status.intersects(S::WD_INDEX_MODIFIED | S::WD_WD_MODIFIED | S::WD_UNTRACKED) {
+        SubmoduleState::Dirty
+    } else {
+        SubmoduleState::UpToDate
+    }
+}
+
+fn submodule_info(repo: &git2::Repository, sub: &git2::Submodule) -> SubmoduleInfo {
+    let name = sub.name().unwrap_or("").to_string();
+    let status = repo
+        .submodule_status(&name, git2::SubmoduleIgnore::None)
+        .unwrap_or(git2::SubmoduleStatus::empty());
+
+    SubmoduleInfo {
+        path: sub.path().to_string_lossy().to_string(),
+        url: sub.url().map(str::to_string),
+        branch: sub.branch().map(str::to_string),
+        head_oid: sub.head_id().map(|id| id.to_string()),
+        index_oid: sub.index_id().map(|id| id.to_string()),
+        workdir_oid: sub.workdir_id().map(|id| id.to_string()),
+        state: submodule_state(status),
+        name,
+    }
+}
+
+/// Submodules whose path is in `paths`, or all of them.
+fn select_submodules<'r>(
+    repo: &'r git2::Repository,
+    paths: Option<&[String]>,
+) -> Result<Vec<git2::Submodule<'r>>, GitError> {
+    let submodules = repo.submodules()?;
+    let Some(paths) = paths else {
+        return Ok(submodules);
+    };
+
+    if let Some(missing) = paths
+        .iter()
+        .find(|p| !submodules.iter().any(|s| s.path() == Path::new(p)))
+    {
+        return Err(GitError::SubmoduleNotFound(missing.clone()));
+    }
+
+    Ok(submodules
+        .into_iter()
+        .filter(|s| paths.iter().any(|p| s.path() == Path::new(p)))
+        .collect())
+}
+
+pub fn list_submodules_internal(repo: &git2::Repository) -> Result<Vec<SubmoduleInfo>, GitError> {
+    Ok(repo
+        .submodules()?
+        .iter()
+        .map(|sub| submodule_info(repo, sub))
+        .collect())
+}
+
+/// Copy submodule URLs from `.gitmodules` into `.git/config` (`git submodule init`).
+pub fn init_submodules_internal(
+    repo: &git2::Repository,
+    paths: Option<&[String]>,
+) -> Result<(), GitError> {
+    for mut sub in select_submodules(repo, paths)? {
+        sub.init(false)?;
+    }
+    Ok(())
+}
+
+/// Clone missing submodules and check out the recorded commits
+/// (`git submodule update --init [--recursive]`).
+pub fn update_submodules_internal(
+    repo: &git2::Repository,
+    paths: Option<&[String]>,
+    recursive: bool,
+    on_progress: &(dyn Fn(SubmoduleProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<(), GitError> {
+    update_submodules_in(repo, paths, recursive, "", on_progress, cancel)
+}
+
+fn update_submodules_in(
+    repo: &git2::Repository,
+    paths: Option<&[String]>,
+    recursive: bool,
+    prefix: &str,
+    on_progress: &(dyn Fn(SubmoduleProgress) + Sync),
+    cancel: &CancellationToken,
+) -> Result<(), GitError> {
+    for mut sub in select_submodules(repo, paths)? {
+        cancel.check()?;
+        let path = format!("{}{}", prefix, sub.path().to_string_lossy());
+        on_progress(SubmoduleProgress::Updating { path: path.clone() });
+
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.transfer_progress(|stats| {
+            on_progress(SubmoduleProgress::Transferring {
+                path: path.clone(),
+                current: stats.received_objects() as u32,
+                total: stats.total_objects() as u32,
+                bytes: stats.received_bytes() as u32,
+            });
+            !cancel.is_cancelled()
+        });
+        let credentials = CredentialSession::new();
+        callbacks.credentials(credentials.callback());
+        callbacks.certificate_check(create_certificate_check(sub.url().unwrap_or("")));
+
+        let network = NetworkSettings::load(Some(repo));
+        network.apply_ca_info()?;
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+        fetch_options.proxy_options(network.proxy_options());
+        let mut options = git2::SubmoduleUpdateOptions::new();
+        options.fetch(fetch_options);
+
+        let result = sub.update(true, Some(&mut options));
+        credentials.finish(&result);
+        if let Err(e) = result {
+            cancel.check()?;
+            return Err(GitError::OperationFailed(format!(
+                "{}: {}",
+                path,
+                e.message()
+            )));
+        }
+        drop(options);
+
+        if recursive {
+            let sub_repo = sub.open()?;
+            update_submodules_in(
+                &sub_repo,
+                None,
+                true,
+                &format!("{}/", path),
+                on_progress,
+                cancel,
+            )?;
+        }
+
+        on_progress(SubmoduleProgress::Updated { path });
+    }
+    Ok(())
+}
+
+/// Propagate URL changes from `.gitmodules` to `.git/config` and the
+/// submodules' `origin` remotes (`git submodule sync [--recursive]`).
+pub fn sync_submodules_internal(repo: &git2::Repository, recursive: bool) -> Result<(), GitError> {
+    for mut sub in repo.submodules()? {
+        sub.sync()?;
+        if recursive && let Ok(sub_repo) = sub.open() {
+            sync_submodules_internal(&sub_repo, true)?;
+        }
+    }
+    Ok(())
+}
+
+/// Set or clear the branch a submodule tracks in `.gitmodules`
+/// (`git submodule set-branch`).
+pub fn set_submodule_branch_internal(
+    repo: &git2::Repository,
+    path: &str,
+    branch: Option<&str>,
+) -> Result<SubmoduleInfo, GitError> {
+    let paths = [path.to_string()];
+    let sub = select_submodules(repo, Some(&paths))?
+        .pop()
+        .ok_or_else(|| GitError::SubmoduleNotFound(path.to_string()))?;
+    let name = sub.name().unwrap_or(path).to_string();
+
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::OperationFailed("Repository has no working tree".to_string()))?;
+    let mut gitmodules = git2::Config::open(&workdir.join(".gitmodules"))?;
+    let key = format!("submodule.{}.branch", name);
+    match branch {
+        Some(branch) => gitmodules.set_str(&key, branch)?,
+        None => match gitmodules.remove(&key) {
+            Ok(()) => {}
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+
+    let sub = repo.find_submodule(&name)?;
+    Ok(submodule_info(repo, &sub))
+}
+
+/// Describe a submodule pointer moving from `old` to `new`.
+///
+/// Commit counts are only available when the submodule is checked out and
+/// has both commits.
+pub(crate) fn describe_submodule_change(
+    repo: &git2::Repository,
+    path: &str,
+    old: Option<git2::Oid>,
+    new: Option<git2::Oid>,
+    dirty: bool,
+) -> SubmoduleChange {
+    let counts = match (old, new) {
+        (Some(old), Some(new)) if old != new => repo
+            .find_submodule(path)
+            .and_then(|sub| sub.open())
+            .and_then(|sub_repo| sub_repo.graph_ahead_behind(new, old))
+            .ok(),
+        _ => None,
+    };
+
+    let mut summary = match (old, new) {
+        (Some(old), Some(new)) if old == new => short(new),
+        (Some(old), Some(new)) => {
+            let commits = match counts {
+                Some((ahead, 0)) => format!(" ({})", plural_commits(ahead)),
+                Some((0, behind)) => format!(" ({} back)", plural_commits(behind)),
+                Some((ahead, behind)) => format!(" ({} ahead, {} behind)", ahead, behind),
+                None => String::new(),
+            };
+            format!("{} -> {}{}", short(old), short(new), commits)
+        }
+        (None, Some(new)) => format!("added at {}", short(new)),
+        (Some(old), None) => format!("removed (was {})", short(old)),
+        (None, None) => String::new(),
+    };
+    if dirty {
+        summary.push_str(if summary.is_empty() {
+            "modified content"
+        } else {
+            " (modified content)"
+        });
+    }
+
+    SubmoduleChange {
+        old_oid: old.map(|id| id.to_string()),
+        new_oid: new.map(|id| id.to_string()),
+        commits_ahead: counts.map(|(ahead, _)| ahead as u32),
+        commits_behind: counts.map(|(_, behind)| behind as u32),
+        dirty,
+        summary,
+    }
+}
+
+fn plural_commits(count: usize) -> String {
+    if count == 1 {
+        "1 commit".to_string()
+    } else {
+        format!("{} commits", count)
+    }
+}
+
+/// Commit a gitlink entry in `tree` points to.
+pub(crate) fn gitlink_in_tree(tree: &git2::Tree, path: &str) -> Option<git2::Oid> {
+    tree.get_path(Path::new(path))
+        .ok()
+        .filter(|entry| entry.filemode() == GITLINK_MODE)
+        .map(|entry| entry.id())
+}
+
+fn gitlink_in_index(repo: &git2::Repository, path: &str) -> Option<git2::Oid> {
+    repo.index()
+        .ok()?
+        .get_path(Path::new(path), 0)
+        .filter(|entry| entry.mode == GITLINK_MODE as u32)
+        .map(|entry| entry.id)
+}
+
+/// Staged pointer change of a submodule (HEAD -> index), if `path` is one.
+pub(crate) fn staged_submodule_change(
+    repo: &git2::Repository,
+    path: &str,
+) -> Option<SubmoduleChange> {
+    let head = repo
+        .head()
+        .ok()
+        .and_then(|h| h.peel_to_tree().ok())
+        .and_then(|tree| gitlink_in_tree(&tree, path));
+    let index = gitlink_in_index(repo, path);
+    if head.is_none() && index.is_none() {
+        return None;
+    }
+    Some(describe_submodule_change(repo, path, head, index, false))
+}
+
+/// Unstaged change of a submodule (index -> checked-out commit), if `path` is one.
+pub(crate) fn unstaged_submodule_change(
+    repo: &git2::Repository,
+    path: &str,
+) -> Option<SubmoduleChange> {
+    let sub = repo.find_submodule(path).ok()?;
+    let index = gitlink_in_index(repo, path).or_else(|| sub.index_id());
+    let status = repo
+        .submodule_status(sub.name()?, git2::SubmoduleIgnore::None)
+        .unwrap_or(git2::SubmoduleStatus::empty());
+    let dirty = status.intersects(
+        git2::SubmoduleStatus::WD_INDEX_MODIFIED
+            | git2::SubmoduleStatus::WD_WD_MODIFIED
+            | git2::SubmoduleStatus::WD_UNTRACKED,
+    );
+    Some(describe_submodule_change(
+        repo,
+        path,
+        index,
+        sub.workdir_id(),
+        dirty,
+    ))
+}
+
+/// Text git shows for a submodule side of a diff.
+pub(crate) fn subproject_text(oid: Option<&str>, dirty: bool) -> String {
+    match oid {
+        Some(oid) if dirty => format!("Subproject commit {}-dirty\n", oid),
+        Some(oid) => format!("Subproject commit {}\n", oid),
+        None => String::new(),
+    }
+}
+
+/// List the submodules of the open repository.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_submodules(
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<SubmoduleInfo>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        list_submodules_internal(&repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Initialize submodules (all when `paths` is `None`).
+#[tauri::command]
+#[specta::specta]
+pub async fn init_submodules(
+    paths: Option<Vec<String>>,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<SubmoduleInfo>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        init_submodules_internal(&repo, paths.as_deref())?;
+        list_submodules_internal(&repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Clone and check out submodules at their recorded commits, initializing them as needed.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_submodules(
+    paths: Option<Vec<String>>,
+    recursive: bool,
+    on_progress: Channel<SubmoduleProgress>,
+    operation_id: Option<String>,
+    state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<Vec<SubmoduleInfo>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+    let operation = registry.register(operation_id)?;
+    let cancel = operation.token();
+
+    let progress_channel = on_progress.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        update_submodules_internal(
+            &repo,
+            paths.as_deref(),
+            recursive,
+            &|progress| {
+                let _ = progress_channel.send(progress);
+            },
+            &cancel,
+        )?;
+        list_submodules_internal(&repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?;
+
+    if let Err(GitError::Cancelled) = result {
+        on_progress.send(SubmoduleProgress::Cancelled).ok();
+    }
+    result
+}
+
+/// Re-apply submodule URLs from `.gitmodules`.
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_submodules(
+    recursive: bool,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<SubmoduleInfo>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        sync_submodules_internal(&repo, recursive)?;
+        list_submodules_internal(&repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Set the branch a submodule tracks; `None` goes back to the remote's default branch.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_submodule_branch(
+    path: String,
+    branch: Option<String>,
+    state: State<'_, RepositoryState>,
+) -> Result<SubmoduleInfo, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        set_submodule_branch_internal(&repo, &path, branch.as_deref())
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_file, commit_index};
+    use tempfile::TempDir;
+
+    /// A library repository and a superproject with it as `libs/lib`.
+    fn setup_repos() -> (TempDir, git2::Repository, git2::Repository) {
+        let dir = TempDir::new().unwrap();
+        let lib = git2::Repository::init(dir.path().join("lib")).unwrap();
+        commit_file(&lib, "lib.txt", "v1", "Update lib.txt");
+
+        let main = git2::Repository::init(dir.path().join("main")).unwrap();
+        commit_file(&main, "README", "main", "Update README");
+        let lib_url = dir.path().join("lib").to_string_lossy().to_string();
+        {
+            let mut sub = main
+                .submodule(&lib_url, Path::new("libs/lib"), true)
+                .unwrap();
+            sub.clone(None).unwrap();
+            sub.add_finalize().unwrap();
+        }
+        commit_index(&main, "Add lib");
+
+        (dir, lib, main)
+    }
+
+    #[test]
+    fn test_list_and_update_cloned_superproject() {
+        let (dir, lib, main) = setup_repos();
+        let lib_head = lib.head().unwrap().target().unwrap();
+
+        let listed = list_submodules_internal(&main).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].path, "libs/lib");
+        assert_eq!(listed[0].state, SubmoduleState::UpToDate);
+        assert_eq!(listed[0].head_oid, Some(lib_head.to_string()));
+
+        let clone = git2::Repository::clone(
+            main.workdir().unwrap().to_str().unwrap(),
+            dir.path().join("clone"),
+        )
+        .unwrap();
+        let listed = list_submodules_internal(&clone).unwrap();
+        assert_eq!(listed[0].state, SubmoduleState::Uninitialized);
+
+        let updated = std::sync::Mutex::new(Vec::new());
+        update_submodules_internal(
+            &clone,
+            None,
+            true,
+            &|progress| {
+                if let SubmoduleProgress::Updated { path } = progress {
+                    updated.lock().unwrap().push(path);
+                }
+            },
+            &CancellationToken::default(),
+        )
+        .unwrap();
+        assert_eq!(updated.into_inner().unwrap(), vec!["libs/lib".to_string()]);
+
+        let listed = list_submodules_internal(&clone).unwrap();
+        assert_eq!(listed[0].state, SubmoduleState::UpToDate);
+        assert_eq!(listed[0].workdir_oid, Some(lib_head.to_string()));
+        assert!(clone.workdir().unwrap().join("libs/lib/lib.txt").exists());
+
+        assert!(matches!(
+            update_submodules_internal(
+                &clone,
+                Some(&["nope".to_string()]),
+                false,
+                &|_| {},
+                &CancellationToken::default()
+            ),
+            Err(GitError::SubmoduleNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_pointer_change_is_described_with_commit_count() {
+        let (_dir, lib, main) = setup_repos();
+        let old = lib.head().unwrap().target().unwrap();
+        commit_file(&lib, "lib.txt", "v2", "Update lib.txt");
+        let new = commit_file(&lib, "lib.txt", "v3", "Update lib.txt");
+
+        // Move the submodule checkout forward
+        let sub_repo = main.find_submodule("libs/lib").unwrap().open().unwrap();
+        sub_repo
+            .find_remote("origin")
+            .unwrap()
+            .fetch(&["+refs/heads/*:refs/remotes/origin/*"], None, None)
+            .unwrap();
+        sub_repo.set_head_detached(new).unwrap();
+        sub_repo
+            .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+
+        let change = unstaged_submodule_change(&main, "libs/lib").unwrap();
+        assert_eq!(
+            change.summary,
+            format!("{} -> {} (2 commits)", short(old), short(new))
+        );
+        assert_eq!(change.commits_ahead, Some(2));
+        assert!(!change.dirty);
+        assert_eq!(
+            list_submodules_internal(&main).unwrap()[0].state,
+            SubmoduleState::Modified
+        );
+
+        let mut index = main.index().unwrap();
+        index.add_path(Path::new("libs/lib")).unwrap();
+        index.write().unwrap();
+        let staged = staged_submodule_change(&main, "libs/lib").unwrap();
+        assert_eq!(staged.new_oid, Some(new.to_string()));
+        assert_eq!(staged.commits_ahead, Some(2));
+        assert!(staged_submodule_change(&main, "README").is_none());
+    }
+
+    #[test]
+    fn test_set_branch_and_sync() {
+        let (dir, _lib, mut main) = setup_repos();
+
+        let info = set_submodule_branch_internal(&main, "libs/lib", Some("develop")).unwrap();
+        assert_eq!(info.branch.as_deref(), Some("develop"));
+        let info = set_submodule_branch_internal(&main, "libs/lib", None).unwrap();
+        assert_eq!(info.branch, None);
+
+        // Point .gitmodules at a moved URL and sync it into the submodule's origin
+        let moved = dir.path().join("moved").to_string_lossy().to_string();
+        main.submodule_set_url("libs/lib", &moved).unwrap();
+        sync_submodules_internal(&main, true).unwrap();
+        let sub_repo = main.find_submodule("libs/lib").unwrap().open().unwrap();
+        assert_eq!(
+            sub_repo.find_remote("origin").unwrap().url(),
+            Some(moved.as_str())
+        );
+    }
+}
@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::cmp::Ordering;
 use tauri::State;
+use tauri::ipc::Channel;
 
+use crate::git::cancellation::OperationRegistry;
+use crate::git::diff::{DiffLine, DiffLineOrigin};
 use crate::git::error::GitError;
+use crate::git::remote::{PushOptions, SyncProgress, SyncResult, run_push};
 use crate::git::repository::RepositoryState;
+use crate::git::signing::create_tag_signed;
+use crate::git::undo::record_operation;
 
 /// Information about a git tag.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -23,12 +30,398 @@ pub struct TagInfo {
     pub is_annotated: bool,
     /// Creation timestamp in milliseconds since epoch
     pub created_at_ms: f64,
+    /// Semantic version parsed from the name, if it is one (e.g. "v1.2.0-rc.1")
+    pub version: Option<TagVersion>,
 }
 
-/// List all tags in the repository.
+/// A semantic version parsed from a tag name.
+///
+/// An optional `v` prefix and a missing patch number ("v1.2") are accepted;
+/// build metadata after `+` is ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TagVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// Pre-release identifiers after `-` (e.g. "rc.1"), None for a release
+    pub pre_release: Option<String>,
+}
+
+impl TagVersion {
+    pub fn parse(name: &str) -> Option<Self> {
+        let version = name
+            .strip_prefix('v')
+            .or_else(|| name.strip_prefix('V'))
+            .unwrap_or(name);
+        let version = version.split('+').next()?;
+        let (core, pre_release) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version, None),
+        };
+        if pre_release.is_some_and(|pre| {
+            pre.split('.').any(|part| {
+                part.is_empty() || !part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+        }) {
+            return None;
+        }
+
+        let numbers: Vec<&str> = core.split('.').collect();
+        if !(2..=3).contains(&numbers.len())
+            || numbers
+                .iter()
+                .any(|n| n.is_empty() || !n.chars().all(|c| c.is_ascii_digit()))
+        {
+            return None;
+        }
+        Some(Self {
+            major: numbers[0].parse().ok()?,
+            minor: numbers[1].parse().ok()?,
+            patch: numbers.get(2).map_or(Some(0), |n| n.parse().ok())?,
+            pre_release: pre_release.map(str::to_string),
+        })
+    }
+}
+
+impl Ord for TagVersion {
+    /// Semver precedence: pre-releases sort before their release, and
+    /// pre-release identifiers compare numerically when both are numbers.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre_release, &other.pre_release) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => {
+                    let mut a = a.split('.');
+                    let mut b = b.split('.');
+                    loop {
+                        match (a.next(), b.next()) {
+                            (None, None) => return Ordering::Equal,
+                            (None, Some(_)) => return Ordering::Less,
+                            (Some(_), None) => return Ordering::Greater,
+                            (Some(x), Some(y)) => {
+                                let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+                                    (Ok(x), Ok(y)) => x.cmp(&y),
+                                    (Ok(_), Err(_)) => Ordering::Less,
+                                    (Err(_), Ok(_)) => Ordering::Greater,
+                                    (Err(_), Err(_)) => x.cmp(y),
+                                };
+                                if ordering != Ordering::Equal {
+                                    return ordering;
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+    }
+}
+
+impl PartialOrd for TagVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Order of `list_tags`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TagSort {
+    /// Newest first by tagger or commit date
+    #[default]
+    Date,
+    /// Highest semantic version first; other tags follow, newest first
+    Semver,
+}
+
+/// How `list_tag_groups` buckets versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TagGrouping {
+    /// One group per major version ("1.x")
+    #[default]
+    Major,
+    /// One group per minor version ("1.2.x")
+    Minor,
+}
+
+/// Tags sharing a major or minor version, highest version first.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TagGroup {
+    /// "1.x", "1.2.x", or "Other" for tags that are not versions
+    pub label: String,
+    pub tags: Vec<TagInfo>,
+    /// How many of the tags are pre-releases
+    pub pre_release_count: u32,
+}
+
+/// The nearest tag reachable from a commit (`git describe --tags`).
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TagDescription {
+    pub tag: String,
+    /// Commits between the tag and the described commit
+    pub distance: u32,
+    /// `git describe` output, e.g. "v1.2.0-3-gabc1234" or "v1.2.0"
+    pub description: String,
+}
+
+/// Two tags side by side, for release comparisons.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TagComparison {
+    pub from: TagInfo,
+    pub to: TagInfo,
+    /// Commits in `to` that are not in `from`
+    pub commits_ahead: u32,
+    /// Commits in `from` that are not in `to`
+    pub commits_behind: u32,
+    /// Milliseconds from `from`'s creation to `to`'s
+    pub time_between_ms: f64,
+    /// Whether the tagger differs
+    pub tagger_changed: bool,
+    /// Line diff of the tag messages (empty when they are identical)
+    pub message_diff: Vec<DiffLine>,
+}
+
+/// Read one tag by name.
+fn tag_info(repo: &git2::Repository, name: &str) -> Result<TagInfo, GitError> {
+    let ref_name = format!("refs/tags/{}", name);
+    let reference = repo
+        .find_reference(&ref_name)
+        .map_err(|_| GitError::TagNotFound(name.to_string()))?;
+    let resolved = reference.resolve()?;
+    let oid = resolved
+        .target()
+        .ok_or_else(|| GitError::Internal(format!("Tag {} has no target", name)))?;
+    let obj = repo.find_object(oid, None)?;
+    let version = TagVersion::parse(name);
+
+    let tag_info = if let Some(tag) = obj.as_tag() {
+        let target = tag.target()?.peel_to_commit()?;
+        let tagger_info = tag.tagger().map(|sig: git2::Signature| {
+            format!(
+                "{} <{}>",
+                sig.name().unwrap_or(""),
+                sig.email().unwrap_or("")
+            )
+        });
+        TagInfo {
+            name: name.to_string(),
+            oid: oid.to_string(),
+            target_oid: target.id().to_string(),
+            message: tag.message().map(|m: &str| m.trim().to_string()),
+            tagger: tagger_info,
+            is_annotated: true,
+            created_at_ms: tag
+                .tagger()
+                .map(|sig| (sig.when().seconds() as f64) * 1000.0)
+                .unwrap_or_else(|| (target.time().seconds() as f64) * 1000.0),
+            version,
+        }
+    } else {
+        let commit = obj.peel_to_commit()?;
+        TagInfo {
+            name: name.to_string(),
+            oid: commit.id().to_string(),
+            target_oid: commit.id().to_string(),
+            message: None,
+            tagger: None,
+            is_annotated: false,
+            created_at_ms: (commit.time().seconds() as f64) * 1000.0,
+            version,
+        }
+    };
+    Ok(tag_info)
+}
+
+fn newest_first(a: &TagInfo, b: &TagInfo) -> Ordering {
+    b.created_at_ms
+        .partial_cmp(&a.created_at_ms)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Highest version first, then tags that are not versions, newest first.
+fn semver_order(a: &TagInfo, b: &TagInfo) -> Ordering {
+    match (&a.version, &b.version) {
+        (Some(x), Some(y)) => y.cmp(x).then_with(|| a.name.cmp(&b.name)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => newest_first(a, b),
+    }
+}
+
+pub fn list_tags_internal(
+    repo: &git2::Repository,
+    sort: TagSort,
+) -> Result<Vec<TagInfo>, GitError> {
+    let tag_names = repo.tag_names(None)?;
+    let mut tags = Vec::new();
+    for name in tag_names.iter().flatten() {
+        tags.push(tag_info(repo, name)?);
+    }
+
+    match sort {
+        TagSort::Date => tags.sort_by(newest_first),
+        TagSort::Semver => tags.sort_by(semver_order),
+    }
+    Ok(tags)
+}
+
+/// Bucket tags by major or minor version, highest first; "Other" comes last.
+fn group_tags(mut tags: Vec<TagInfo>, grouping: TagGrouping) -> Vec<TagGroup> {
+    tags.sort_by(semver_order);
+    let mut groups: Vec<TagGroup> = Vec::new();
+    for tag in tags {
+        let label = match (&tag.version, grouping) {
+            (Some(v), TagGrouping::Major) => format!("{}.x", v.major),
+            (Some(v), TagGrouping::Minor) => format!("{}.{}.x", v.major, v.minor),
+            (None, _) => "Other".to_string(),
+        };
+        let pre_release = tag
+            .version
+            .as_ref()
+            .is_some_and(|v| v.pre_release.is_some());
+        // Sorted, so a group's tags are adjacent
+        let group = match groups.last_mut() {
+            Some(group) if group.label == label => group,
+            _ => {
+                groups.push(TagGroup {
+                    label,
+                    tags: Vec::new(),
+                    pre_release_count: 0,
+                });
+                groups.last_mut().expect("just pushed")
+            }
+        };
+        group.tags.push(tag);
+        group.pre_release_count += pre_release as u32;
+    }
+    groups
+}
+
+fn parse_oid(oid: &str) -> Result<git2::Oid, GitError> {
+    git2::Oid::from_str(oid)
+        .map_err(|e| GitError::OperationFailed(format!("Invalid OID '{}': {}", oid, e)))
+}
+
+/// Names of tags whose commit contains `oid` (`git tag --contains`), by version.
+pub fn tags_containing_internal(
+    repo: &git2::Repository,
+    oid: git2::Oid,
+) -> Result<Vec<String>, GitError> {
+    let mut tags = list_tags_internal(repo, TagSort::Semver)?;
+    tags.retain(|tag| {
+        git2::Oid::from_str(&tag.target_oid).is_ok_and(|target| {
+            target == oid || repo.graph_descendant_of(target, oid).unwrap_or(false)
+        })
+    });
+    Ok(tags.into_iter().map(|tag| tag.name).collect())
+}
+
+/// Nearest tag reachable from `oid`, or None if no tag is.
+pub fn describe_internal(
+    repo: &git2::Repository,
+    oid: git2::Oid,
+) -> Result<Option<TagDescription>, GitError> {
+    let commit = repo.find_commit(oid)?;
+    let mut options = git2::DescribeOptions::new();
+    options.describe_tags();
+    let describe = match commit.as_object().describe(&options) {
+        Ok(describe) => describe,
+        // libgit2 reports "no tag reachable" as a describe-class error
+        Err(e) if e.class() == git2::ErrorClass::Describe => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    // An abbreviation size of 0 prints only the tag name (`--abbrev=0`)
+    let tag = describe.format(Some(git2::DescribeFormatOptions::new().abbreviated_size(0)))?;
+    let description = describe.format(None)?;
+    let tag_commit = repo
+        .revparse_single(&format!("refs/tags/{}", tag))?
+        .peel_to_commit()?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(oid)?;
+    revwalk.hide(tag_commit.id())?;
+    let distance = revwalk.count() as u32;
+
+    Ok(Some(TagDescription {
+        tag,
+        distance,
+        description,
+    }))
+}
+
+/// Line diff of two tag messages.
+fn message_diff(old: &str, new: &str) -> Result<Vec<DiffLine>, GitError> {
+    if old == new {
+        return Ok(Vec::new());
+    }
+    let with_newline = |text: &str| {
+        if text.is_empty() || text.ends_with('\n') {
+            text.to_string()
+        } else {
+            format!("{}\n", text)
+        }
+    };
+    let (old, new) = (with_newline(old), with_newline(new));
+    let patch = git2::Patch::from_buffers(old.as_bytes(), None, new.as_bytes(), None, None)?;
+
+    let mut lines = Vec::new();
+    for hunk in 0..patch.num_hunks() {
+        for line in 0..patch.num_lines_in_hunk(hunk)? {
+            let line = patch.line_in_hunk(hunk, line)?;
+            lines.push(DiffLine {
+                origin: match line.origin() {
+                    '+' => DiffLineOrigin::Addition,
+                    '-' => DiffLineOrigin::Deletion,
+                    _ => DiffLineOrigin::Context,
+                },
+                old_lineno: line.old_lineno(),
+                new_lineno: line.new_lineno(),
+                content: String::from_utf8_lossy(line.content()).to_string(),
+            });
+        }
+    }
+    Ok(lines)
+}
+
+pub fn compare_tags_internal(
+    repo: &git2::Repository,
+    from: &str,
+    to: &str,
+) -> Result<TagComparison, GitError> {
+    let from = tag_info(repo, from)?;
+    let to = tag_info(repo, to)?;
+    let (commits_ahead, commits_behind) =
+        repo.graph_ahead_behind(parse_oid(&to.target_oid)?, parse_oid(&from.target_oid)?)?;
+    let message_diff = message_diff(
+        from.message.as_deref().unwrap_or(""),
+        to.message.as_deref().unwrap_or(""),
+    )?;
+
+    Ok(TagComparison {
+        commits_ahead: commits_ahead as u32,
+        commits_behind: commits_behind as u32,
+        time_between_ms: to.created_at_ms - from.created_at_ms,
+        tagger_changed: from.tagger != to.tagger,
+        message_diff,
+        from,
+        to,
+    })
+}
+
+/// List all tags in the repository, newest first or by semantic version.
 #[tauri::command]
 #[specta::specta]
-pub async fn list_tags(state: State<'_, RepositoryState>) -> Result<Vec<TagInfo>, GitError> {
+pub async fn list_tags(
+    sort: Option<TagSort>,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<TagInfo>, GitError> {
     let repo_path = state
         .get_path()
         .await
@@ -36,60 +429,93 @@ pub async fn list_tags(state: State<'_, RepositoryState>) -> Result<Vec<TagInfo>
 
     tokio::task::spawn_blocking(move || {
         let repo = git2::Repository::open(&repo_path)?;
-        let tag_names = repo.tag_names(None)?;
-        let mut tags = Vec::new();
-
-        for name in tag_names.iter().flatten() {
-            let ref_name = format!("refs/tags/{}", name);
-            let reference = repo.find_reference(&ref_name)?;
-            let resolved = reference.resolve()?;
-            let oid = resolved
-                .target()
-                .ok_or_else(|| GitError::Internal(format!("Tag {} has no target", name)))?;
-            let obj = repo.find_object(oid, None)?;
-
-            let tag_info = if let Some(tag) = obj.as_tag() {
-                let target = tag.target()?.peel_to_commit()?;
-                let tagger_info = tag.tagger().map(|sig: git2::Signature| {
-                    format!(
-                        "{} <{}>",
-                        sig.name().unwrap_or(""),
-                        sig.email().unwrap_or("")
-                    )
-                });
-                TagInfo {
-                    name: name.to_string(),
-                    oid: oid.to_string(),
-                    target_oid: target.id().to_string(),
-                    message: tag.message().map(|m: &str| m.trim().to_string()),
-                    tagger: tagger_info,
-                    is_annotated: true,
-                    created_at_ms: tag
-                        .tagger()
-                        .map(|sig| (sig.when().seconds() as f64) * 1000.0)
-                        .unwrap_or_else(|| (target.time().seconds() as f64) * 1000.0),
-                }
-            } else {
-                let commit = obj.peel_to_commit()?;
-                TagInfo {
-                    name: name.to_string(),
-                    oid: commit.id().to_string(),
-                    target_oid: commit.id().to_string(),
-                    message: None,
-                    tagger: None,
-                    is_annotated: false,
-                    created_at_ms: (commit.time().seconds() as f64) * 1000.0,
-                }
-            };
-            tags.push(tag_info);
-        }
+        list_tags_internal(&repo, sort.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
 
-        tags.sort_by(|a, b| {
-            b.created_at_ms
-                .partial_cmp(&a.created_at_ms)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-        Ok(tags)
+/// List tags grouped by major or minor version, highest version first.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_tag_groups(
+    grouping: Option<TagGrouping>,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<TagGroup>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        let tags = list_tags_internal(&repo, TagSort::Semver)?;
+        Ok(group_tags(tags, grouping.unwrap_or_default()))
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Names of the tags that contain a commit.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_tags_containing(
+    oid: String,
+    state: State<'_, RepositoryState>,
+) -> Result<Vec<String>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        tags_containing_internal(&repo, parse_oid(&oid)?)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Describe a commit (HEAD by default) relative to its nearest tag.
+#[tauri::command]
+#[specta::specta]
+pub async fn describe_commit(
+    oid: Option<String>,
+    state: State<'_, RepositoryState>,
+) -> Result<Option<TagDescription>, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        let oid = match oid {
+            Some(oid) => parse_oid(&oid)?,
+            None => repo.head()?.peel_to_commit()?.id(),
+        };
+        describe_internal(&repo, oid)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Compare two tags: tagger, dates, messages and the commits between them.
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_tags(
+    from: String,
+    to: String,
+    state: State<'_, RepositoryState>,
+) -> Result<TagComparison, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        compare_tags_internal(&repo, &from, &to)
     })
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
@@ -109,7 +535,7 @@ pub async fn create_tag(
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
+    record_operation(repo_path.clone(), format!("Create tag '{}'", name), move || {
         let repo = git2::Repository::open(&repo_path)?;
 
         // Check if tag already exists
@@ -138,7 +564,7 @@ pub async fn create_tag(
                     e.message()
                 ))
             })?;
-            let tag_oid = repo.tag(&name, target_obj, &sig, msg, false)?;
+            let tag_oid = create_tag_signed(&repo, &name, target_obj, &sig, msg)?;
             let tagger_info = format!(
                 "{} <{}>",
                 sig.name().unwrap_or(""),
@@ -152,6 +578,7 @@ pub async fn create_tag(
                 tagger: Some(tagger_info),
                 is_annotated: true,
                 created_at_ms: (sig.when().seconds() as f64) * 1000.0,
+                version: TagVersion::parse(&name),
             })
         } else {
             // Lightweight tag
@@ -164,11 +591,11 @@ pub async fn create_tag(
                 tagger: None,
                 is_annotated: false,
                 created_at_ms: (target_commit.time().seconds() as f64) * 1000.0,
+                version: TagVersion::parse(&name),
             })
         }
     })
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// Delete a tag by name.
@@ -180,7 +607,7 @@ pub async fn delete_tag(name: String, state: State<'_, RepositoryState>) -> Resu
         .await
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
-    tokio::task::spawn_blocking(move || {
+    record_operation(repo_path.clone(), format!("Delete tag '{}'", name), move || {
         let repo = git2::Repository::open(&repo_path)?;
 
         let ref_name = format!("refs/tags/{}", name);
@@ -193,5 +620,198 @@ pub async fn delete_tag(name: String, state: State<'_, RepositoryState>) -> Resu
         Ok(())
     })
     .await
-    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Push tags to a remote; every local tag when `tags` is empty.
+#[tauri::command]
+#[specta::specta]
+pub async fn push_tags(
+    remote: String,
+    tags: Vec<String>,
+    on_progress: Channel<SyncProgress>,
+    operation_id: Option<String>,
+    state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<SyncResult, GitError> {
+    let options = PushOptions {
+        skip_branch: true,
+        all_tags: tags.is_empty(),
+        tags,
+        ..Default::default()
+    };
+    run_push(remote, options, on_progress, operation_id, state, registry).await
+}
+
+/// Delete tags on a remote. Local tags are kept.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_remote_tags(
+    remote: String,
+    tags: Vec<String>,
+    on_progress: Channel<SyncProgress>,
+    operation_id: Option<String>,
+    state: State<'_, RepositoryState>,
+    registry: State<'_, OperationRegistry>,
+) -> Result<SyncResult, GitError> {
+    if tags.is_empty() {
+        return Err(GitError::OperationFailed("No tags to delete".to_string()));
+    }
+    let options = PushOptions {
+        skip_branch: true,
+        delete_tags: tags,
+        ..Default::default()
+    };
+    run_push(remote, options, on_progress, operation_id, state, registry).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_index, setup_repo};
+
+    fn annotate(repo: &git2::Repository, name: &str, oid: git2::Oid, message: &str) {
+        let sig = repo.signature().unwrap();
+        let target = repo.find_object(oid, None).unwrap();
+        repo.tag(name, &target, &sig, message, false).unwrap();
+    }
+
+    fn lightweight(repo: &git2::Repository, name: &str, oid: git2::Oid) {
+        let target = repo.find_object(oid, None).unwrap();
+        repo.tag_lightweight(name, &target, false).unwrap();
+    }
+
+    #[test]
+    fn test_semver_precedence() {
+        let v = |name: &str| TagVersion::parse(name).unwrap();
+        assert_eq!(
+            v("v1.2"),
+            TagVersion {
+                major: 1,
+                minor: 2,
+                patch: 0,
+                pre_release: None
+            }
+        );
+        assert!(TagVersion::parse("release-2024").is_none());
+        assert!(TagVersion::parse("v1.2.3.4").is_none());
+        assert!(TagVersion::parse("v1.2.3-").is_none());
+
+        // Ordering from the semver spec, plus v10 after v9
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "v1.0.0+build.5",
+            "v9.0.0",
+            "v10.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_sorting_and_grouping_by_version() {
+        let (_dir, repo) = setup_repo();
+        let oid = commit_index(&repo, "One");
+        for name in [
+            "v1.9.0",
+            "v1.10.0",
+            "v2.0.0-rc.1",
+            "v1.10.1",
+            "nightly",
+            "v2.0.0",
+        ] {
+            lightweight(&repo, name, oid);
+        }
+
+        let names: Vec<_> = list_tags_internal(&repo, TagSort::Semver)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "v2.0.0",
+                "v2.0.0-rc.1",
+                "v1.10.1",
+                "v1.10.0",
+                "v1.9.0",
+                "nightly"
+            ]
+        );
+
+        let tags = list_tags_internal(&repo, TagSort::Date).unwrap();
+        let groups = group_tags(tags.clone(), TagGrouping::Major);
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.label.as_str(), g.tags.len(), g.pre_release_count))
+            .collect();
+        assert_eq!(summary, vec![("2.x", 2, 1), ("1.x", 3, 0), ("Other", 1, 0)]);
+
+        let minor: Vec<_> = group_tags(tags, TagGrouping::Minor)
+            .into_iter()
+            .map(|g| g.label)
+            .collect();
+        assert_eq!(minor, vec!["2.0.x", "1.10.x", "1.9.x", "Other"]);
+    }
+
+    #[test]
+    fn test_containing_describe_and_compare() {
+        let (_dir, repo) = setup_repo();
+        let first = commit_index(&repo, "One");
+        annotate(&repo, "v1.0.0", first, "First release");
+        let second = commit_index(&repo, "Two");
+        let third = commit_index(&repo, "Three");
+        annotate(&repo, "v1.1.0", third, "Second release\n\nWith fixes");
+        let fourth = commit_index(&repo, "Four");
+
+        assert_eq!(
+            tags_containing_internal(&repo, second).unwrap(),
+            vec!["v1.1.0"]
+        );
+        assert_eq!(
+            tags_containing_internal(&repo, first).unwrap(),
+            vec!["v1.1.0", "v1.0.0"]
+        );
+        assert!(tags_containing_internal(&repo, fourth).unwrap().is_empty());
+
+        let described = describe_internal(&repo, fourth).unwrap().unwrap();
+        assert_eq!(described.tag, "v1.1.0");
+        assert_eq!(described.distance, 1);
+        assert!(described.description.starts_with("v1.1.0-1-g"));
+        let exact = describe_internal(&repo, first).unwrap().unwrap();
+        assert_eq!((exact.description.as_str(), exact.distance), ("v1.0.0", 0));
+
+        let comparison = compare_tags_internal(&repo, "v1.0.0", "v1.1.0").unwrap();
+        assert_eq!(comparison.commits_ahead, 2);
+        assert_eq!(comparison.commits_behind, 0);
+        assert!(!comparison.tagger_changed);
+        let changed: Vec<_> = comparison
+            .message_diff
+            .iter()
+            .filter(|l| !matches!(l.origin, DiffLineOrigin::Context))
+            .map(|l| l.content.trim_end().to_string())
+            .collect();
+        assert_eq!(
+            changed,
+            vec!["First release", "Second release", "", "With fixes"]
+        );
+        assert!(matches!(
+            compare_tags_internal(&repo, "v1.0.0", "v9"),
+            Err(GitError::TagNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_describe_without_tags() {
+        let (_dir, repo) = setup_repo();
+        let oid = commit_index(&repo, "One");
+        assert!(describe_internal(&repo, oid).unwrap().is_none());
+    }
 }
@@ -0,0 +1,81 @@
+//! Repository fixtures shared by the git module tests.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// A fresh repository in a temporary directory, see [`init_repo`].
+pub(crate) fn setup_repo() -> (TempDir, git2::Repository) {
+    let dir = TempDir::new().unwrap();
+    let repo = init_repo(dir.path());
+    (dir, repo)
+}
+
+/// Initialize a repository at `path` with a test identity configured.
+pub(crate) fn init_repo(path: &Path) -> git2::Repository {
+    let repo = git2::Repository::init(path).unwrap();
+    {
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+    }
+    repo
+}
+
+/// Write `name` in the work tree and add it to the index.
+pub(crate) fn stage_file(repo: &git2::Repository, name: &str, content: &str) {
+    std::fs::write(repo.workdir().unwrap().join(name), content).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(name)).unwrap();
+    index.write().unwrap();
+}
+
+/// Commit the index on top of HEAD, or as the root commit on an unborn branch.
+pub(crate) fn commit_index(repo: &git2::Repository, message: &str) -> git2::Oid {
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+    let parents: Vec<git2::Commit> = repo
+        .head()
+        .ok()
+        .and_then(|h| h.peel_to_commit().ok())
+        .into_iter()
+        .collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+        .unwrap()
+}
+
+/// Write, stage and commit a single file.
+pub(crate) fn commit_file(
+    repo: &git2::Repository,
+    name: &str,
+    content: &str,
+    message: &str,
+) -> git2::Oid {
+    stage_file(repo, name, content);
+    commit_index(repo, message)
+}
+
+/// Configure SSH signing of commits and tags with a fresh key in `dir`;
+/// `None` if ssh-keygen is unavailable.
+pub(crate) fn configure_ssh_signing(repo: &git2::Repository, dir: &Path) -> Option<PathBuf> {
+    let key = dir.join("signing_key");
+    let status = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+        .arg(&key)
+        .status()
+        .ok()?;
+    if !status.success() {
+        return None;
+    }
+    let mut config = repo.config().unwrap();
+    config.set_str("gpg.format", "ssh").unwrap();
+    config
+        .set_str("user.signingkey", key.to_str().unwrap())
+        .unwrap();
+    config.set_bool("commit.gpgsign", true).unwrap();
+    config.set_bool("tag.gpgsign", true).unwrap();
+    Some(key)
+}
@@ -1,10 +1,44 @@
+//! Undo support.
+//!
+//! Mutating commands run through [`record_operation`], which snapshots the
+//! refs, HEAD, index tree and stash list before and after the command and
+//! appends the pair to a per-repository journal in `.git/flowforge`. The
+//! journal is an undo/redo stack: undoing an entry restores its "before"
+//! snapshot, redoing restores its "after" snapshot. Only the parts an entry
+//! actually changed are restored, and refs that moved since the entry was
+//! recorded are left alone; an entry that changed the stash list is only
+//! restored while that list is still as the entry left it. Working-tree
+//! changes are never discarded.
+//!
+//! Staging commands are not journaled; undoing a commit still restores the
+//! staged changes because the index tree is part of each snapshot.
+
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tauri::State;
 
 use crate::git::error::GitError;
+use crate::git::operation::abort_operation_internal;
+use crate::git::rebase::committer_signature;
+use crate::git::reflog::{ReflogAction, classify_reflog_message};
 use crate::git::repository::RepositoryState;
 
+/// Journal file, relative to the repository's git directory.
+const JOURNAL_FILE: &str = "flowforge/undo-journal.json";
+
+/// Oldest entries are dropped beyond this many.
+const MAX_JOURNAL_ENTRIES: usize = 100;
+
+/// Ref namespaces captured in snapshots (the stash is tracked separately).
+const TRACKED_REF_PREFIXES: [&str; 3] = ["refs/heads/", "refs/tags/", "refs/remotes/"];
+
+/// Serializes journal reads and writes across concurrent commands.
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
 /// Information about what can be undone
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -19,8 +53,598 @@ pub struct UndoInfo {
     pub target_oid: Option<String>,
 }
 
+/// A stash entry as captured in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct StashSnapshot {
+    oid: String,
+    message: String,
+}
+
+/// Repository state captured before or after a journaled command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RepoSnapshot {
+    /// Symbolic target of HEAD (None when detached)
+    head_ref: Option<String>,
+    /// Commit HEAD resolves to (None on an unborn branch)
+    head_oid: Option<String>,
+    /// Branch, tag and remote-tracking refs by full name
+    refs: BTreeMap<String, String>,
+    /// Tree written from the index (None while it has conflicts)
+    index_tree: Option<String>,
+    /// Stash list, newest first
+    stashes: Vec<StashSnapshot>,
+    /// Whether a merge, rebase, cherry-pick or similar was in progress
+    in_operation: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredEntry {
+    id: u32,
+    description: String,
+    timestamp: i64,
+    before: RepoSnapshot,
+    after: RepoSnapshot,
+}
+
+/// On-disk journal. `entries[..position]` can be undone, `entries[position..]` redone.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Journal {
+    next_id: u32,
+    position: usize,
+    entries: Vec<StoredEntry>,
+}
+
+/// An entry of the undo journal, as shown to the user.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntry {
+    /// Stable identifier used by `restore_journal_entry`
+    pub id: u32,
+    /// What the command did (e.g. "Delete branch 'feature'")
+    pub description: String,
+    /// When the command ran, in milliseconds since epoch (safe for JS Number)
+    pub timestamp_ms: f64,
+    /// HEAD commit before the command
+    pub head_before: Option<String>,
+    /// HEAD commit after the command
+    pub head_after: Option<String>,
+    /// Full names of refs the command created, moved or deleted
+    pub changed_refs: Vec<String>,
+    /// Whether the command changed the index
+    pub index_changed: bool,
+    /// Whether the command changed the stash list
+    pub stash_changed: bool,
+    /// True if the entry has been undone (it is on the redo stack)
+    pub undone: bool,
+}
+
+/// The undo/redo stack.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoJournal {
+    /// Entries, newest first
+    pub entries: Vec<JournalEntry>,
+    /// Whether there is an entry to undo
+    pub can_undo: bool,
+    /// Whether there is an entry to redo
+    pub can_redo: bool,
+}
+
+impl RepoSnapshot {
+    fn capture(repo: &git2::Repository) -> Result<Self, GitError> {
+        let head_ref = repo
+            .find_reference("HEAD")?
+            .symbolic_target()
+            .map(|s| s.to_string());
+        let head_oid = repo
+            .head()
+            .ok()
+            .and_then(|h| h.target())
+            .map(|oid| oid.to_string());
+
+        let mut refs = BTreeMap::new();
+        for reference in repo.references()?.flatten() {
+            let (Some(name), Some(target)) = (reference.name(), reference.target()) else {
+                continue;
+            };
+            if TRACKED_REF_PREFIXES.iter().any(|p| name.starts_with(p)) {
+                refs.insert(name.to_string(), target.to_string());
+            }
+        }
+
+        let index_tree = repo
+            .index()
+            .and_then(|mut index| index.write_tree())
+            .ok()
+            .map(|oid| oid.to_string());
+
+        Ok(Self {
+            head_ref,
+            head_oid,
+            refs,
+            index_tree,
+            stashes: capture_stashes(repo)?,
+            in_operation: repo.state() != git2::RepositoryState::Clean,
+        })
+    }
+}
+
+/// The stash list, newest first.
+fn capture_stashes(repo: &git2::Repository) -> Result<Vec<StashSnapshot>, GitError> {
+    let mut stashes = Vec::new();
+    if repo.find_reference("refs/stash").is_ok() {
+        for entry in repo.reflog("refs/stash")?.iter() {
+            stashes.push(StashSnapshot {
+                oid: entry.id_new().to_string(),
+                message: entry.message().unwrap_or("").to_string(),
+            });
+        }
+    }
+    Ok(stashes)
+}
+
+impl StoredEntry {
+    fn changed_refs(&self) -> Vec<String> {
+        let names: BTreeSet<&String> = self
+            .before
+            .refs
+            .keys()
+            .chain(self.after.refs.keys())
+            .collect();
+        names
+            .into_iter()
+            .filter(|name| self.before.refs.get(*name) != self.after.refs.get(*name))
+            .cloned()
+            .collect()
+    }
+
+    fn to_public(&self, undone: bool) -> JournalEntry {
+        JournalEntry {
+            id: self.id,
+            description: self.description.clone(),
+            timestamp_ms: (self.timestamp as f64) * 1000.0,
+            head_before: self.before.head_oid.clone(),
+            head_after: self.after.head_oid.clone(),
+            changed_refs: self.changed_refs(),
+            index_changed: self.before.index_tree != self.after.index_tree,
+            stash_changed: self.before.stashes != self.after.stashes,
+            undone,
+        }
+    }
+}
+
+impl Journal {
+    fn path(repo: &git2::Repository) -> PathBuf {
+        repo.path().join(JOURNAL_FILE)
+    }
+
+    /// Load the journal; a missing file is an empty journal, an unreadable
+    /// one is an error so it is never silently replaced.
+    fn load(repo: &git2::Repository) -> Result<Self, GitError> {
+        match fs::read_to_string(Self::path(repo)) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| GitError::Internal(format!("Failed to parse undo journal: {}", e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(GitError::OperationFailed(format!(
+                "Failed to read undo journal: {}",
+                e
+            ))),
+        }
+    }
+
+    fn save(&self, repo: &git2::Repository) -> Result<(), GitError> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                GitError::OperationFailed(format!("Failed to create journal directory: {}", e))
+            })?;
+        }
+
+        let content = serde_json::to_string(self)
+            .map_err(|e| GitError::Internal(format!("Failed to serialize journal: {}", e)))?;
+        fs::write(&path, content)
+            .map_err(|e| GitError::OperationFailed(format!("Failed to write journal: {}", e)))
+    }
+
+    fn push(&mut self, description: &str, before: RepoSnapshot, after: RepoSnapshot) {
+        // A new command discards anything that was undone
+        self.entries.truncate(self.position);
+        self.entries.push(StoredEntry {
+            id: self.next_id,
+            description: description.to_string(),
+            timestamp: chrono::Utc::now().timestamp(),
+            before,
+            after,
+        });
+        self.next_id = self.next_id.wrapping_add(1);
+
+        if self.entries.len() > MAX_JOURNAL_ENTRIES {
+            let excess = self.entries.len() - MAX_JOURNAL_ENTRIES;
+            self.entries.drain(..excess);
+        }
+        self.position = self.entries.len();
+    }
+
+    fn to_public(&self) -> UndoJournal {
+        UndoJournal {
+            entries: self
+                .entries
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, entry)| entry.to_public(i >= self.position))
+                .collect(),
+            can_undo: self.position > 0,
+            can_redo: self.position < self.entries.len(),
+        }
+    }
+}
+
+fn lock_journal() -> std::sync::MutexGuard<'static, ()> {
+    JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Run a mutating command and record its before/after state in the journal.
+///
+/// Journal failures never fail the command itself. Commands that error out
+/// are still recorded if they changed the repository before failing.
+pub(crate) fn journal_operation<T, E>(
+    repo_path: &Path,
+    description: &str,
+    op: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E> {
+    let before = git2::Repository::open(repo_path)
+        .ok()
+        .and_then(|repo| RepoSnapshot::capture(&repo).ok());
+
+    let result = op();
+
+    if let Some(before) = before
+        && let Ok(repo) = git2::Repository::open(repo_path)
+    {
+        let _ = append_entry(&repo, description, before);
+    }
+
+    result
+}
+
+/// Async wrapper around [`journal_operation`] that runs the command on the blocking pool.
+pub(crate) async fn record_operation<T, F>(
+    repo_path: PathBuf,
+    description: String,
+    op: F,
+) -> Result<T, GitError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, GitError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || journal_operation(&repo_path, &description, op))
+        .await
+        .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+fn append_entry(
+    repo: &git2::Repository,
+    description: &str,
+    before: RepoSnapshot,
+) -> Result<(), GitError> {
+    let after = RepoSnapshot::capture(repo)?;
+    if after == before {
+        return Ok(());
+    }
+
+    let _guard = lock_journal();
+    let mut journal = Journal::load(repo)?;
+    journal.push(description, before, after);
+    journal.save(repo)
+}
+
+/// Replace the stash list (newest first), rewriting `refs/stash` and its reflog.
+///
+/// Entries already in the list keep their reflog signature and time; only
+/// re-added ones are stamped with the current committer.
+fn write_stash_list(repo: &git2::Repository, stashes: &[StashSnapshot]) -> Result<(), GitError> {
+    let existing: Vec<(git2::Oid, git2::Signature<'static>)> = match repo.reflog("refs/stash") {
+        Ok(reflog) => reflog
+            .iter()
+            .map(|entry| (entry.id_new(), entry.committer().to_owned()))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let Some(newest) = stashes.first() else {
+        if let Ok(mut reference) = repo.find_reference("refs/stash") {
+            reference.delete()?;
+        }
+        return Ok(());
+    };
+
+    let newest_oid = git2::Oid::from_str(&newest.oid)
+        .map_err(|e| GitError::Internal(format!("Invalid stash OID: {}", e)))?;
+    repo.reference("refs/stash", newest_oid, true, &newest.message)?;
+
+    let mut reflog = repo.reflog("refs/stash")?;
+    while !reflog.is_empty() {
+        reflog.remove(0, false)?;
+    }
+
+    let now = committer_signature(repo)?;
+    for stash in stashes.iter().rev() {
+        let oid = git2::Oid::from_str(&stash.oid)
+            .map_err(|e| GitError::Internal(format!("Invalid stash OID: {}", e)))?;
+        let sig = existing
+            .iter()
+            .find(|(existing_oid, _)| *existing_oid == oid)
+            .map_or(&now, |(_, sig)| sig);
+        reflog.append(oid, sig, Some(&stash.message))?;
+    }
+    reflog.write()?;
+
+    Ok(())
+}
+
+fn parse_oid(oid: &str) -> Result<git2::Oid, GitError> {
+    git2::Oid::from_str(oid).map_err(|e| GitError::Internal(format!("Invalid OID: {}", e)))
+}
+
+/// Move the repository from the `from` snapshot to the `to` snapshot.
+///
+/// Only the parts that differ between the two are touched.
+fn apply_transition(
+    repo: &mut git2::Repository,
+    from: &RepoSnapshot,
+    to: &RepoSnapshot,
+    description: &str,
+) -> Result<(), GitError> {
+    if !to.in_operation && repo.state() != git2::RepositoryState::Clean {
+        if from.in_operation {
+            // The entry started this operation, so undoing it aborts it
+            abort_operation_internal(repo)?;
+        } else {
+            return Err(GitError::OperationInProgress(format!("{:?}", repo.state())));
+        }
+    }
+
+    // The stash list is replaced as a whole, so it must not have changed
+    // since the entry was recorded
+    let stashes_changed = from.stashes != to.stashes;
+    if stashes_changed && capture_stashes(repo)? != from.stashes {
+        return Err(GitError::OperationFailed(format!(
+            "The stash list changed after '{}'; restore the stashes manually",
+            description
+        )));
+    }
+
+    // Undoing a stash push: bring the stashed changes back before dropping the entry
+    let restored_stash = from
+        .stashes
+        .first()
+        .filter(|top| to.stashes.len() < from.stashes.len() && !to.stashes.contains(top));
+    if let Some(top) = restored_stash {
+        let mut index = None;
+        repo.stash_foreach(|i, _, oid| {
+            if oid.to_string() == top.oid {
+                index = Some(i);
+            }
+            index.is_none()
+        })?;
+        let index = index.ok_or_else(|| GitError::NotFound(format!("Stash {}", top.oid)))?;
+        let mut opts = git2::StashApplyOptions::new();
+        opts.reinstantiate_index();
+        repo.stash_apply(index, Some(&mut opts))?;
+    } else if from.index_tree != to.index_tree
+        && let Some(tree_oid) = &to.index_tree
+    {
+        let tree = repo.find_tree(parse_oid(tree_oid)?)?;
+        repo.checkout_tree(
+            tree.as_object(),
+            Some(git2::build::CheckoutBuilder::new().safe()),
+        )?;
+        let mut index = repo.index()?;
+        index.read_tree(&tree)?;
+        index.write()?;
+    }
+
+    if stashes_changed {
+        write_stash_list(repo, &to.stashes)?;
+    }
+
+    let message = format!("undo journal: {}", description);
+    let names: BTreeSet<&String> = from.refs.keys().chain(to.refs.keys()).collect();
+    let mut deletions = Vec::new();
+    for name in names {
+        let (old, new) = (from.refs.get(name), to.refs.get(name));
+        if old == new {
+            continue;
+        }
+
+        // Leave refs alone that moved since the entry was recorded
+        let current = repo
+            .find_reference(name)
+            .ok()
+            .and_then(|r| r.target())
+            .map(|oid| oid.to_string());
+        if current.as_ref() != old {
+            continue;
+        }
+
+        match new {
+            Some(oid) => {
+                repo.reference(name, parse_oid(oid)?, true, &message)?;
+            }
+            None => deletions.push(name.clone()),
+        }
+    }
+
+    if from.head_ref != to.head_ref || from.head_oid != to.head_oid {
+        match (&to.head_ref, &to.head_oid) {
+            (Some(head_ref), _) => repo.set_head(head_ref)?,
+            (None, Some(oid)) => repo.set_head_detached(parse_oid(oid)?)?,
+            (None, None) => {}
+        }
+    }
+
+    // Deleted after HEAD has moved, in case HEAD pointed at one of them
+    for name in deletions {
+        repo.find_reference(&name)?.delete()?;
+    }
+
+    Ok(())
+}
+
+pub fn get_undo_journal_internal(repo: &git2::Repository) -> Result<UndoJournal, GitError> {
+    let _guard = lock_journal();
+    Ok(Journal::load(repo)?.to_public())
+}
+
+pub fn undo_internal(repo: &mut git2::Repository) -> Result<UndoJournal, GitError> {
+    let _guard = lock_journal();
+    let mut journal = Journal::load(repo)?;
+    if journal.position == 0 {
+        return Err(GitError::OperationFailed("Nothing to undo".to_string()));
+    }
+
+    let entry = journal.entries[journal.position - 1].clone();
+    apply_transition(repo, &entry.after, &entry.before, &entry.description)?;
+    journal.position -= 1;
+    journal.save(repo)?;
+
+    Ok(journal.to_public())
+}
+
+pub fn redo_internal(repo: &mut git2::Repository) -> Result<UndoJournal, GitError> {
+    let _guard = lock_journal();
+    let mut journal = Journal::load(repo)?;
+    if journal.position >= journal.entries.len() {
+        return Err(GitError::OperationFailed("Nothing to redo".to_string()));
+    }
+
+    let entry = journal.entries[journal.position].clone();
+    apply_transition(repo, &entry.before, &entry.after, &entry.description)?;
+    journal.position += 1;
+    journal.save(repo)?;
+
+    Ok(journal.to_public())
+}
+
+/// Jump to a journal entry: undo it (and everything after it) if it is applied,
+/// or redo up to and including it if it was undone.
+pub fn restore_entry_internal(
+    repo: &mut git2::Repository,
+    id: u32,
+) -> Result<UndoJournal, GitError> {
+    let _guard = lock_journal();
+    let mut journal = Journal::load(repo)?;
+    let index = journal
+        .entries
+        .iter()
+        .position(|e| e.id == id)
+        .ok_or_else(|| GitError::NotFound(format!("Journal entry {}", id)))?;
+
+    // Step one entry at a time so each restore only touches what that entry changed
+    let mut result = Ok(());
+    if index < journal.position {
+        for i in (index..journal.position).rev() {
+            let entry = journal.entries[i].clone();
+            result = apply_transition(repo, &entry.after, &entry.before, &entry.description);
+            if result.is_err() {
+                break;
+            }
+            journal.position = i;
+        }
+    } else {
+        for i in journal.position..=index {
+            let entry = journal.entries[i].clone();
+            result = apply_transition(repo, &entry.before, &entry.after, &entry.description);
+            if result.is_err() {
+                break;
+            }
+            journal.position = i + 1;
+        }
+    }
+
+    // Persist partial progress too, so the stack matches the repository
+    journal.save(repo)?;
+    result.map(|_| journal.to_public())
+}
+
+/// Get the undo/redo stack for the current repository.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_undo_journal(state: State<'_, RepositoryState>) -> Result<UndoJournal, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let repo = git2::Repository::open(&repo_path)?;
+        get_undo_journal_internal(&repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Undo the most recent journal entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn undo_operation(state: State<'_, RepositoryState>) -> Result<UndoJournal, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut repo = git2::Repository::open(&repo_path)?;
+        undo_internal(&mut repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Redo the most recently undone journal entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn redo_operation(state: State<'_, RepositoryState>) -> Result<UndoJournal, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut repo = git2::Repository::open(&repo_path)?;
+        redo_internal(&mut repo)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Restore the repository to the state around a specific journal entry.
+///
+/// An applied entry is undone together with every entry after it; an undone
+/// entry is redone together with every undone entry before it.
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_journal_entry(
+    id: u32,
+    state: State<'_, RepositoryState>,
+) -> Result<UndoJournal, GitError> {
+    let repo_path = state
+        .get_path()
+        .await
+        .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut repo = git2::Repository::open(&repo_path)?;
+        restore_entry_internal(&mut repo, id)
+    })
+    .await
+    .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
+}
+
 /// Get information about what can be undone.
-/// Looks at HEAD reflog to find the previous state.
+/// Uses the newest journal entry, falling back to the HEAD reflog when the
+/// journal is empty (e.g. for changes made outside FlowForge).
 #[tauri::command]
 #[specta::specta]
 pub async fn get_undo_info(state: State<'_, RepositoryState>) -> Result<UndoInfo, GitError> {
@@ -32,6 +656,19 @@ pub async fn get_undo_info(state: State<'_, RepositoryState>) -> Result<UndoInfo
     tokio::task::spawn_blocking(move || {
         let repo = git2::Repository::open(&repo_path)?;
 
+        let journal = {
+            let _guard = lock_journal();
+            Journal::load(&repo)?
+        };
+        if let Some(entry) = journal.position.checked_sub(1).map(|i| &journal.entries[i]) {
+            return Ok(UndoInfo {
+                can_undo: true,
+                description: Some(format!("Undo {}", entry.description)),
+                reflog_message: None,
+                target_oid: entry.before.head_oid.clone(),
+            });
+        }
+
         // Get HEAD reflog
         let reflog = match repo.reflog("HEAD") {
             Ok(r) => r,
@@ -86,7 +723,10 @@ pub async fn get_undo_info(state: State<'_, RepositoryState>) -> Result<UndoInfo
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
 
-/// Undo the last operation by resetting HEAD to the previous reflog entry.
+/// Undo the last operation.
+///
+/// Undoes the newest journal entry; without one, resets HEAD to the previous
+/// reflog entry.
 #[tauri::command]
 #[specta::specta]
 pub async fn undo_last_operation(state: State<'_, RepositoryState>) -> Result<(), GitError> {
@@ -96,7 +736,11 @@ pub async fn undo_last_operation(state: State<'_, RepositoryState>) -> Result<()
         .ok_or_else(|| GitError::NotFound("No repository open".to_string()))?;
 
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
+        let mut repo = git2::Repository::open(&repo_path)?;
+
+        if get_undo_journal_internal(&repo)?.can_undo {
+            return undo_internal(&mut repo).map(|_| ());
+        }
 
         // Get the target from reflog
         let reflog = repo.reflog("HEAD")?;
@@ -123,21 +767,200 @@ pub async fn undo_last_operation(state: State<'_, RepositoryState>) -> Result<()
 
 /// Parse reflog message into human-readable description
 fn parse_undo_description(msg: &str) -> String {
-    if msg.starts_with("commit:") {
-        format!("Undo commit: {}", msg.trim_start_matches("commit:").trim())
-    } else if msg.starts_with("commit (amend):") {
-        "Undo amend commit".to_string()
-    } else if msg.starts_with("reset:") {
-        "Undo reset".to_string()
-    } else if msg.starts_with("checkout:") {
-        "Undo checkout".to_string()
-    } else if msg.starts_with("merge") {
-        "Undo merge".to_string()
-    } else if msg.starts_with("rebase") {
-        "Undo rebase".to_string()
-    } else if msg.starts_with("pull:") {
-        "Undo pull".to_string()
-    } else {
-        format!("Undo: {}", msg)
+    match classify_reflog_message(msg) {
+        ReflogAction::Commit => {
+            let subject = msg.split_once(':').map(|(_, s)| s.trim()).unwrap_or(msg);
+            format!("Undo commit: {}", subject)
+        }
+        ReflogAction::Amend => "Undo amend commit".to_string(),
+        ReflogAction::Reset => "Undo reset".to_string(),
+        ReflogAction::Checkout => "Undo checkout".to_string(),
+        ReflogAction::Merge => "Undo merge".to_string(),
+        ReflogAction::Rebase => "Undo rebase".to_string(),
+        ReflogAction::Pull => "Undo pull".to_string(),
+        _ => format!("Undo: {}", msg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_index, setup_repo, stage_file};
+
+    fn journaled_commit(
+        repo: &git2::Repository,
+        dir: &Path,
+        name: &str,
+        message: &str,
+    ) -> git2::Oid {
+        stage_file(repo, name, message);
+        journal_operation(dir, message, || {
+            Ok::<_, GitError>(commit_index(repo, message))
+        })
+        .unwrap()
+    }
+
+    fn head_oid(repo: &git2::Repository) -> git2::Oid {
+        repo.head().unwrap().target().unwrap()
+    }
+
+    #[test]
+    fn test_undo_commit_keeps_changes_staged_and_redo_restores() {
+        let (dir, mut repo) = setup_repo();
+        let first = journaled_commit(&repo, dir.path(), "a.txt", "first");
+        let second = journaled_commit(&repo, dir.path(), "b.txt", "second");
+
+        let journal = undo_internal(&mut repo).unwrap();
+        assert!(journal.can_redo);
+        assert_eq!(head_oid(&repo), first);
+
+        // The undone commit's changes are staged again
+        {
+            let head_tree = repo.head().unwrap().peel_to_tree().unwrap();
+            let diff = repo
+                .diff_tree_to_index(Some(&head_tree), None, None)
+                .unwrap();
+            assert_eq!(diff.deltas().len(), 1);
+        }
+
+        redo_internal(&mut repo).unwrap();
+        assert_eq!(head_oid(&repo), second);
+    }
+
+    #[test]
+    fn test_undo_branch_deletion() {
+        let (dir, mut repo) = setup_repo();
+        let first = journaled_commit(&repo, dir.path(), "a.txt", "first");
+        repo.branch("feature", &repo.find_commit(first).unwrap(), false)
+            .unwrap();
+
+        journal_operation(dir.path(), "Delete branch 'feature'", || {
+            repo.find_branch("feature", git2::BranchType::Local)?
+                .delete()
+        })
+        .unwrap();
+        assert!(
+            repo.find_branch("feature", git2::BranchType::Local)
+                .is_err()
+        );
+
+        undo_internal(&mut repo).unwrap();
+        let branch = repo
+            .find_branch("feature", git2::BranchType::Local)
+            .unwrap();
+        assert_eq!(branch.get().target(), Some(first));
+    }
+
+    #[test]
+    fn test_undo_stash_save_and_drop() {
+        let (dir, mut repo) = setup_repo();
+        journaled_commit(&repo, dir.path(), "a.txt", "first");
+        fs::write(dir.path().join("a.txt"), "changed").unwrap();
+
+        let path = dir.path().to_path_buf();
+        let sig = repo.signature().unwrap();
+        journal_operation(&path, "Stash changes", || {
+            repo.stash_save(&sig, "wip", None).map(|_| ())
+        })
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "first"
+        );
+
+        undo_internal(&mut repo).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "changed"
+        );
+        assert!(RepoSnapshot::capture(&repo).unwrap().stashes.is_empty());
+
+        // Redo stashes again, then a journaled drop can be undone
+        redo_internal(&mut repo).unwrap();
+        journal_operation(&path, "Drop stash@{0}", || repo.stash_drop(0)).unwrap();
+        assert!(RepoSnapshot::capture(&repo).unwrap().stashes.is_empty());
+
+        undo_internal(&mut repo).unwrap();
+        let stashes = RepoSnapshot::capture(&repo).unwrap().stashes;
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].message.contains("wip"));
+    }
+
+    #[test]
+    fn test_undo_stash_refuses_when_stash_list_changed() {
+        let (dir, mut repo) = setup_repo();
+        journaled_commit(&repo, dir.path(), "a.txt", "first");
+        let sig = repo.signature().unwrap();
+
+        fs::write(dir.path().join("a.txt"), "journaled").unwrap();
+        journal_operation(dir.path(), "Stash changes", || {
+            repo.stash_save(&sig, "journaled", None).map(|_| ())
+        })
+        .unwrap();
+
+        // A stash made outside the journal must survive
+        fs::write(dir.path().join("a.txt"), "outside").unwrap();
+        repo.stash_save(&sig, "outside", None).unwrap();
+        let before = capture_stashes(&repo).unwrap();
+
+        assert!(matches!(
+            undo_internal(&mut repo),
+            Err(GitError::OperationFailed(_))
+        ));
+        assert_eq!(capture_stashes(&repo).unwrap(), before);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "first"
+        );
+    }
+
+    #[test]
+    fn test_unreadable_journal_is_not_overwritten() {
+        let (dir, repo) = setup_repo();
+        let path = Journal::path(&repo);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+
+        assert!(get_undo_journal_internal(&repo).is_err());
+        journaled_commit(&repo, dir.path(), "a.txt", "first");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn test_new_operation_clears_redo_stack() {
+        let (dir, mut repo) = setup_repo();
+        journaled_commit(&repo, dir.path(), "a.txt", "first");
+        journaled_commit(&repo, dir.path(), "b.txt", "second");
+
+        undo_internal(&mut repo).unwrap();
+        journaled_commit(&repo, dir.path(), "c.txt", "third");
+
+        let journal = get_undo_journal_internal(&repo).unwrap();
+        assert!(!journal.can_redo);
+        let descriptions: Vec<&str> = journal
+            .entries
+            .iter()
+            .map(|e| e.description.as_str())
+            .collect();
+        assert_eq!(descriptions, vec!["third", "first"]);
+    }
+
+    #[test]
+    fn test_restore_entry_undoes_later_entries() {
+        let (dir, mut repo) = setup_repo();
+        let first = journaled_commit(&repo, dir.path(), "a.txt", "first");
+        journaled_commit(&repo, dir.path(), "b.txt", "second");
+        let third = journaled_commit(&repo, dir.path(), "c.txt", "third");
+
+        let journal = get_undo_journal_internal(&repo).unwrap();
+        let second_id = journal.entries[1].id;
+
+        let journal = restore_entry_internal(&mut repo, second_id).unwrap();
+        assert_eq!(head_oid(&repo), first);
+        assert_eq!(journal.entries.iter().filter(|e| e.undone).count(), 2);
+
+        let third_id = journal.entries[0].id;
+        restore_entry_internal(&mut repo, third_id).unwrap();
+        assert_eq!(head_oid(&repo), third);
     }
 }
@@ -55,9 +55,7 @@ pub struct CreateWorktreeOptions {
 /// Get the status of a repository's working directory.
 fn get_worktree_status(repo: &git2::Repository) -> WorktreeStatus {
     let mut opts = git2::StatusOptions::new();
-    opts.include_untracked(true)
-        .recurse_untracked_dirs(false)
-        .exclude_submodules(true);
+    opts.include_untracked(true).recurse_untracked_dirs(false);
 
     match repo.statuses(Some(&mut opts)) {
         Ok(statuses) => {
@@ -327,3 +325,31 @@ pub async fn delete_worktree(
     .await
     .map_err(|e| GitError::Internal(format!("Task join error: {}", e)))?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_file, commit_index};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_moved_submodule_pointer_makes_worktree_dirty() {
+        let dir = TempDir::new().unwrap();
+        let lib = git2::Repository::init(dir.path().join("lib")).unwrap();
+        commit_file(&lib, "lib.txt", "lib.txt", "Update");
+        let main = git2::Repository::init(dir.path().join("main")).unwrap();
+        commit_file(&main, "README", "README", "Update");
+        {
+            let lib_url = dir.path().join("lib").to_string_lossy().to_string();
+            let mut sub = main.submodule(&lib_url, Path::new("lib"), true).unwrap();
+            sub.clone(None).unwrap();
+            sub.add_finalize().unwrap();
+        }
+        commit_index(&main, "Update");
+        assert!(matches!(get_worktree_status(&main), WorktreeStatus::Clean));
+
+        let checkout = git2::Repository::open(dir.path().join("main/lib")).unwrap();
+        commit_file(&checkout, "more.txt", "more.txt", "Update");
+        assert!(matches!(get_worktree_status(&main), WorktreeStatus::Dirty));
+    }
+}
@@ -6,6 +6,8 @@ use specta::Type;
 use tauri::State;
 
 use crate::git::repository::RepositoryState;
+use crate::git::signing::create_tag_signed;
+use crate::git::undo::journal_operation;
 use crate::gitflow::error::GitflowError;
 use crate::gitflow::machine::GitflowState;
 use crate::gitflow::merge::merge_no_ff;
@@ -48,37 +50,40 @@ pub async fn start_feature(
         .await
         .ok_or(GitflowError::Git("No repository open".to_string()))?;
 
+    let description = format!("Start feature '{}'", name);
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-
-        // Must be on develop
-        let current = get_current_branch_name(&repo)?
-            .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
-
-        if !is_develop_branch(&current) {
-            return Err(GitflowError::InvalidContext {
-                expected: "develop".to_string(),
-                actual: current,
-            });
-        }
+        journal_operation(&repo_path, &description, || {
+            let repo = git2::Repository::open(&repo_path)?;
+
+            // Must be on develop
+            let current = get_current_branch_name(&repo)?
+                .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+
+            if !is_develop_branch(&current) {
+                return Err(GitflowError::InvalidContext {
+                    expected: "develop".to_string(),
+                    actual: current,
+                });
+            }
 
-        let branch_name = format!("feature/{}", name);
+            let branch_name = format!("feature/{}", name);
 
-        // Check branch doesn't exist
-        if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
-            return Err(GitflowError::BranchExists(branch_name));
-        }
+            // Check branch doesn't exist
+            if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
+                return Err(GitflowError::BranchExists(branch_name));
+            }
 
-        // Create branch from HEAD
-        let head_commit = repo.head()?.peel_to_commit()?;
-        repo.branch(&branch_name, &head_commit, false)?;
+            // Create branch from HEAD
+            let head_commit = repo.head()?.peel_to_commit()?;
+            repo.branch(&branch_name, &head_commit, false)?;
 
-        // Checkout new branch (safe checkout preserves uncommitted changes)
-        let refname = format!("refs/heads/{}", branch_name);
-        repo.set_head(&refname)?;
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
+            // Checkout new branch (safe checkout preserves uncommitted changes)
+            let refname = format!("refs/heads/{}", branch_name);
+            repo.set_head(&refname)?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
 
-        Ok(branch_name)
+            Ok(branch_name)
+        })
     })
     .await
     .map_err(|e| GitflowError::Git(format!("Task error: {}", e)))?
@@ -93,27 +98,30 @@ pub async fn finish_feature(state: State<'_, RepositoryState>) -> Result<(), Git
         .await
         .ok_or(GitflowError::Git("No repository open".to_string()))?;
 
+    let description = "Finish feature".to_string();
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-        ensure_clean_working_tree(&repo)?;
+        journal_operation(&repo_path, &description, || {
+            let repo = git2::Repository::open(&repo_path)?;
+            ensure_clean_working_tree(&repo)?;
 
-        // Must be on feature branch
-        let current = get_current_branch_name(&repo)?
-            .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+            // Must be on feature branch
+            let current = get_current_branch_name(&repo)?
+                .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
 
-        let _feature_name = current
-            .strip_prefix("feature/")
-            .ok_or(GitflowError::NotOnFeatureBranch)?;
+            let _feature_name = current
+                .strip_prefix("feature/")
+                .ok_or(GitflowError::NotOnFeatureBranch)?;
 
-        // Merge to develop with --no-ff
-        let message = format!("Merge branch '{}' into develop", current);
-        merge_no_ff(&repo, &current, "develop", &message)?;
+            // Merge to develop with --no-ff
+            let message = format!("Merge branch '{}' into develop", current);
+            merge_no_ff(&repo, &current, "develop", &message)?;
 
-        // Delete feature branch (we're now on develop after merge)
-        let mut branch = repo.find_branch(&current, BranchType::Local)?;
-        branch.delete()?;
+            // Delete feature branch (we're now on develop after merge)
+            let mut branch = repo.find_branch(&current, BranchType::Local)?;
+            branch.delete()?;
 
-        Ok(())
+            Ok(())
+        })
     })
     .await
     .map_err(|e| GitflowError::Git(format!("Task error: {}", e)))?
@@ -142,45 +150,48 @@ pub async fn start_release(
         .await
         .ok_or(GitflowError::Git("No repository open".to_string()))?;
 
+    let description = format!("Start release '{}'", version);
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-
-        // Must be on develop
-        let current = get_current_branch_name(&repo)?
-            .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+        journal_operation(&repo_path, &description, || {
+            let repo = git2::Repository::open(&repo_path)?;
+
+            // Must be on develop
+            let current = get_current_branch_name(&repo)?
+                .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+
+            if !is_develop_branch(&current) {
+                return Err(GitflowError::InvalidContext {
+                    expected: "develop".to_string(),
+                    actual: current,
+                });
+            }
 
-        if !is_develop_branch(&current) {
-            return Err(GitflowError::InvalidContext {
-                expected: "develop".to_string(),
-                actual: current,
-            });
-        }
-
-        // Check no active release
-        for branch in repo.branches(Some(BranchType::Local))? {
-            let (branch, _) = branch?;
-            if let Some(name) = branch.name()? {
-                if name.starts_with("release/") {
-                    return Err(GitflowError::ReleaseInProgress(name.to_string()));
+            // Check no active release
+            for branch in repo.branches(Some(BranchType::Local))? {
+                let (branch, _) = branch?;
+                if let Some(name) = branch.name()? {
+                    if name.starts_with("release/") {
+                        return Err(GitflowError::ReleaseInProgress(name.to_string()));
+                    }
                 }
             }
-        }
 
-        let branch_name = format!("release/{}", version);
+            let branch_name = format!("release/{}", version);
 
-        // Check branch doesn't exist
-        if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
-            return Err(GitflowError::BranchExists(branch_name));
-        }
+            // Check branch doesn't exist
+            if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
+                return Err(GitflowError::BranchExists(branch_name));
+            }
 
-        // Create and checkout (safe checkout preserves uncommitted changes)
-        let head_commit = repo.head()?.peel_to_commit()?;
-        repo.branch(&branch_name, &head_commit, false)?;
-        let refname = format!("refs/heads/{}", branch_name);
-        repo.set_head(&refname)?;
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
+            // Create and checkout (safe checkout preserves uncommitted changes)
+            let head_commit = repo.head()?.peel_to_commit()?;
+            repo.branch(&branch_name, &head_commit, false)?;
+            let refname = format!("refs/heads/{}", branch_name);
+            repo.set_head(&refname)?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
 
-        Ok(branch_name)
+            Ok(branch_name)
+        })
     })
     .await
     .map_err(|e| GitflowError::Git(format!("Task error: {}", e)))?
@@ -199,44 +210,47 @@ pub async fn finish_release(
         .await
         .ok_or(GitflowError::Git("No repository open".to_string()))?;
 
+    let description = "Finish release".to_string();
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-        ensure_clean_working_tree(&repo)?;
-
-        // Must be on release branch
-        let current = get_current_branch_name(&repo)?
-            .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
-
-        let version = current
-            .strip_prefix("release/")
-            .ok_or(GitflowError::NotOnReleaseBranch)?
-            .to_string();
-
-        // 1. Merge to main with --no-ff
-        let main_branch = if repo.find_branch("main", BranchType::Local).is_ok() {
-            "main"
-        } else {
-            "master"
-        };
-        let main_msg = format!("Merge branch '{}' into {}", current, main_branch);
-        merge_no_ff(&repo, &current, main_branch, &main_msg)?;
-
-        // 2. Create tag on main (we're now on main after merge)
-        let tag_name = format!("v{}", version);
-        let msg = tag_message.unwrap_or_else(|| format!("Release {}", version));
-        let head_commit = repo.head()?.peel_to_commit()?;
-        let sig = repo.signature()?;
-        repo.tag(&tag_name, head_commit.as_object(), &sig, &msg, false)?;
-
-        // 3. Merge to develop with --no-ff
-        let develop_msg = format!("Merge branch '{}' into develop", current);
-        merge_no_ff(&repo, &current, "develop", &develop_msg)?;
-
-        // 4. Delete release branch (we're on develop now)
-        let mut branch = repo.find_branch(&current, BranchType::Local)?;
-        branch.delete()?;
-
-        Ok(tag_name)
+        journal_operation(&repo_path, &description, || {
+            let repo = git2::Repository::open(&repo_path)?;
+            ensure_clean_working_tree(&repo)?;
+
+            // Must be on release branch
+            let current = get_current_branch_name(&repo)?
+                .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+
+            let version = current
+                .strip_prefix("release/")
+                .ok_or(GitflowError::NotOnReleaseBranch)?
+                .to_string();
+
+            // 1. Merge to main with --no-ff
+            let main_branch = if repo.find_branch("main", BranchType::Local).is_ok() {
+                "main"
+            } else {
+                "master"
+            };
+            let main_msg = format!("Merge branch '{}' into {}", current, main_branch);
+            merge_no_ff(&repo, &current, main_branch, &main_msg)?;
+
+            // 2. Create tag on main (we're now on main after merge)
+            let tag_name = format!("v{}", version);
+            let msg = tag_message.unwrap_or_else(|| format!("Release {}", version));
+            let head_commit = repo.head()?.peel_to_commit()?;
+            let sig = repo.signature()?;
+            create_tag_signed(&repo, &tag_name, head_commit.as_object(), &sig, &msg)?;
+
+            // 3. Merge to develop with --no-ff
+            let develop_msg = format!("Merge branch '{}' into develop", current);
+            merge_no_ff(&repo, &current, "develop", &develop_msg)?;
+
+            // 4. Delete release branch (we're on develop now)
+            let mut branch = repo.find_branch(&current, BranchType::Local)?;
+            branch.delete()?;
+
+            Ok(tag_name)
+        })
     })
     .await
     .map_err(|e| GitflowError::Git(format!("Task error: {}", e)))?
@@ -262,44 +276,47 @@ pub async fn start_hotfix(
         .await
         .ok_or(GitflowError::Git("No repository open".to_string()))?;
 
+    let description = format!("Start hotfix '{}'", name);
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-
-        // Must be on main/master
-        let current = get_current_branch_name(&repo)?
-            .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+        journal_operation(&repo_path, &description, || {
+            let repo = git2::Repository::open(&repo_path)?;
+
+            // Must be on main/master
+            let current = get_current_branch_name(&repo)?
+                .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+
+            if !is_main_branch(&current) {
+                return Err(GitflowError::InvalidContext {
+                    expected: "main".to_string(),
+                    actual: current,
+                });
+            }
 
-        if !is_main_branch(&current) {
-            return Err(GitflowError::InvalidContext {
-                expected: "main".to_string(),
-                actual: current,
-            });
-        }
-
-        // Check no active hotfix
-        for branch in repo.branches(Some(BranchType::Local))? {
-            let (branch, _) = branch?;
-            if let Some(bname) = branch.name()? {
-                if bname.starts_with("hotfix/") {
-                    return Err(GitflowError::HotfixInProgress(bname.to_string()));
+            // Check no active hotfix
+            for branch in repo.branches(Some(BranchType::Local))? {
+                let (branch, _) = branch?;
+                if let Some(bname) = branch.name()? {
+                    if bname.starts_with("hotfix/") {
+                        return Err(GitflowError::HotfixInProgress(bname.to_string()));
+                    }
                 }
             }
-        }
 
-        let branch_name = format!("hotfix/{}", name);
+            let branch_name = format!("hotfix/{}", name);
 
-        if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
-            return Err(GitflowError::BranchExists(branch_name));
-        }
+            if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
+                return Err(GitflowError::BranchExists(branch_name));
+            }
 
-        // Safe checkout preserves uncommitted changes
-        let head_commit = repo.head()?.peel_to_commit()?;
-        repo.branch(&branch_name, &head_commit, false)?;
-        let refname = format!("refs/heads/{}", branch_name);
-        repo.set_head(&refname)?;
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
+            // Safe checkout preserves uncommitted changes
+            let head_commit = repo.head()?.peel_to_commit()?;
+            repo.branch(&branch_name, &head_commit, false)?;
+            let refname = format!("refs/heads/{}", branch_name);
+            repo.set_head(&refname)?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))?;
 
-        Ok(branch_name)
+            Ok(branch_name)
+        })
     })
     .await
     .map_err(|e| GitflowError::Git(format!("Task error: {}", e)))?
@@ -318,43 +335,46 @@ pub async fn finish_hotfix(
         .await
         .ok_or(GitflowError::Git("No repository open".to_string()))?;
 
+    let description = "Finish hotfix".to_string();
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-        ensure_clean_working_tree(&repo)?;
-
-        let current = get_current_branch_name(&repo)?
-            .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
-
-        let hotfix_name = current
-            .strip_prefix("hotfix/")
-            .ok_or(GitflowError::NotOnHotfixBranch)?
-            .to_string();
-
-        // 1. Merge to main
-        let main_branch = if repo.find_branch("main", BranchType::Local).is_ok() {
-            "main"
-        } else {
-            "master"
-        };
-        let main_msg = format!("Merge branch '{}' into {}", current, main_branch);
-        merge_no_ff(&repo, &current, main_branch, &main_msg)?;
-
-        // 2. Create tag on main
-        let tag_name = format!("hotfix-{}", hotfix_name);
-        let msg = tag_message.unwrap_or_else(|| format!("Hotfix {}", hotfix_name));
-        let head_commit = repo.head()?.peel_to_commit()?;
-        let sig = repo.signature()?;
-        repo.tag(&tag_name, head_commit.as_object(), &sig, &msg, false)?;
-
-        // 3. Merge to develop
-        let develop_msg = format!("Merge branch '{}' into develop", current);
-        merge_no_ff(&repo, &current, "develop", &develop_msg)?;
-
-        // 4. Delete hotfix branch
-        let mut branch = repo.find_branch(&current, BranchType::Local)?;
-        branch.delete()?;
-
-        Ok(tag_name)
+        journal_operation(&repo_path, &description, || {
+            let repo = git2::Repository::open(&repo_path)?;
+            ensure_clean_working_tree(&repo)?;
+
+            let current = get_current_branch_name(&repo)?
+                .ok_or(GitflowError::Git("HEAD is detached".to_string()))?;
+
+            let hotfix_name = current
+                .strip_prefix("hotfix/")
+                .ok_or(GitflowError::NotOnHotfixBranch)?
+                .to_string();
+
+            // 1. Merge to main
+            let main_branch = if repo.find_branch("main", BranchType::Local).is_ok() {
+                "main"
+            } else {
+                "master"
+            };
+            let main_msg = format!("Merge branch '{}' into {}", current, main_branch);
+            merge_no_ff(&repo, &current, main_branch, &main_msg)?;
+
+            // 2. Create tag on main
+            let tag_name = format!("hotfix-{}", hotfix_name);
+            let msg = tag_message.unwrap_or_else(|| format!("Hotfix {}", hotfix_name));
+            let head_commit = repo.head()?.peel_to_commit()?;
+            let sig = repo.signature()?;
+            create_tag_signed(&repo, &tag_name, head_commit.as_object(), &sig, &msg)?;
+
+            // 3. Merge to develop
+            let develop_msg = format!("Merge branch '{}' into develop", current);
+            merge_no_ff(&repo, &current, "develop", &develop_msg)?;
+
+            // 4. Delete hotfix branch
+            let mut branch = repo.find_branch(&current, BranchType::Local)?;
+            branch.delete()?;
+
+            Ok(tag_name)
+        })
     })
     .await
     .map_err(|e| GitflowError::Git(format!("Task error: {}", e)))?
@@ -483,37 +503,40 @@ pub async fn abort_gitflow(state: State<'_, RepositoryState>) -> Result<(), Gitf
         .await
         .ok_or(GitflowError::Git("No repository open".to_string()))?;
 
+    let description = "Abort gitflow branch".to_string();
     tokio::task::spawn_blocking(move || {
-        let repo = git2::Repository::open(&repo_path)?;
-        ensure_clean_working_tree(&repo)?;
-        let ctx = GitflowContext::from_repo(&repo)?;
-
-        let (branch_to_delete, target_branch) = match &ctx.state {
-            GitflowState::Feature { .. } => (ctx.current_branch.clone(), "develop"),
-            GitflowState::Release { .. } => (ctx.current_branch.clone(), "develop"),
-            GitflowState::Hotfix { .. } => {
-                let main = if repo.find_branch("main", BranchType::Local).is_ok() {
-                    "main"
-                } else {
-                    "master"
-                };
-                (ctx.current_branch.clone(), main)
-            }
-            GitflowState::Idle => {
-                return Err(GitflowError::Git("No active Gitflow operation".to_string()))
-            }
-        };
+        journal_operation(&repo_path, &description, || {
+            let repo = git2::Repository::open(&repo_path)?;
+            ensure_clean_working_tree(&repo)?;
+            let ctx = GitflowContext::from_repo(&repo)?;
+
+            let (branch_to_delete, target_branch) = match &ctx.state {
+                GitflowState::Feature { .. } => (ctx.current_branch.clone(), "develop"),
+                GitflowState::Release { .. } => (ctx.current_branch.clone(), "develop"),
+                GitflowState::Hotfix { .. } => {
+                    let main = if repo.find_branch("main", BranchType::Local).is_ok() {
+                        "main"
+                    } else {
+                        "master"
+                    };
+                    (ctx.current_branch.clone(), main)
+                }
+                GitflowState::Idle => {
+                    return Err(GitflowError::Git("No active Gitflow operation".to_string()))
+                }
+            };
 
-        // Checkout target branch
-        let refname = format!("refs/heads/{}", target_branch);
-        repo.set_head(&refname)?;
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+            // Checkout target branch
+            let refname = format!("refs/heads/{}", target_branch);
+            repo.set_head(&refname)?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
 
-        // Delete the workflow branch
-        let mut branch = repo.find_branch(&branch_to_delete, BranchType::Local)?;
-        branch.delete()?;
+            // Delete the workflow branch
+            let mut branch = repo.find_branch(&branch_to_delete, BranchType::Local)?;
+            branch.delete()?;
 
-        Ok(())
+            Ok(())
+        })
     })
     .await
     .map_err(|e| GitflowError::Git(format!("Task error: {}", e)))?
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use thiserror::Error;
 
+use crate::git::error::GitError;
+
 /// Gitflow operation errors that serialize across the IPC boundary.
 #[derive(Debug, Error, Serialize, Deserialize, Type, Clone)]
 #[serde(tag = "type", content = "data")]
@@ -60,11 +62,24 @@ pub enum GitflowError {
     #[error("Invalid branch name: {0}")]
     InvalidBranchName(String),
 
+    /// Commit signing was requested by config but failed
+    #[error("Signing failed: {0}")]
+    SigningFailed(String),
+
     /// Wrapped git2 error
     #[error("Git error: {0}")]
     Git(String),
 }
 
+impl From<GitError> for GitflowError {
+    fn from(err: GitError) -> Self {
+        match err {
+            GitError::SigningFailed(message) => GitflowError::SigningFailed(message),
+            other => GitflowError::Git(other.to_string()),
+        }
+    }
+}
+
 impl From<git2::Error> for GitflowError {
     fn from(err: git2::Error) -> Self {
         GitflowError::Git(err.message().to_string())
@@ -2,6 +2,7 @@
 
 use git2::{BranchType, Oid, Repository};
 
+use crate::git::signing::create_commit_signed;
 use crate::gitflow::error::GitflowError;
 
 /// Merge source branch into target branch, always creating a merge commit (--no-ff behavior).
@@ -39,7 +40,8 @@ pub fn merge_no_ff(
         let tree_oid = repo.index()?.write_tree()?;
         let tree = repo.find_tree(tree_oid)?;
         let sig = repo.signature()?;
-        let commit_oid = repo.commit(
+        let commit_oid = create_commit_signed(
+            repo,
             Some("HEAD"),
             &sig,
             &sig,
@@ -73,7 +75,8 @@ pub fn merge_no_ff(
     let tree = repo.find_tree(tree_oid)?;
     let sig = repo.signature()?;
 
-    let commit_oid = repo.commit(
+    let commit_oid = create_commit_signed(
+        repo,
         Some("HEAD"),
         &sig,
         &sig,
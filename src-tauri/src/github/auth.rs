@@ -9,6 +9,7 @@
 
 use std::time::Duration;
 
+use super::client::http_client;
 use super::error::GitHubError;
 use super::token;
 use super::types::{AuthResult, DeviceFlowResponse};
@@ -50,7 +51,7 @@ struct GitHubTokenResponse {
 pub async fn github_start_device_flow(
     scopes: Vec<String>,
 ) -> Result<DeviceFlowResponse, GitHubError> {
-    let client = reqwest::Client::new();
+    let client = http_client()?;
     let scope_string = scopes.join(" ");
 
     let resp = client
@@ -108,7 +109,7 @@ pub async fn github_poll_auth(
     // The interval parameter is received from the frontend but not used here
     // since this is a single-poll command. The frontend controls the timing.
     let _ = interval;
-    let client = reqwest::Client::new();
+    let client = http_client()?;
 
     let resp = client
         .post(GITHUB_ACCESS_TOKEN_URL)
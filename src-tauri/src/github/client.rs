@@ -3,7 +3,8 @@
 //! Provides reusable helpers for making authenticated GET requests,
 //! parsing pagination Link headers, and extracting rate limit info.
 //! All GitHub API modules (pulls, issues, etc.) use these helpers
-//! instead of building their own reqwest clients.
+//! instead of building their own reqwest clients; [`http_client`] applies
+//! the git `http.proxy` and `http.sslCAInfo` settings.
 
 use std::time::Duration;
 
@@ -11,6 +12,12 @@ use serde::Serialize;
 
 use super::error::GitHubError;
 use super::token;
+use crate::git::network;
+
+/// HTTP client honouring the git proxy and CA settings.
+pub fn http_client() -> Result<reqwest::Client, GitHubError> {
+    network::http_client().map_err(GitHubError::NetworkError)
+}
 
 /// Make an authenticated GET request to the GitHub REST API.
 ///
@@ -18,7 +25,7 @@ use super::token;
 /// and maps HTTP error statuses to typed `GitHubError` variants.
 pub async fn github_get(path: &str) -> Result<reqwest::Response, GitHubError> {
     let access_token = token::get_token().await?;
-    let client = reqwest::Client::new();
+    let client = http_client()?;
 
     let url = format!("https://api.github.com{}", path);
     let resp = client
@@ -56,7 +63,7 @@ pub async fn github_get_with_params(
     };
 
     let access_token = token::get_token().await?;
-    let client = reqwest::Client::new();
+    let client = http_client()?;
 
     let resp = client
         .get(&url)
@@ -78,7 +85,7 @@ pub async fn github_get_with_params(
 /// may take more time to complete on GitHub's side.
 pub async fn github_post<T: Serialize>(path: &str, body: &T) -> Result<reqwest::Response, GitHubError> {
     let access_token = token::get_token().await?;
-    let client = reqwest::Client::new();
+    let client = http_client()?;
 
     let url = format!("https://api.github.com{}", path);
     let resp = client
@@ -101,7 +108,7 @@ pub async fn github_post<T: Serialize>(path: &str, body: &T) -> Result<reqwest::
 /// Used for operations like merging pull requests.
 pub async fn github_put<T: Serialize>(path: &str, body: &T) -> Result<reqwest::Response, GitHubError> {
     let access_token = token::get_token().await?;
-    let client = reqwest::Client::new();
+    let client = http_client()?;
 
     let url = format!("https://api.github.com{}", path);
     let resp = client
@@ -3,6 +3,7 @@
 //! Fetches rate limit information from the GitHub API
 //! using the authenticated user's token from the keychain.
 
+use super::client::http_client;
 use super::error::GitHubError;
 use super::token;
 use super::types::RateLimitInfo;
@@ -34,7 +35,7 @@ struct RateLimitData {
 #[specta::specta]
 pub async fn github_check_rate_limit() -> Result<RateLimitInfo, GitHubError> {
     let access_token = token::get_token().await?;
-    let client = reqwest::Client::new();
+    let client = http_client()?;
 
     let resp = client
         .get("https://api.github.com/rate_limit")
@@ -10,6 +10,7 @@
 
 use std::time::Duration;
 
+use super::client::http_client;
 use super::error::GitHubError;
 use super::types::{AuthResult, GitHubUser};
 
@@ -34,16 +35,20 @@ pub async fn store_token(token: &str) -> Result<(), GitHubError> {
 /// Retrieve the OAuth token from the OS keychain.
 /// Returns `GitHubError::NotAuthenticated` if no token is stored.
 pub async fn get_token() -> Result<String, GitHubError> {
-    tokio::task::spawn_blocking(|| {
-        let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
-            .map_err(|e| GitHubError::KeychainError(format!("Failed to create entry: {}", e)))?;
-        entry.get_password().map_err(|e| match e {
-            keyring::Error::NoEntry => GitHubError::NotAuthenticated,
-            other => GitHubError::KeychainError(format!("Failed to get token: {}", other)),
-        })
+    tokio::task::spawn_blocking(read_token)
+        .await
+        .map_err(|e| GitHubError::Internal(format!("Task join error: {}", e)))?
+}
+
+/// Blocking variant of [`get_token`], for git credential callbacks that
+/// already run on a blocking thread.
+pub fn read_token() -> Result<String, GitHubError> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
+        .map_err(|e| GitHubError::KeychainError(format!("Failed to create entry: {}", e)))?;
+    entry.get_password().map_err(|e| match e {
+        keyring::Error::NoEntry => GitHubError::NotAuthenticated,
+        other => GitHubError::KeychainError(format!("Failed to get token: {}", other)),
     })
-    .await
-    .map_err(|e| GitHubError::Internal(format!("Task join error: {}", e)))?
 }
 
 /// Delete the OAuth token from the OS keychain.
@@ -113,7 +118,7 @@ pub async fn github_get_auth_status() -> Result<AuthResult, GitHubError> {
     };
 
     // Validate token by calling GitHub API
-    let client = reqwest::Client::new();
+    let client = http_client()?;
     match fetch_github_user(&client, &token).await {
         Ok(user) => Ok(AuthResult {
             authenticated: true,
@@ -6,20 +6,35 @@ mod gitflow;
 use std::sync::Mutex;
 
 use git::{
-    RepositoryState, WatcherState,
+    AutoFetchState, IdentityState, OperationRegistry, RepositoryState, WatcherState,
+    auto_fetch::{get_auto_fetch_status, set_auto_fetch_config},
     branch::{
         batch_delete_branches, checkout_branch, checkout_remote_branch, create_branch,
-        delete_branch, get_branch_ahead_behind, get_recent_checkouts, list_all_branches,
-        list_branches,
+        delete_branch, get_branch_ahead_behind, get_branches_containing, get_recent_checkouts,
+        list_all_branches, list_branches, rename_branch, set_branch_description,
+        set_branch_upstream, unset_branch_upstream,
     },
     browse::{list_repo_files, read_repo_file},
+    cancellation::cancel_operation,
     changelog::generate_changelog_cmd,
-    clone::clone_repository,
+    clone::{clone_repository, register_partial_clone_extension},
     commands::{
         close_repository, get_repo_health_quick, get_repository_status, is_git_repository,
         open_in_terminal, open_repository,
     },
-    config::{get_git_global_config, set_git_global_config},
+    cherry_pick::{
+        abort_cherry_pick, abort_revert, cherry_pick_commits, continue_cherry_pick,
+        continue_revert, revert_commit,
+    },
+    config::{
+        add_config_value, get_config_values, get_git_global_config, list_config_entries,
+        set_config_value, set_git_global_config, unset_config_value, validate_config_entry,
+    },
+    identity::{
+        apply_identity_profile, delete_identity_profile, get_repository_identity,
+        list_identity_profiles, save_identity_profile,
+    },
+    credentials::{answer_credential_prompt, set_credential_prompt_channel},
     commit::{create_commit, get_last_commit_message},
     conventional::{
         get_scope_suggestions, infer_scope_from_staged, suggest_commit_type,
@@ -35,15 +50,37 @@ use git::{
     graph::get_commit_graph,
     history::{get_commit_details, get_commit_history, search_commits},
     merge::{abort_merge, get_merge_status, merge_branch},
+    rebase::{
+        abort_rebase, continue_rebase, get_rebase_todo, skip_rebase, start_interactive_rebase,
+    },
+    reflog::{find_lost_commits, list_reflog, recover_lost_commit},
+    operation::{abort_operation, continue_operation, get_operation_state, skip_operation},
     conflict::{get_conflict_content, list_conflict_files, resolve_conflict_file},
-    remote::{fetch_from_remote, get_remotes, pull_from_remote, push_to_remote},
+    remote::{
+        add_remote, deepen_repository, fetch_all, fetch_from_remote, get_remotes, pull_from_remote, push_to_remote,
+        remove_remote, rename_remote, set_remote_fetch_refspec, set_remote_push_url,
+        set_remote_url, unshallow_repository,
+    },
     staging::{
         get_staging_status, stage_all, stage_file, stage_files, stage_hunks, stage_lines,
         unstage_all, unstage_file, unstage_files, unstage_hunks, unstage_lines,
     },
-    stash::{list_stashes, stash_apply, stash_drop, stash_pop, stash_save},
-    tag::{create_tag, delete_tag, list_tags},
-    undo::{get_undo_info, undo_last_operation},
+    stash::{
+        get_stash_diff, list_stashes, stash_apply, stash_branch, stash_drop, stash_pop,
+        stash_save,
+    },
+    submodule::{
+        init_submodules, list_submodules, set_submodule_branch, sync_submodules,
+        update_submodules,
+    },
+    tag::{
+        compare_tags, create_tag, delete_remote_tags, delete_tag, describe_commit,
+        get_tags_containing, list_tag_groups, list_tags, push_tags,
+    },
+    undo::{
+        get_undo_info, get_undo_journal, redo_operation, restore_journal_entry, undo_last_operation,
+        undo_operation,
+    },
     worktree::{create_worktree, delete_worktree, list_worktrees},
 };
 use extensions::discovery::discover_extensions;
@@ -66,6 +103,9 @@ use tauri_specta::{Builder, collect_commands};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Partial clones must be readable before any repository is opened
+    register_partial_clone_extension();
+
     let builder = Builder::<tauri::Wry>::new().commands(collect_commands![
         // Repository commands
         open_repository,
@@ -102,9 +142,23 @@ pub fn run() {
         get_commit_graph,
         // Remote commands
         get_remotes,
+        add_remote,
+        remove_remote,
+        rename_remote,
+        set_remote_url,
+        set_remote_push_url,
+        set_remote_fetch_refspec,
         fetch_from_remote,
+        fetch_all,
+        get_auto_fetch_status,
+        set_auto_fetch_config,
+        deepen_repository,
+        unshallow_repository,
         push_to_remote,
         pull_from_remote,
+        // Credential commands
+        set_credential_prompt_channel,
+        answer_credential_prompt,
         // Branch commands
         list_branches,
         create_branch,
@@ -115,20 +169,58 @@ pub fn run() {
         batch_delete_branches,
         get_recent_checkouts,
         get_branch_ahead_behind,
+        rename_branch,
+        set_branch_upstream,
+        unset_branch_upstream,
+        set_branch_description,
+        get_branches_containing,
+        // Submodule commands
+        list_submodules,
+        init_submodules,
+        update_submodules,
+        sync_submodules,
+        set_submodule_branch,
         // Stash commands
         list_stashes,
         stash_save,
         stash_apply,
         stash_pop,
         stash_drop,
+        get_stash_diff,
+        stash_branch,
         // Tag commands
         list_tags,
         create_tag,
         delete_tag,
+        list_tag_groups,
+        get_tags_containing,
+        describe_commit,
+        compare_tags,
+        push_tags,
+        delete_remote_tags,
         // Merge commands
         merge_branch,
         get_merge_status,
         abort_merge,
+        // Rebase commands
+        get_rebase_todo,
+        start_interactive_rebase,
+        continue_rebase,
+        skip_rebase,
+        abort_rebase,
+        // Cherry-pick / revert commands
+        cherry_pick_commits,
+        continue_cherry_pick,
+        abort_cherry_pick,
+        revert_commit,
+        continue_revert,
+        abort_revert,
+        // Operation commands
+        get_operation_state,
+        continue_operation,
+        skip_operation,
+        abort_operation,
+        cancel_operation,
         // Conflict commands
         list_conflict_files,
         get_conflict_content,
@@ -156,6 +248,14 @@ pub fn run() {
         // Undo commands
         get_undo_info,
         undo_last_operation,
+        get_undo_journal,
+        undo_operation,
+        redo_operation,
+        restore_journal_entry,
+        // Reflog / recovery commands
+        list_reflog,
+        find_lost_commits,
+        recover_lost_commit,
         // Clone commands
         clone_repository,
         // Init commands
@@ -176,6 +276,18 @@ pub fn run() {
         // Config commands
         get_git_global_config,
         set_git_global_config,
+        list_config_entries,
+        get_config_values,
+        set_config_value,
+        add_config_value,
+        unset_config_value,
+        validate_config_entry,
+        // Identity commands
+        list_identity_profiles,
+        save_identity_profile,
+        delete_identity_profile,
+        apply_identity_profile,
+        get_repository_identity,
         // Extension commands
         discover_extensions,
         extension_fetch_manifest,
@@ -225,9 +337,12 @@ pub fn run() {
         .plugin(tauri_plugin_window_state::Builder::new().build())
         .manage(RepositoryState::new())
         .manage(Mutex::new(WatcherState::new()))
+        .manage(OperationRegistry::new())
         .invoke_handler(builder.invoke_handler())
         .setup(move |app| {
             builder.mount_events(app);
+            app.manage(IdentityState::new(app.path().app_config_dir()?));
+            app.manage(Mutex::new(AutoFetchState::new(app.path().app_config_dir()?)));
 
             // Show window after setup to prevent flash
             if let Some(window) = app.get_webview_window("main") {
@@ -8,7 +8,8 @@ export const commands = {
    *
    * Validates the path exists and is a git repository,
    * then stores it as the current active repository.
-   * Also starts a file watcher to detect external changes.
+   * Also starts a file watcher to detect external changes and the
+   * background auto-fetch scheduler.
    */
   async openRepository(path: string): Promise<Result<RepoStatus, GitError>> {
     try {
@@ -56,7 +57,7 @@ export const commands = {
   /**
    * Close the current repository.
    *
-   * Stops the file watcher and clears the stored repository path.
+   * Stops the file watcher and auto-fetch, and clears the stored repository path.
    */
   async closeRepository(): Promise<Result<null, GitError>> {
     try {
@@ -367,20 +368,27 @@ export const commands = {
    *
    * Creates a commit with the given message from the current index (staged changes).
    * If `amend` is true, replaces the last commit instead of creating a new one.
+   * Output of the repository's commit hooks is streamed to `on_hook_output`.
    *
    * # Errors
    * - `NoStagedChanges` if index is empty (nothing staged)
    * - `SignatureError` if git config lacks user.name/email
+   * - `HookFailed` if a pre-commit or commit-msg hook rejects the commit
    * - Various git2 errors for other failures
    */
   async createCommit(
     message: string,
     amend: boolean,
+    onHookOutput: TAURI_CHANNEL<HookEvent> | null,
   ): Promise<Result<CommitInfo, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("create_commit", { message, amend }),
+        data: await TAURI_INVOKE("create_commit", {
+          message,
+          amend,
+          onHookOutput,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -493,6 +501,108 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * Add a new remote.
+   */
+  async addRemote(
+    name: string,
+    url: string,
+  ): Promise<Result<RemoteInfo, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("add_remote", { name, url }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Remove a remote and its remote-tracking branches.
+   */
+  async removeRemote(name: string): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("remove_remote", { name }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Rename a remote.
+   *
+   * Fetch refspecs that do not follow the default pattern are kept as they
+   * are and listed in `problems`.
+   */
+  async renameRemote(
+    oldName: string,
+    newName: string,
+  ): Promise<Result<RenamedRemote, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("rename_remote", { oldName, newName }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Change a remote's fetch URL.
+   */
+  async setRemoteUrl(
+    name: string,
+    url: string,
+  ): Promise<Result<RemoteInfo, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_remote_url", { name, url }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Set a separate push URL for a remote; `None` pushes to the fetch URL again.
+   */
+  async setRemotePushUrl(
+    name: string,
+    url: string | null,
+  ): Promise<Result<RemoteInfo, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_remote_push_url", { name, url }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Replace a remote's fetch refspecs; `None` restores the default refspec.
+   */
+  async setRemoteFetchRefspec(
+    name: string,
+    refspec: string | null,
+  ): Promise<Result<RemoteInfo, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_remote_fetch_refspec", { name, refspec }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
   /**
    * Fetch from a remote without merging.
    *
@@ -502,11 +612,112 @@ export const commands = {
   async fetchFromRemote(
     remote: string,
     onProgress: TAURI_CHANNEL<SyncProgress>,
+    options: FetchOptions | null,
+    operationId: string | null,
+  ): Promise<Result<SyncResult, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("fetch_from_remote", {
+          remote,
+          onProgress,
+          options,
+          operationId,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Fetch all remotes in parallel, optionally pruning stale refs.
+   */
+  async fetchAll(
+    onProgress: TAURI_CHANNEL<SyncProgress>,
+    options: FetchOptions | null,
+    operationId: string | null,
+  ): Promise<Result<RemoteFetchResult[], GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("fetch_all", {
+          onProgress,
+          options,
+          operationId,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the auto-fetch settings and scheduler state.
+   */
+  async getAutoFetchStatus(): Promise<Result<AutoFetchStatus, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_auto_fetch_status"),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Change the auto-fetch settings and restart the scheduler for the open repository.
+   */
+  async setAutoFetchConfig(
+    config: AutoFetchConfig,
+  ): Promise<Result<AutoFetchStatus, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_auto_fetch_config", { config }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Fetch `by` more commits of history into a shallow repository (`git fetch --deepen`).
+   */
+  async deepenRepository(
+    by: number,
+    onProgress: TAURI_CHANNEL<SyncProgress>,
+    operationId: string | null,
+  ): Promise<Result<SyncResult, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("deepen_repository", {
+          by,
+          onProgress,
+          operationId,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Fetch the complete history of a shallow repository (`git fetch --unshallow`).
+   */
+  async unshallowRepository(
+    onProgress: TAURI_CHANNEL<SyncProgress>,
+    operationId: string | null,
   ): Promise<Result<SyncResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("fetch_from_remote", { remote, onProgress }),
+        data: await TAURI_INVOKE("unshallow_repository", {
+          onProgress,
+          operationId,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -514,18 +725,27 @@ export const commands = {
     }
   },
   /**
-   * Push current branch to a remote.
+   * Push to a remote.
    *
-   * Sends local commits to the remote repository.
+   * Without `options` this pushes the current branch to the same-named
+   * remote branch. See [`PushOptions`] for force-with-lease, upstream
+   * tracking, tags, deletions and renamed remote branches.
    */
   async pushToRemote(
     remote: string,
     onProgress: TAURI_CHANNEL<SyncProgress>,
+    options: PushOptions | null,
+    operationId: string | null,
   ): Promise<Result<SyncResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("push_to_remote", { remote, onProgress }),
+        data: await TAURI_INVOKE("push_to_remote", {
+          remote,
+          onProgress,
+          options,
+          operationId,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -533,19 +753,63 @@ export const commands = {
     }
   },
   /**
-   * Pull from a remote (fetch + merge).
+   * Pull from a remote (fetch + integrate).
    *
-   * Downloads objects from the remote and merges the tracking branch
-   * into the current branch. Fast-forward merges are preferred.
+   * Downloads objects from the remote and integrates the upstream branch
+   * using `options.strategy`, defaulting to `pull.rebase` / `pull.ff`.
+   * A dirty working tree can be stashed and re-applied with `autostash`.
    */
   async pullFromRemote(
     remote: string,
     onProgress: TAURI_CHANNEL<SyncProgress>,
+    options: PullOptions | null,
+    operationId: string | null,
   ): Promise<Result<SyncResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("pull_from_remote", { remote, onProgress }),
+        data: await TAURI_INVOKE("pull_from_remote", {
+          remote,
+          onProgress,
+          options,
+          operationId,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Register the channel on which credential prompts reach the frontend.
+   *
+   * Operations that run out of stored credentials send a [`CredentialPrompt`]
+   * here and wait for `answer_credential_prompt`.
+   */
+  async setCredentialPromptChannel(
+    onPrompt: TAURI_CHANNEL<CredentialPrompt>,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_credential_prompt_channel", { onPrompt }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Answer a credential prompt; `None` declines it.
+   */
+  async answerCredentialPrompt(
+    id: string,
+    answer: CredentialAnswer | null,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("answer_credential_prompt", { id, answer }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -582,12 +846,19 @@ export const commands = {
   },
   /**
    * Switch to an existing branch.
+   *
+   * `mode` (default `Safe`) decides what happens when uncommitted changes
+   * would be overwritten by the target: refuse, only report them, or
+   * stash and re-apply them around the switch.
    */
-  async checkoutBranch(branchName: string): Promise<Result<null, GitError>> {
+  async checkoutBranch(
+    branchName: string,
+    mode: CheckoutMode | null,
+  ): Promise<Result<CheckoutResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("checkout_branch", { branchName }),
+        data: await TAURI_INVOKE("checkout_branch", { branchName, mode }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -698,27 +969,37 @@ export const commands = {
     }
   },
   /**
-   * List all stash entries in the repository.
+   * Rename a local branch, carrying its upstream, description and reflog.
    */
-  async listStashes(): Promise<Result<StashEntry[], GitError>> {
+  async renameBranch(
+    oldName: string,
+    newName: string,
+    force: boolean | null,
+  ): Promise<Result<BranchInfo, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("list_stashes") };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("rename_branch", { oldName, newName, force }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Save current changes to stash.
+   * Track `upstream` (e.g., "origin/main") from a local branch.
    */
-  async stashSave(
-    message: string | null,
-    includeUntracked: boolean,
-  ): Promise<Result<string, GitError>> {
+  async setBranchUpstream(
+    branchName: string,
+    upstream: string,
+  ): Promise<Result<null, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("stash_save", { message, includeUntracked }),
+        data: await TAURI_INVOKE("set_branch_upstream", {
+          branchName,
+          upstream,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -726,13 +1007,15 @@ export const commands = {
     }
   },
   /**
-   * Apply a stash by index without removing it.
+   * Stop tracking an upstream from a local branch.
    */
-  async stashApply(index: number): Promise<Result<null, GitError>> {
+  async unsetBranchUpstream(
+    branchName: string,
+  ): Promise<Result<null, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("stash_apply", { index }),
+        data: await TAURI_INVOKE("unset_branch_upstream", { branchName }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -740,24 +1023,35 @@ export const commands = {
     }
   },
   /**
-   * Apply a stash by index and remove it from the stash list.
+   * Set or clear a local branch's description.
    */
-  async stashPop(index: number): Promise<Result<null, GitError>> {
+  async setBranchDescription(
+    branchName: string,
+    description: string | null,
+  ): Promise<Result<null, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("stash_pop", { index }) };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_branch_description", {
+          branchName,
+          description,
+        }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Drop a stash by index without applying it.
+   * List local and remote branches that contain a commit.
    */
-  async stashDrop(index: number): Promise<Result<null, GitError>> {
+  async getBranchesContaining(
+    oid: string,
+  ): Promise<Result<BranchesContaining, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("stash_drop", { index }),
+        data: await TAURI_INVOKE("get_branches_containing", { oid }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -765,28 +1059,26 @@ export const commands = {
     }
   },
   /**
-   * List all tags in the repository.
+   * List the submodules of the open repository.
    */
-  async listTags(): Promise<Result<TagInfo[], GitError>> {
+  async listSubmodules(): Promise<Result<SubmoduleInfo[], GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("list_tags") };
+      return { status: "ok", data: await TAURI_INVOKE("list_submodules") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Create a new tag.
+   * Initialize submodules (all when `paths` is `None`).
    */
-  async createTag(
-    name: string,
-    message: string | null,
-    targetOid: string | null,
-  ): Promise<Result<TagInfo, GitError>> {
+  async initSubmodules(
+    paths: string[] | null,
+  ): Promise<Result<SubmoduleInfo[], GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("create_tag", { name, message, targetOid }),
+        data: await TAURI_INVOKE("init_submodules", { paths }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -794,26 +1086,39 @@ export const commands = {
     }
   },
   /**
-   * Delete a tag by name.
+   * Clone and check out submodules at their recorded commits, initializing them as needed.
    */
-  async deleteTag(name: string): Promise<Result<null, GitError>> {
+  async updateSubmodules(
+    paths: string[] | null,
+    recursive: boolean,
+    onProgress: TAURI_CHANNEL<SubmoduleProgress>,
+    operationId: string | null,
+  ): Promise<Result<SubmoduleInfo[], GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("delete_tag", { name }) };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("update_submodules", {
+          paths,
+          recursive,
+          onProgress,
+          operationId,
+        }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Merge a source branch into the current branch.
+   * Re-apply submodule URLs from `.gitmodules`.
    */
-  async mergeBranch(
-    sourceBranch: string,
-  ): Promise<Result<MergeResult, GitError>> {
+  async syncSubmodules(
+    recursive: boolean,
+  ): Promise<Result<SubmoduleInfo[], GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("merge_branch", { sourceBranch }),
+        data: await TAURI_INVOKE("sync_submodules", { recursive }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -821,53 +1126,76 @@ export const commands = {
     }
   },
   /**
-   * Get the status of an in-progress merge.
+   * Set the branch a submodule tracks; `None` goes back to the remote's default branch.
    */
-  async getMergeStatus(): Promise<Result<MergeStatus, GitError>> {
+  async setSubmoduleBranch(
+    path: string,
+    branch: string | null,
+  ): Promise<Result<SubmoduleInfo, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("get_merge_status") };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_submodule_branch", { path, branch }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Abort an in-progress merge.
+   * List all stash entries in the repository.
    */
-  async abortMerge(): Promise<Result<null, GitError>> {
+  async listStashes(): Promise<Result<StashEntry[], GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("abort_merge") };
+      return { status: "ok", data: await TAURI_INVOKE("list_stashes") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * List all conflicted file paths from the git index.
+   * Save current changes to stash.
    *
-   * Returns an empty Vec if no conflicts exist (not an error).
+   * `paths` limits the stash to those pathspecs (not combinable with
+   * untracked files); `keep_index` leaves staged changes in place;
+   * `include_ignored` stashes ignored files as well as untracked ones (`--all`).
    */
-  async listConflictFiles(): Promise<Result<string[], GitError>> {
+  async stashSave(
+    message: string | null,
+    includeUntracked: boolean,
+    keepIndex: boolean | null,
+    includeIgnored: boolean | null,
+    paths: string[] | null,
+  ): Promise<Result<string, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("list_conflict_files") };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("stash_save", {
+          message,
+          includeUntracked,
+          keepIndex,
+          includeIgnored,
+          paths,
+        }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Read ours/theirs/base content for a specific conflicted file from index stages.
+   * Apply a stash by index without removing it.
    *
-   * Uses git2 index stages (1=ancestor, 2=ours, 3=theirs) to read clean content
-   * without conflict markers. Returns NotFound if the file is not in the conflict list.
+   * With `restore_index`, changes that were staged are staged again.
    */
-  async getConflictContent(
-    path: string,
-  ): Promise<Result<ConflictContent, GitError>> {
+  async stashApply(
+    index: number,
+    restoreIndex: boolean | null,
+  ): Promise<Result<null, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("get_conflict_content", { path }),
+        data: await TAURI_INVOKE("stash_apply", { index, restoreIndex }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -875,19 +1203,18 @@ export const commands = {
     }
   },
   /**
-   * Write resolved content to a conflicted file, stage it, and clear the conflict.
+   * Apply a stash by index and remove it from the stash list.
    *
-   * Writes the resolved content to the working directory file, then stages it
-   * via `index.add_path()` which automatically clears the conflict entry.
+   * With `restore_index`, changes that were staged are staged again.
    */
-  async resolveConflictFile(
-    path: string,
-    content: string,
+  async stashPop(
+    index: number,
+    restoreIndex: boolean | null,
   ): Promise<Result<null, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("resolve_conflict_file", { path, content }),
+        data: await TAURI_INVOKE("stash_pop", { index, restoreIndex }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -895,23 +1222,13 @@ export const commands = {
     }
   },
   /**
-   * Initialize Gitflow on a repository.
-   *
-   * This command:
-   * 1. Verifies the main branch exists
-   * 2. Creates the develop branch if it doesn't exist
-   * 3. Stores configuration in .git/config for git-flow CLI compatibility
-   * 4. Checks out the develop branch
-   * 5. Optionally pushes develop to origin
+   * Drop a stash by index without applying it.
    */
-  async initGitflow(
-    config: GitflowConfig,
-    pushDevelop: boolean,
-  ): Promise<Result<GitflowInitResult, GitflowError>> {
+  async stashDrop(index: number): Promise<Result<null, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("init_gitflow", { config, pushDevelop }),
+        data: await TAURI_INVOKE("stash_drop", { index }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -919,13 +1236,16 @@ export const commands = {
     }
   },
   /**
-   * Start a new feature branch from develop.
+   * Get the changes in a stash as per-file diffs, untracked files included.
    */
-  async startFeature(name: string): Promise<Result<string, GitflowError>> {
+  async getStashDiff(
+    index: number,
+    contextLines: number | null,
+  ): Promise<Result<FileDiff[], GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("start_feature", { name }),
+        data: await TAURI_INVOKE("get_stash_diff", { index, contextLines }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -933,41 +1253,46 @@ export const commands = {
     }
   },
   /**
-   * Finish the current feature branch, merging to develop.
+   * Create and check out a branch from the commit a stash was made on, then
+   * pop the stash onto it.
    */
-  async finishFeature(): Promise<Result<null, GitflowError>> {
+  async stashBranch(
+    index: number,
+    branchName: string,
+  ): Promise<Result<null, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("finish_feature") };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("stash_branch", { index, branchName }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Start a new release branch from develop.
+   * List all tags in the repository, newest first or by semantic version.
    */
-  async startRelease(version: string): Promise<Result<string, GitflowError>> {
+  async listTags(sort: TagSort | null): Promise<Result<TagInfo[], GitError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("start_release", { version }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("list_tags", { sort }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Finish the current release branch.
-   * Merges to main AND develop, creates version tag, deletes branch.
+   * Create a new tag.
    */
-  async finishRelease(
-    tagMessage: string | null,
-  ): Promise<Result<string, GitflowError>> {
+  async createTag(
+    name: string,
+    message: string | null,
+    targetOid: string | null,
+  ): Promise<Result<TagInfo, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("finish_release", { tagMessage }),
+        data: await TAURI_INVOKE("create_tag", { name, message, targetOid }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -975,30 +1300,26 @@ export const commands = {
     }
   },
   /**
-   * Start a new hotfix branch from main.
+   * Delete a tag by name.
    */
-  async startHotfix(name: string): Promise<Result<string, GitflowError>> {
+  async deleteTag(name: string): Promise<Result<null, GitError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("start_hotfix", { name }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("delete_tag", { name }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Finish the current hotfix branch.
-   * Merges to main AND develop, creates tag, deletes branch.
+   * List tags grouped by major or minor version, highest version first.
    */
-  async finishHotfix(
-    tagMessage: string | null,
-  ): Promise<Result<string, GitflowError>> {
+  async listTagGroups(
+    grouping: TagGrouping | null,
+  ): Promise<Result<TagGroup[], GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("finish_hotfix", { tagMessage }),
+        data: await TAURI_INVOKE("list_tag_groups", { grouping }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1006,61 +1327,70 @@ export const commands = {
     }
   },
   /**
-   * Get current Gitflow status for UI.
+   * Names of the tags that contain a commit.
    */
-  async getGitflowStatus(): Promise<Result<GitflowStatus, GitflowError>> {
+  async getTagsContaining(oid: string): Promise<Result<string[], GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("get_gitflow_status") };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_tags_containing", { oid }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Abort current Gitflow operation, returning to source branch.
+   * Describe a commit (HEAD by default) relative to its nearest tag.
    */
-  async abortGitflow(): Promise<Result<null, GitflowError>> {
+  async describeCommit(
+    oid: string | null,
+  ): Promise<Result<TagDescription | null, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("abort_gitflow") };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("describe_commit", { oid }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Validate a conventional commit message.
-   *
-   * Returns validation result with errors and warnings.
-   */
-  async validateConventionalCommit(message: string): Promise<ValidationResult> {
-    return await TAURI_INVOKE("validate_conventional_commit", { message });
-  },
-  /**
-   * Suggest a commit type based on staged files.
-   *
-   * Analyzes the currently staged files and returns a type suggestion
-   * with confidence level.
+   * Compare two tags: tagger, dates, messages and the commits between them.
    */
-  async suggestCommitType(): Promise<Result<TypeSuggestion, GitError>> {
+  async compareTags(
+    from: string,
+    to: string,
+  ): Promise<Result<TagComparison, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("suggest_commit_type") };
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("compare_tags", { from, to }),
+      };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Get scope suggestions from commit history.
-   *
-   * Extracts scopes used in previous commits, sorted by frequency.
+   * Push tags to a remote; every local tag when `tags` is empty.
    */
-  async getScopeSuggestions(
-    limit: number | null,
-  ): Promise<Result<ScopeSuggestion[], GitError>> {
+  async pushTags(
+    remote: string,
+    tags: string[],
+    onProgress: TAURI_CHANNEL<SyncProgress>,
+    operationId: string | null,
+  ): Promise<Result<SyncResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("get_scope_suggestions", { limit }),
+        data: await TAURI_INVOKE("push_tags", {
+          remote,
+          tags,
+          onProgress,
+          operationId,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1068,13 +1398,23 @@ export const commands = {
     }
   },
   /**
-   * Infer scope from staged files based on common directory.
+   * Delete tags on a remote. Local tags are kept.
    */
-  async inferScopeFromStaged(): Promise<Result<string | null, GitError>> {
+  async deleteRemoteTags(
+    remote: string,
+    tags: string[],
+    onProgress: TAURI_CHANNEL<SyncProgress>,
+    operationId: string | null,
+  ): Promise<Result<SyncResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("infer_scope_from_staged"),
+        data: await TAURI_INVOKE("delete_remote_tags", {
+          remote,
+          tags,
+          onProgress,
+          operationId,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1082,22 +1422,21 @@ export const commands = {
     }
   },
   /**
-   * Generate a changelog from commit history.
+   * Merge a source branch into the current branch.
    *
-   * Generates markdown changelog grouped by commit type.
+   * Runs `pre-merge-commit`, `prepare-commit-msg`, `commit-msg` and
+   * `post-merge` hooks, streaming their output to `on_hook_output`.
    */
-  async generateChangelogCmd(
-    fromRef: string | null,
-    toRef: string | null,
-    version: string | null,
-  ): Promise<Result<ChangelogOutput, GitError>> {
+  async mergeBranch(
+    sourceBranch: string,
+    onHookOutput: TAURI_CHANNEL<HookEvent> | null,
+  ): Promise<Result<MergeResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("generate_changelog_cmd", {
-          fromRef,
-          toRef,
-          version,
+        data: await TAURI_INVOKE("merge_branch", {
+          sourceBranch,
+          onHookOutput,
         }),
       };
     } catch (e) {
@@ -1106,26 +1445,40 @@ export const commands = {
     }
   },
   /**
-   * List all worktrees for the current repository.
+   * Get the status of an in-progress merge.
    */
-  async listWorktrees(): Promise<Result<WorktreeInfo[], GitError>> {
+  async getMergeStatus(): Promise<Result<MergeStatus, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("list_worktrees") };
+      return { status: "ok", data: await TAURI_INVOKE("get_merge_status") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Create a new worktree.
+   * Abort an in-progress merge.
    */
-  async createWorktree(
-    options: CreateWorktreeOptions,
-  ): Promise<Result<WorktreeInfo, GitError>> {
+  async abortMerge(): Promise<Result<null, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("abort_merge") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the default rebase plan for the commits between `base_oid` and HEAD.
+   *
+   * Every non-merge commit is listed oldest first with a `pick` action,
+   * ready to be edited by the user and passed to `start_interactive_rebase`.
+   */
+  async getRebaseTodo(
+    baseOid: string,
+  ): Promise<Result<RebaseTodoItem[], GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("create_worktree", { options }),
+        data: await TAURI_INVOKE("get_rebase_todo", { baseOid }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1133,21 +1486,19 @@ export const commands = {
     }
   },
   /**
-   * Delete a worktree.
+   * Start an interactive rebase of HEAD onto `base_oid` using the given plan.
+   *
+   * Commits of the range that are missing from the plan are dropped.
+   * Stops early on conflicts or `edit` steps; resume with `continue_rebase`.
    */
-  async deleteWorktree(
-    name: string,
-    force: boolean,
-    deleteBranch: boolean,
-  ): Promise<Result<null, GitError>> {
+  async startInteractiveRebase(
+    baseOid: string,
+    todo: RebaseTodoItem[],
+  ): Promise<Result<RebaseProgress, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("delete_worktree", {
-          name,
-          force,
-          deleteBranch,
-        }),
+        data: await TAURI_INVOKE("start_interactive_rebase", { baseOid, todo }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1155,79 +1506,59 @@ export const commands = {
     }
   },
   /**
-   * Get information about what can be undone.
-   * Looks at HEAD reflog to find the previous state.
+   * Continue a stopped rebase after resolving conflicts or amending an `edit` step.
    */
-  async getUndoInfo(): Promise<Result<UndoInfo, GitError>> {
+  async continueRebase(): Promise<Result<RebaseProgress, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("get_undo_info") };
+      return { status: "ok", data: await TAURI_INVOKE("continue_rebase") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Undo the last operation by resetting HEAD to the previous reflog entry.
+   * Skip the stopped rebase step, discarding its changes.
    */
-  async undoLastOperation(): Promise<Result<null, GitError>> {
+  async skipRebase(): Promise<Result<RebaseProgress, GitError>> {
     try {
-      return { status: "ok", data: await TAURI_INVOKE("undo_last_operation") };
+      return { status: "ok", data: await TAURI_INVOKE("skip_rebase") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Clone a Git repository with progress tracking.
-   *
-   * Clones a repository from a URL to a local destination path,
-   * sending progress events through the provided channel.
-   *
-   * # Arguments
-   * * `url` - The repository URL (HTTPS or SSH)
-   * * `destination` - Local path where the repository will be cloned
-   * * `on_progress` - Channel for sending progress events
-   *
-   * # Returns
-   * The path to the cloned repository on success
+   * Abort an in-progress rebase and restore the original branch.
    */
-  async cloneRepository(
-    url: string,
-    destination: string,
-    onProgress: TAURI_CHANNEL<CloneProgress>,
-  ): Promise<Result<string, GitError>> {
+  async abortRebase(): Promise<Result<null, GitError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("clone_repository", {
-          url,
-          destination,
-          onProgress,
-        }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("abort_rebase") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Initialize a new Git repository at the specified path.
-   *
-   * Validates the path exists and is a directory that is not already a git repository,
-   * then creates a new repository with an optional default branch name.
+   * Cherry-pick one or more commits onto the current branch.
    *
-   * # Arguments
-   * * `path` - Directory where the repository will be initialized
-   * * `default_branch` - Optional initial branch name (defaults to "main")
-   */
-  async gitInit(
-    path: string,
-    defaultBranch: string | null,
-  ): Promise<Result<InitResult, GitError>> {
+   * Each revision may be a single commit or an `A..B` range (applied oldest first).
+   * `record_origin` appends a "(cherry picked from commit ...)" line like `git cherry-pick -x`;
+   * `mainline` selects the parent to diff against when picking merge commits.
+   * Stops on the first conflict; resolve the files and call `continue_cherry_pick`.
+   */
+  async cherryPickCommits(
+    revisions: string[],
+    recordOrigin: boolean,
+    mainline: number | null,
+  ): Promise<Result<CherryPickResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("git_init", { path, defaultBranch }),
+        data: await TAURI_INVOKE("cherry_pick_commits", {
+          revisions,
+          recordOrigin,
+          mainline,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1235,54 +1566,40 @@ export const commands = {
     }
   },
   /**
-   * List all available .gitignore template names.
-   *
-   * Attempts to fetch from GitHub API with a 5s timeout, falling back to
-   * bundled templates on any error.
+   * Commit the resolved cherry-pick and apply any remaining commits.
    */
-  async listGitignoreTemplates(): Promise<
-    Result<GitignoreTemplateList, GitError>
-  > {
+  async continueCherryPick(): Promise<Result<CherryPickResult, GitError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("list_gitignore_templates"),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("continue_cherry_pick") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Get the content of a single .gitignore template by name.
-   *
-   * Tries GitHub API first, falls back to bundled templates.
+   * Abort an in-progress cherry-pick, restoring HEAD from before the sequence.
    */
-  async getGitignoreTemplate(
-    name: string,
-  ): Promise<Result<GitignoreTemplate, GitError>> {
+  async abortCherryPick(): Promise<Result<null, GitError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("get_gitignore_template", { name }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("abort_cherry_pick") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Detect project types by scanning for marker files in a directory.
+   * Create a commit that undoes the changes of the given commit.
    *
-   * Returns all detected project types with recommended .gitignore templates.
+   * `mainline` selects the parent to revert against when reverting a merge commit.
    */
-  async detectProjectType(
-    path: string,
-  ): Promise<Result<ProjectDetection, GitError>> {
+  async revertCommit(
+    oid: string,
+    mainline: number | null,
+  ): Promise<Result<RevertResult, GitError>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("detect_project_type", { path }),
+        data: await TAURI_INVOKE("revert_commit", { oid, mainline }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1290,31 +1607,681 @@ export const commands = {
     }
   },
   /**
-   * Write initialization files (.gitignore, README.md, etc.) to a directory.
+   * Commit the resolved revert.
    */
-  async writeInitFiles(
-    path: string,
-    files: InitFile[],
-  ): Promise<Result<null, GitError>> {
+  async continueRevert(): Promise<Result<RevertResult, GitError>> {
     try {
-      return {
-        status: "ok",
-        data: await TAURI_INVOKE("write_init_files", { path, files }),
-      };
+      return { status: "ok", data: await TAURI_INVOKE("continue_revert") };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: "error", error: e as any };
     }
   },
   /**
-   * Fetch NuGet package information by package ID.
-   *
-   * Queries the NuGet Search API and Registration API, returning
-   * combined package metadata.
+   * Abort an in-progress revert and discard its changes.
    */
-  async fetchNugetInfo(
-    packageId: string,
-  ): Promise<Result<NugetPackageInfo, GitError>> {
+  async abortRevert(): Promise<Result<null, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("abort_revert") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the operation currently in progress, if any.
+   */
+  async getOperationState(): Promise<Result<OperationState | null, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_operation_state") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Continue the in-progress operation after resolving conflicts.
+   *
+   * Returns the operation state afterwards, or None once it has completed.
+   */
+  async continueOperation(): Promise<Result<OperationState | null, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("continue_operation") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Skip the stopped step of a rebase, cherry-pick or revert.
+   *
+   * Returns the operation state afterwards, or None once it has completed.
+   */
+  async skipOperation(): Promise<Result<OperationState | null, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("skip_operation") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Abort the in-progress operation and restore the state before it started.
+   */
+  async abortOperation(): Promise<Result<null, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("abort_operation") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Cancel a running clone, fetch, pull, push or submodule update by its id.
+   *
+   * Returns false if the operation already finished.
+   */
+  async cancelOperation(id: string): Promise<Result<boolean, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("cancel_operation", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List all conflicted file paths from the git index.
+   *
+   * Returns an empty Vec if no conflicts exist (not an error).
+   */
+  async listConflictFiles(): Promise<Result<string[], GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("list_conflict_files") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Read ours/theirs/base content for a specific conflicted file from index stages.
+   *
+   * Uses git2 index stages (1=ancestor, 2=ours, 3=theirs) to read clean content
+   * without conflict markers. Returns NotFound if the file is not in the conflict list.
+   */
+  async getConflictContent(
+    path: string,
+  ): Promise<Result<ConflictContent, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_conflict_content", { path }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Write resolved content to a conflicted file, stage it, and clear the conflict.
+   *
+   * Writes the resolved content to the working directory file, then stages it
+   * via `index.add_path()` which automatically clears the conflict entry.
+   */
+  async resolveConflictFile(
+    path: string,
+    content: string,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("resolve_conflict_file", { path, content }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Initialize Gitflow on a repository.
+   *
+   * This command:
+   * 1. Verifies the main branch exists
+   * 2. Creates the develop branch if it doesn't exist
+   * 3. Stores configuration in .git/config for git-flow CLI compatibility
+   * 4. Checks out the develop branch
+   * 5. Optionally pushes develop to origin
+   */
+  async initGitflow(
+    config: GitflowConfig,
+    pushDevelop: boolean,
+  ): Promise<Result<GitflowInitResult, GitflowError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("init_gitflow", { config, pushDevelop }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Start a new feature branch from develop.
+   */
+  async startFeature(name: string): Promise<Result<string, GitflowError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("start_feature", { name }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Finish the current feature branch, merging to develop.
+   */
+  async finishFeature(): Promise<Result<null, GitflowError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("finish_feature") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Start a new release branch from develop.
+   */
+  async startRelease(version: string): Promise<Result<string, GitflowError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("start_release", { version }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Finish the current release branch.
+   * Merges to main AND develop, creates version tag, deletes branch.
+   */
+  async finishRelease(
+    tagMessage: string | null,
+  ): Promise<Result<string, GitflowError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("finish_release", { tagMessage }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Start a new hotfix branch from main.
+   */
+  async startHotfix(name: string): Promise<Result<string, GitflowError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("start_hotfix", { name }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Finish the current hotfix branch.
+   * Merges to main AND develop, creates tag, deletes branch.
+   */
+  async finishHotfix(
+    tagMessage: string | null,
+  ): Promise<Result<string, GitflowError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("finish_hotfix", { tagMessage }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get current Gitflow status for UI.
+   */
+  async getGitflowStatus(): Promise<Result<GitflowStatus, GitflowError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_gitflow_status") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Abort current Gitflow operation, returning to source branch.
+   */
+  async abortGitflow(): Promise<Result<null, GitflowError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("abort_gitflow") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Validate a conventional commit message.
+   *
+   * Returns validation result with errors and warnings.
+   */
+  async validateConventionalCommit(message: string): Promise<ValidationResult> {
+    return await TAURI_INVOKE("validate_conventional_commit", { message });
+  },
+  /**
+   * Suggest a commit type based on staged files.
+   *
+   * Analyzes the currently staged files and returns a type suggestion
+   * with confidence level.
+   */
+  async suggestCommitType(): Promise<Result<TypeSuggestion, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("suggest_commit_type") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get scope suggestions from commit history.
+   *
+   * Extracts scopes used in previous commits, sorted by frequency.
+   */
+  async getScopeSuggestions(
+    limit: number | null,
+  ): Promise<Result<ScopeSuggestion[], GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_scope_suggestions", { limit }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Infer scope from staged files based on common directory.
+   */
+  async inferScopeFromStaged(): Promise<Result<string | null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("infer_scope_from_staged"),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Generate a changelog from commit history.
+   *
+   * Generates markdown changelog grouped by commit type.
+   */
+  async generateChangelogCmd(
+    fromRef: string | null,
+    toRef: string | null,
+    version: string | null,
+  ): Promise<Result<ChangelogOutput, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("generate_changelog_cmd", {
+          fromRef,
+          toRef,
+          version,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List all worktrees for the current repository.
+   */
+  async listWorktrees(): Promise<Result<WorktreeInfo[], GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("list_worktrees") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Create a new worktree.
+   */
+  async createWorktree(
+    options: CreateWorktreeOptions,
+  ): Promise<Result<WorktreeInfo, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("create_worktree", { options }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Delete a worktree.
+   */
+  async deleteWorktree(
+    name: string,
+    force: boolean,
+    deleteBranch: boolean,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("delete_worktree", {
+          name,
+          force,
+          deleteBranch,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get information about what can be undone.
+   * Uses the newest journal entry, falling back to the HEAD reflog when the
+   * journal is empty (e.g. for changes made outside FlowForge).
+   */
+  async getUndoInfo(): Promise<Result<UndoInfo, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_undo_info") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Undo the last operation.
+   *
+   * Undoes the newest journal entry; without one, resets HEAD to the previous
+   * reflog entry.
+   */
+  async undoLastOperation(): Promise<Result<null, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("undo_last_operation") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the undo/redo stack for the current repository.
+   */
+  async getUndoJournal(): Promise<Result<UndoJournal, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("get_undo_journal") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Undo the most recent journal entry.
+   */
+  async undoOperation(): Promise<Result<UndoJournal, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("undo_operation") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Redo the most recently undone journal entry.
+   */
+  async redoOperation(): Promise<Result<UndoJournal, GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("redo_operation") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Restore the repository to the state around a specific journal entry.
+   *
+   * An applied entry is undone together with every entry after it; an undone
+   * entry is redone together with every undone entry before it.
+   */
+  async restoreJournalEntry(
+    id: number,
+  ): Promise<Result<UndoJournal, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("restore_journal_entry", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List the reflog of a ref, newest first.
+   *
+   * `ref_name` may be "HEAD", a full ref name or a short branch name.
+   */
+  async listReflog(
+    refName: string,
+    limit: number | null,
+  ): Promise<Result<ReflogEntry[], GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("list_reflog", { refName, limit }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Scan for commits no ref reaches any more, including dropped stashes.
+   *
+   * Only the tip of each lost line of history is returned, newest first.
+   */
+  async findLostCommits(
+    limit: number | null,
+  ): Promise<Result<LostCommit[], GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("find_lost_commits", { limit }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Create a branch pointing at a lost commit.
+   */
+  async recoverLostCommit(
+    oid: string,
+    branchName: string,
+  ): Promise<Result<BranchInfo, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("recover_lost_commit", { oid, branchName }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Clone a Git repository with progress tracking.
+   *
+   * Clones a repository from a URL to a local destination path,
+   * sending progress events through the provided channel.
+   *
+   * # Arguments
+   * * `url` - The repository URL (HTTPS or SSH)
+   * * `destination` - Local path where the repository will be cloned
+   * * `on_progress` - Channel for sending progress events
+   * * `options` - Optional settings: depth, branch, partial clone filter,
+   * bare/mirror and submodule recursion
+   * * `operation_id` - Id under which `cancel_operation` can stop the clone
+   *
+   * # Returns
+   * The path to the cloned repository on success
+   */
+  async cloneRepository(
+    url: string,
+    destination: string,
+    onProgress: TAURI_CHANNEL<CloneProgress>,
+    options: CloneOptions | null,
+    operationId: string | null,
+  ): Promise<Result<string, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("clone_repository", {
+          url,
+          destination,
+          onProgress,
+          options,
+          operationId,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Initialize a new Git repository at the specified path.
+   *
+   * Validates the path exists and is a directory that is not already a git repository,
+   * then creates a new repository with an optional default branch name.
+   *
+   * # Arguments
+   * * `path` - Directory where the repository will be initialized
+   * * `default_branch` - Optional initial branch name (defaults to "main")
+   */
+  async gitInit(
+    path: string,
+    defaultBranch: string | null,
+  ): Promise<Result<InitResult, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("git_init", { path, defaultBranch }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List all available .gitignore template names.
+   *
+   * Attempts to fetch from GitHub API with a 5s timeout, falling back to
+   * bundled templates on any error.
+   */
+  async listGitignoreTemplates(): Promise<
+    Result<GitignoreTemplateList, GitError>
+  > {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("list_gitignore_templates"),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get the content of a single .gitignore template by name.
+   *
+   * Tries GitHub API first, falls back to bundled templates.
+   */
+  async getGitignoreTemplate(
+    name: string,
+  ): Promise<Result<GitignoreTemplate, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_gitignore_template", { name }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Detect project types by scanning for marker files in a directory.
+   *
+   * Returns all detected project types with recommended .gitignore templates.
+   */
+  async detectProjectType(
+    path: string,
+  ): Promise<Result<ProjectDetection, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("detect_project_type", { path }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Write initialization files (.gitignore, README.md, etc.) to a directory.
+   */
+  async writeInitFiles(
+    path: string,
+    files: InitFile[],
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("write_init_files", { path, files }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Fetch NuGet package information by package ID.
+   *
+   * Queries the NuGet Search API and Registration API, returning
+   * combined package metadata.
+   */
+  async fetchNugetInfo(
+    packageId: string,
+  ): Promise<Result<NugetPackageInfo, GitError>> {
     try {
       return {
         status: "ok",
@@ -1435,6 +2402,190 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  /**
+   * List every config entry with its level and origin file.
+   *
+   * Without an open repository only the system, XDG and global levels are listed.
+   */
+  async listConfigEntries(): Promise<Result<ConfigEntry[], GitError>> {
+    try {
+      return { status: "ok", data: await TAURI_INVOKE("list_config_entries") };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Get every value of a key, optionally only those at one level.
+   *
+   * The effective value is the last entry.
+   */
+  async getConfigValues(
+    key: string,
+    level: ConfigLevel | null,
+  ): Promise<Result<ConfigEntry[], GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_config_values", { key, level }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Set a key at a level, replacing its value there.
+   *
+   * Fails for keys with several values at that level; unset them first.
+   */
+  async setConfigValue(
+    key: string,
+    value: string,
+    level: ConfigLevel,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("set_config_value", { key, value, level }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Add a value to a multi-valued key at a level (`git config --add`).
+   */
+  async addConfigValue(
+    key: string,
+    value: string,
+    level: ConfigLevel,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("add_config_value", { key, value, level }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Remove a key at a level. `all` removes every value of a multi-valued key.
+   */
+  async unsetConfigValue(
+    key: string,
+    level: ConfigLevel,
+    all: boolean | null,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("unset_config_value", { key, level, all }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Check a key and value before saving, e.g. while the user types.
+   */
+  async validateConfigEntry(
+    key: string,
+    value: string,
+  ): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("validate_config_entry", { key, value }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * List the stored identity profiles.
+   */
+  async listIdentityProfiles(): Promise<Result<IdentityProfile[], GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("list_identity_profiles"),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Create or update a profile and rewrite the managed `includeIf` blocks.
+   *
+   * A profile with an empty or unknown id is added; its id is returned.
+   */
+  async saveIdentityProfile(
+    profile: IdentityProfile,
+  ): Promise<Result<IdentityProfile, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("save_identity_profile", { profile }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Delete a profile, its repository assignments and its `includeIf` blocks.
+   *
+   * Local configs it was applied to keep their `user.*` values.
+   */
+  async deleteIdentityProfile(id: string): Promise<Result<null, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("delete_identity_profile", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * Apply a profile to the open repository: write its identity into the
+   * local config and remember the assignment for the commit check.
+   */
+  async applyIdentityProfile(
+    id: string,
+  ): Promise<Result<RepositoryIdentity, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("apply_identity_profile", { id }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  /**
+   * The open repository's effective identity and the profile assigned to it.
+   */
+  async getRepositoryIdentity(): Promise<Result<RepositoryIdentity, GitError>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("get_repository_identity"),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
   /**
    * Discover extensions by scanning subdirectories for `flowforge.extension.json` manifests.
    *
@@ -1461,14 +2612,19 @@ export const commands = {
    *
    * Clones the repo to a temp directory, reads the manifest file,
    * and returns both the manifest JSON and the temp path for subsequent install.
+   * The clone can be stopped with `cancel_operation(operation_id)`.
    */
   async extensionFetchManifest(
     gitUrl: string,
+    operationId: string | null,
   ): Promise<Result<ExtensionFetchResult, string>> {
     try {
       return {
         status: "ok",
-        data: await TAURI_INVOKE("extension_fetch_manifest", { gitUrl }),
+        data: await TAURI_INVOKE("extension_fetch_manifest", {
+          gitUrl,
+          operationId,
+        }),
       };
     } catch (e) {
       if (e instanceof Error) throw e;
@@ -1914,6 +3070,34 @@ export type AuthResult = {
   avatarUrl: string | null;
   scopes: string[];
 };
+/**
+ * User-facing auto-fetch settings.
+ */
+export type AutoFetchConfig = {
+  enabled: boolean;
+  /**
+   * Seconds between fetches (at least 60)
+   */
+  intervalSecs: number;
+};
+/**
+ * Current scheduler state, for display in the UI.
+ */
+export type AutoFetchStatus = {
+  config: AutoFetchConfig;
+  /**
+   * Why fetching is paused (network unavailable), if it is
+   */
+  pausedReason: string | null;
+  /**
+   * Consecutive authentication failures driving the backoff
+   */
+  authFailures: number;
+  /**
+   * Seconds until the next attempt
+   */
+  nextFetchSecs: number;
+};
 /**
  * Result of a batch branch deletion operation.
  */
@@ -2025,6 +3209,10 @@ export type BranchInfo = {
    * Remote name (e.g., "origin") for remote branches, None for local
    */
   remoteName: string | null;
+  /**
+   * `branch.<name>.description` for local branches
+   */
+  description: string | null;
 };
 /**
  * Branch information for pre-filling the Create PR form.
@@ -2063,6 +3251,19 @@ export type BranchType =
    * Other branches that don't follow Gitflow naming
    */
   | "other";
+/**
+ * Local and remote-tracking branches whose history contains a commit.
+ */
+export type BranchesContaining = {
+  /**
+   * Local branch names, alphabetically
+   */
+  local: string[];
+  /**
+   * Remote branch names (e.g., "origin/main"), alphabetically
+   */
+  remote: string[];
+};
 /**
  * A commit entry for the changelog.
  */
@@ -2088,26 +3289,147 @@ export type ChangelogCommit = {
    */
   author: string;
   /**
-   * Commit date in ISO format.
+   * Commit date in ISO format.
+   */
+  date: string;
+};
+/**
+ * Output from changelog generation.
+ */
+export type ChangelogOutput = {
+  /**
+   * Rendered markdown changelog.
+   */
+  markdown: string;
+  /**
+   * Total number of commits included.
+   */
+  commitCount: number;
+  /**
+   * Commits grouped by type.
+   */
+  groups: CommitGroup[];
+};
+/**
+ * How `checkout_branch` treats uncommitted changes that the target
+ * branch would overwrite.
+ */
+export type CheckoutMode =
+  /**
+   * Refuse to switch when changes would be overwritten
+   */
+  | "safe"
+  /**
+   * Only report what would conflict; never switch
+   */
+  | "preview"
+  /**
+   * Stash conflicting changes, switch, then re-apply them
+   */
+  | "autostash";
+/**
+ * Outcome of `checkout_branch`.
+ */
+export type CheckoutResult = {
+  /**
+   * Whether HEAD now points at the target branch
+   */
+  switched: boolean;
+  /**
+   * Uncommitted files (including untracked) before the checkout
+   */
+  dirtyFiles: string[];
+  /**
+   * Dirty files the target branch would overwrite
+   */
+  conflicts: string[];
+  /**
+   * Whether local changes were stashed and re-applied
+   */
+  autostashed: boolean;
+  /**
+   * Files left conflicted by re-applying the autostash; the stash is kept
+   * (as stash@{0}) when this is not empty
+   */
+  reapplyConflicts: string[];
+};
+/**
+ * Result of a cherry-pick operation.
+ */
+export type CherryPickResult = {
+  /**
+   * Whether every requested commit was applied
+   */
+  success: boolean;
+  /**
+   * OIDs of the commits created, in order
+   */
+  createdOids: string[];
+  /**
+   * OIDs of commits skipped because their changes were already present
+   */
+  skippedOids: string[];
+  /**
+   * True if the operation stopped on conflicts
+   */
+  hasConflicts: boolean;
+  /**
+   * List of conflicted file paths
+   */
+  conflictedFiles: string[];
+  /**
+   * OID of the commit that could not be applied cleanly
+   */
+  stoppedOid: string | null;
+  /**
+   * Number of commits still waiting to be applied after the stopped one
+   */
+  remaining: number;
+};
+/**
+ * Partial clone filters.
+ */
+export type CloneFilter =
+  /**
+   * Download file contents only when needed (`--filter=blob:none`)
+   */
+  | "blobless"
+  /**
+   * Download trees and file contents only when needed (`--filter=tree:0`)
+   */
+  | "treeless";
+/**
+ * Optional clone settings.
+ */
+export type CloneOptions = {
+  /**
+   * Initialize and check out submodules, recursively (`--recurse-submodules`)
+   */
+  recurseSubmodules: boolean;
+  /**
+   * Only fetch this many commits of history (`--depth`)
+   */
+  depth: number | null;
+  /**
+   * Branch to check out instead of the remote's default (`--branch`)
+   */
+  branch: string | null;
+  /**
+   * Only fetch the checked-out branch (`--single-branch`)
    */
-  date: string;
-};
-/**
- * Output from changelog generation.
- */
-export type ChangelogOutput = {
+  singleBranch: boolean;
   /**
-   * Rendered markdown changelog.
+   * Partial clone filter; needs the `git` command line to be installed
    */
-  markdown: string;
+  filter: CloneFilter | null;
   /**
-   * Total number of commits included.
+   * Clone without a working tree (`--bare`)
    */
-  commitCount: number;
+  bare: boolean;
   /**
-   * Commits grouped by type.
+   * Bare clone that mirrors every ref of the remote (`--mirror`)
    */
-  groups: CommitGroup[];
+  mirror: boolean;
 };
 /**
  * Progress events for clone operations.
@@ -2137,10 +3459,18 @@ export type CloneProgress =
       event: "checkout";
       data: { current: number; total: number; path: string };
     }
+  /**
+   * Cloning and checking out a submodule
+   */
+  | { event: "submodule"; data: { path: string } }
   /**
    * Clone completed successfully
    */
-  | { event: "finished"; data: { path: string } };
+  | { event: "finished"; data: { path: string } }
+  /**
+   * Clone was cancelled and the partial clone removed
+   */
+  | { event: "cancelled" };
 /**
  * Comment info for frontend display.
  */
@@ -2174,6 +3504,10 @@ export type CommitDetails = {
   committerTimestampMs: number;
   parentOids: string[];
   filesChanged: FileChanged[];
+  /**
+   * Signature verification result
+   */
+  signature: CommitSignature;
 };
 /**
  * Complete commit graph with nodes and edges for visualization.
@@ -2228,6 +3562,25 @@ export type CommitInfo = {
    * Commit message
    */
   message: string;
+  /**
+   * Set when the commit identity differs from the identity profile
+   * assigned to the repository
+   */
+  identityWarning: string | null;
+};
+/**
+ * Signature information attached to commits in history, details and the graph.
+ */
+export type CommitSignature = {
+  status: SignatureStatus;
+  /**
+   * Signer identity (gpg user id or ssh principal), when known
+   */
+  signer: string | null;
+  /**
+   * Key id (gpg long key id or ssh key fingerprint), when known
+   */
+  keyId: string | null;
 };
 /**
  * Summary of a commit for list display.
@@ -2242,6 +3595,10 @@ export type CommitSummary = {
    * Unix timestamp in milliseconds (safe for JS Number up to year 275760)
    */
   timestampMs: number;
+  /**
+   * Signature verification result
+   */
+  signature: CommitSignature;
 };
 /**
  * Commit type enumeration.
@@ -2262,6 +3619,60 @@ export type CommitType =
  * Confidence level for type inference.
  */
 export type Confidence = "high" | "medium" | "low";
+/**
+ * One value of a config key and where it came from.
+ */
+export type ConfigEntry = {
+  /**
+   * Key in canonical form (`section.subsection.name`).
+   */
+  name: string;
+  /**
+   * Value, or None for a bare boolean key like `[core] bare`.
+   */
+  value: string | null;
+  level: ConfigLevel;
+  /**
+   * File of the level the entry came from, if known.
+   */
+  origin: string | null;
+  /**
+   * Whether the entry came from a file pulled in with `include`/`includeIf`.
+   */
+  included: boolean;
+};
+/**
+ * A git config level, from lowest to highest precedence.
+ */
+export type ConfigLevel =
+  /**
+   * Windows `%PROGRAMDATA%\Git\config`.
+   */
+  | "programData"
+  /**
+   * `/etc/gitconfig`.
+   */
+  | "system"
+  /**
+   * `~/.config/git/config`.
+   */
+  | "xdg"
+  /**
+   * `~/.gitconfig`.
+   */
+  | "global"
+  /**
+   * `.git/config`.
+   */
+  | "local"
+  /**
+   * `config.worktree` of the current worktree, with `extensions.worktreeConfig`.
+   */
+  | "worktree"
+  /**
+   * Values set by the application at runtime.
+   */
+  | "app";
 /**
  * Content from all three sides of a merge conflict.
  */
@@ -2350,6 +3761,45 @@ export type CreateWorktreeOptions = {
    */
   createBranch: boolean;
 };
+/**
+ * The user's answer to a credential prompt.
+ */
+export type CredentialAnswer = {
+  /**
+   * Ignored for passphrase and host key prompts
+   */
+  username: string | null;
+  /**
+   * Password, token or key passphrase; ignored for host key prompts
+   */
+  secret: string;
+};
+/**
+ * A credential prompt sent to the frontend.
+ */
+export type CredentialPrompt = {
+  /**
+   * Pass to `answer_credential_prompt`
+   */
+  id: string;
+  url: string;
+  kind: CredentialPromptKind;
+};
+/**
+ * What the frontend is asked for.
+ */
+export type CredentialPromptKind =
+  | { type: "usernamePassword"; username: string | null }
+  | { type: "sshKeyPassphrase"; key_path: string }
+  /**
+   * An SSH host not in `known_hosts`; any answer trusts the key
+   */
+  | {
+      type: "unknownHostKey";
+      host: string;
+      key_type: string;
+      fingerprint: string;
+    };
 /**
  * Daily commit count for a specific date.
  */
@@ -2543,6 +3993,33 @@ export type ExtensionToolbarContribution = {
    */
   priority: number | null;
 };
+/**
+ * How to fetch; unset fields fall back to the repository's configuration.
+ */
+export type FetchOptions = {
+  /**
+   * Delete remote-tracking refs that no longer exist on the remote
+   * (`fetch.prune` / `remote.<name>.prune` when unset)
+   */
+  prune: boolean | null;
+  /**
+   * Also delete local tags that no longer exist on the remote
+   * (`fetch.pruneTags` / `remote.<name>.pruneTags` when unset)
+   */
+  pruneTags: boolean | null;
+  /**
+   * Tag following (`remote.<name>.tagOpt` when unset)
+   */
+  tags: TagFollowing | null;
+  /**
+   * Limit history to this many commits from the remote tips (`--depth`)
+   */
+  depth: number | null;
+  /**
+   * Fetch the complete history of a shallow repository (`--unshallow`)
+   */
+  unshallow: boolean;
+};
 /**
  * A single file change with its status and optional diff stats.
  */
@@ -2551,6 +4028,10 @@ export type FileChange = {
   status: FileStatus;
   additions: number | null;
   deletions: number | null;
+  /**
+   * Pointer change when the path is a submodule
+   */
+  submodule: SubmoduleChange | null;
 };
 /**
  * A file changed in a commit.
@@ -2560,6 +4041,10 @@ export type FileChanged = {
   status: string;
   additions: number;
   deletions: number;
+  /**
+   * Pointer change when the path is a submodule
+   */
+  submodule: SubmoduleChange | null;
 };
 /**
  * Complete file diff with old/new content for Monaco DiffEditor.
@@ -2571,6 +4056,10 @@ export type FileDiff = {
   hunks: DiffHunk[];
   isBinary: boolean;
   language: string;
+  /**
+   * Pointer change when the path is a submodule
+   */
+  submodule: SubmoduleChange | null;
 };
 /**
  * Status of a file in the working directory or index.
@@ -2618,13 +4107,26 @@ export type GitError =
   | { type: "TagNotFound"; message: string }
   | { type: "NoMergeInProgress" }
   | { type: "FileNotConflicted"; message: string }
+  | { type: "UnresolvedConflicts"; message: string }
+  | { type: "NoRebaseInProgress" }
+  | { type: "OperationInProgress"; message: string }
+  | { type: "InvalidRebasePlan"; message: string }
+  | { type: "NoOperationInProgress"; message: string }
+  | { type: "MainlineRequired"; message: string }
+  | { type: "UnsupportedOperation"; message: string }
+  | { type: "Cancelled" }
+  | { type: "HookFailed"; message: string }
+  | { type: "SigningFailed"; message: string }
+  | { type: "SubmoduleNotFound"; message: string }
   | { type: "InvalidUrl"; message: string }
   | { type: "PathExists"; message: string }
   | { type: "CloneFailed"; message: string }
   | { type: "InvalidPath"; message: string }
   | { type: "HunkIndexOutOfRange"; message: number }
   | { type: "LineRangeInvalid"; message: string }
-  | { type: "BinaryPartialStaging" };
+  | { type: "BinaryPartialStaging" }
+  | { type: "InvalidConfigKey"; message: string }
+  | { type: "InvalidConfigValue"; message: string };
 /**
  * Snapshot of relevant global git configuration values.
  */
@@ -2783,6 +4285,10 @@ export type GitflowError =
    * Invalid branch name for Gitflow
    */
   | { type: "InvalidBranchName"; data: string }
+  /**
+   * Commit signing was requested by config but failed
+   */
+  | { type: "SigningFailed"; data: string }
   /**
    * Wrapped git2 error
    */
@@ -2910,6 +4416,50 @@ export type GraphNode = {
    * The "ideological branch" name that owns this commit for coloring
    */
   ideologicalBranch: string;
+  /**
+   * Signature verification result
+   */
+  signature: CommitSignature;
+};
+/**
+ * Progress events emitted while hooks run.
+ */
+export type HookEvent =
+  | { event: "started"; data: { hook: string } }
+  | { event: "output"; data: { hook: string; line: string } }
+  | { event: "finished"; data: { hook: string; success: boolean } };
+/**
+ * A commit identity the user can switch between.
+ */
+export type IdentityProfile = {
+  /**
+   * Stable identifier; derived from the label when saving a new profile
+   */
+  id?: string;
+  /**
+   * Display name of the profile (e.g. "Work")
+   */
+  label: string;
+  /**
+   * Value for user.name
+   */
+  userName: string;
+  /**
+   * Value for user.email
+   */
+  userEmail: string;
+  /**
+   * Value for user.signingKey, if commits are signed
+   */
+  signingKey: string | null;
+  /**
+   * GitHub login the profile pushes as
+   */
+  githubAccount: string | null;
+  /**
+   * Directories whose repositories use this profile via `includeIf "gitdir:"`
+   */
+  directories?: string[];
 };
 export type InitFile = { filename: string; content: string };
 /**
@@ -2970,6 +4520,47 @@ export type IssueSummary = {
   htmlUrl: string;
   commentCount: number;
 };
+/**
+ * An entry of the undo journal, as shown to the user.
+ */
+export type JournalEntry = {
+  /**
+   * Stable identifier used by `restore_journal_entry`
+   */
+  id: number;
+  /**
+   * What the command did (e.g. "Delete branch 'feature'")
+   */
+  description: string;
+  /**
+   * When the command ran, in milliseconds since epoch (safe for JS Number)
+   */
+  timestampMs: number;
+  /**
+   * HEAD commit before the command
+   */
+  headBefore: string | null;
+  /**
+   * HEAD commit after the command
+   */
+  headAfter: string | null;
+  /**
+   * Full names of refs the command created, moved or deleted
+   */
+  changedRefs: string[];
+  /**
+   * Whether the command changed the index
+   */
+  indexChanged: boolean;
+  /**
+   * Whether the command changed the stash list
+   */
+  stashChanged: boolean;
+  /**
+   * True if the entry has been undone (it is on the redo stack)
+   */
+  undone: boolean;
+};
 /**
  * Label info for frontend display.
  */
@@ -3001,6 +4592,29 @@ export type LastCommitMessage = {
  * A contiguous range of lines for partial staging operations.
  */
 export type LineRange = { start: number; end: number };
+/**
+ * A commit that is no longer reachable from any ref.
+ */
+export type LostCommit = {
+  commit: CommitSummary;
+  kind: LostCommitKind;
+  /**
+   * Reflog that still mentions the commit (e.g. "HEAD"), if any
+   */
+  foundInReflog: string | null;
+};
+/**
+ * Why a commit is considered lost.
+ */
+export type LostCommitKind =
+  /**
+   * A regular commit no ref reaches
+   */
+  | "commit"
+  /**
+   * A stash that was dropped or cleared
+   */
+  | "stash";
 /**
  * Result of merge analysis.
  */
@@ -3087,7 +4701,71 @@ export type NugetPackageInfo = {
   tags: string[];
   nugetUrl: string;
 };
+/**
+ * Kind of operation currently in progress.
+ */
+export type OperationKind =
+  | "merge"
+  | "rebase"
+  | "cherryPick"
+  | "revert"
+  | "bisect"
+  /**
+   * Applying patches with `git am`
+   */
+  | "applyMailbox";
+/**
+ * State of an in-progress operation.
+ */
+export type OperationState = {
+  /**
+   * Which operation is in progress
+   */
+  kind: OperationKind;
+  /**
+   * Step currently being applied (1-based), for multi-step operations
+   */
+  currentStep: number | null;
+  /**
+   * Total number of steps, for multi-step operations
+   */
+  totalSteps: number | null;
+  /**
+   * Commit the operation is replaying onto (rebase, am)
+   */
+  onto: string | null;
+  /**
+   * Commit being merged, picked or reverted
+   */
+  target: string | null;
+  /**
+   * Branch the operation was started from (rebase, bisect)
+   */
+  headName: string | null;
+  /**
+   * List of conflicted file paths
+   */
+  conflictedFiles: string[];
+  /**
+   * Whether the stopped step can be skipped
+   */
+  canSkip: boolean;
+};
 export type ProjectDetection = { detectedTypes: DetectedProject[] };
+/**
+ * Pull settings; unset fields fall back to git config.
+ */
+export type PullOptions = {
+  /**
+   * Strategy (defaults to `branch.<name>.rebase`, `pull.rebase` and `pull.ff`)
+   */
+  strategy: PullStrategy | null;
+  /**
+   * Stash local changes before integrating and re-apply them afterwards
+   * (defaults to `rebase.autoStash` / `merge.autoStash`)
+   */
+  autostash: boolean | null;
+};
 /**
  * Full pull request detail with body, stats, and comments.
  */
@@ -3143,13 +4821,175 @@ export type PullRequestSummary = {
   commentCount: number;
 };
 /**
- * Rate limit information from the GitHub API.
+ * How pulled changes are integrated into the current branch.
+ */
+export type PullStrategy =
+  /**
+   * Only fast-forward; fail if the branches have diverged
+   */
+  | "ffOnly"
+  /**
+   * Fast-forward when possible, otherwise create a merge commit
+   */
+  | "merge"
+  /**
+   * Replay local commits on top of the upstream branch
+   */
+  | "rebase"
+  /**
+   * Like rebase, but keep local merge commits
+   */
+  | "rebaseMerges";
+/**
+ * What to push; every field is optional.
+ *
+ * The default pushes the current branch to the same-named remote branch,
+ * like a plain `git push <remote> <branch>`.
+ */
+export type PushOptions = {
+  /**
+   * Local branch to push (defaults to the current branch)
+   */
+  branch: string | null;
+  /**
+   * Remote branch name to push to (defaults to the local branch name)
+   */
+  remoteBranch: string | null;
+  /**
+   * Don't push a branch, only the tags and deletions below
+   */
+  skipBranch: boolean;
+  /**
+   * Force the branch update, but only if the remote branch still points
+   * where our remote-tracking ref says (`--force-with-lease`)
+   */
+  forceWithLease: boolean;
+  /**
+   * Record the remote branch as the upstream of the pushed branch
+   */
+  setUpstream: boolean;
+  /**
+   * Push every local tag
+   */
+  allTags: boolean;
+  /**
+   * Push these tags
+   */
+  tags: string[];
+  /**
+   * Delete these branches on the remote
+   */
+  deleteBranches: string[];
+  /**
+   * Delete these tags on the remote
+   */
+  deleteTags: string[];
+};
+/**
+ * Rate limit information from the GitHub API.
+ */
+export type RateLimitInfo = {
+  limit: number;
+  remaining: number;
+  reset: number;
+  used: number;
+};
+/**
+ * Action applied to a commit in a rebase plan.
+ */
+export type RebaseAction =
+  /**
+   * Use the commit as-is
+   */
+  | "pick"
+  /**
+   * Use the commit with a new message
+   */
+  | "reword"
+  /**
+   * Use the commit, then stop so it can be amended
+   */
+  | "edit"
+  /**
+   * Meld into the previous commit, combining both messages
+   */
+  | "squash"
+  /**
+   * Meld into the previous commit, keeping only the previous message
+   */
+  | "fixup"
+  /**
+   * Remove the commit
+   */
+  | "drop";
+/**
+ * Result of starting or resuming a rebase.
+ */
+export type RebaseProgress = {
+  /**
+   * Why the rebase returned
+   */
+  reason: RebaseStopReason;
+  /**
+   * Number of steps processed so far (the stopped step included)
+   */
+  currentStep: number;
+  /**
+   * Total number of steps in the plan
+   */
+  totalSteps: number;
+  /**
+   * OID of the original commit being applied when the rebase stopped
+   */
+  stoppedOid: string | null;
+  /**
+   * Conflicted file paths (empty unless stopped on conflicts)
+   */
+  conflictedFiles: string[];
+  /**
+   * HEAD commit OID after the operation
+   */
+  headOid: string | null;
+};
+/**
+ * Why a rebase returned control to the caller.
  */
-export type RateLimitInfo = {
-  limit: number;
-  remaining: number;
-  reset: number;
-  used: number;
+export type RebaseStopReason =
+  /**
+   * Every step was applied and the branch was updated
+   */
+  | "completed"
+  /**
+   * A step produced conflicts that must be resolved before continuing
+   */
+  | "conflicts"
+  /**
+   * An `edit` step stopped so the commit can be amended
+   */
+  | "edit";
+/**
+ * A single step of a rebase plan.
+ *
+ * The order of items in the plan is the order commits are applied in,
+ * so reordering the list reorders the rewritten history.
+ */
+export type RebaseTodoItem = {
+  /**
+   * What to do with the commit
+   */
+  action: RebaseAction;
+  /**
+   * Full OID of the commit
+   */
+  oid: string;
+  /**
+   * First line of the original commit message (display only)
+   */
+  messageSubject: string;
+  /**
+   * Replacement message for reword/squash steps (None keeps the default)
+   */
+  newMessage: string | null;
 };
 /**
  * A recently checked-out branch extracted from the reflog.
@@ -3164,10 +5004,113 @@ export type RecentCheckout = {
    */
   lastCheckoutMs: number;
 };
+/**
+ * Outcome of one remote ref update reported by the server.
+ */
+export type RefUpdateResult = {
+  /**
+   * Full remote ref name (e.g. `refs/heads/main`)
+   */
+  remoteRef: string;
+  /**
+   * Local ref pushed to it; `None` for deletions
+   */
+  localRef: string | null;
+  success: boolean;
+  /**
+   * Rejection reason from the server when `success` is false
+   */
+  message: string | null;
+};
+/**
+ * Kind of change a reflog entry records, parsed from its message.
+ */
+export type ReflogAction =
+  | "commit"
+  | "amend"
+  | "reset"
+  | "checkout"
+  | "merge"
+  | "rebase"
+  | "pull"
+  | "cherryPick"
+  | "branch"
+  | "clone"
+  | "other";
+/**
+ * A single reflog entry.
+ */
+export type ReflogEntry = {
+  /**
+   * Position in the reflog (0 = newest), as in `HEAD@{n}`
+   */
+  index: number;
+  /**
+   * OID the ref pointed to before the change
+   */
+  oldOid: string;
+  /**
+   * OID the ref pointed to after the change
+   */
+  newOid: string;
+  /**
+   * Parsed kind of change
+   */
+  action: ReflogAction;
+  /**
+   * Raw reflog message
+   */
+  message: string;
+  /**
+   * Who made the change
+   */
+  committerName: string;
+  /**
+   * Unix timestamp in milliseconds
+   */
+  timestampMs: number;
+  /**
+   * The commit the ref pointed to afterwards (None if it no longer exists)
+   */
+  commit: CommitSummary | null;
+};
+/**
+ * Outcome of fetching one remote as part of [`fetch_all`].
+ */
+export type RemoteFetchResult = { remote: string; result: SyncResult };
 /**
  * Information about a configured remote.
  */
-export type RemoteInfo = { name: string; url: string };
+export type RemoteInfo = {
+  name: string;
+  url: string;
+  /**
+   * Separate push URL (`remote.<name>.pushurl`), if configured
+   */
+  pushUrl: string | null;
+  /**
+   * Fetch refspecs (`remote.<name>.fetch`)
+   */
+  fetchRefspecs: string[];
+  /**
+   * GitHub owner, when the URL points at github.com
+   */
+  githubOwner: string | null;
+  /**
+   * GitHub repository name, when the URL points at github.com
+   */
+  githubRepo: string | null;
+};
+/**
+ * A renamed remote and the refspecs libgit2 could not rename.
+ */
+export type RenamedRemote = {
+  remote: RemoteInfo;
+  /**
+   * Non-default fetch refspecs left unchanged, which may need editing by hand
+   */
+  problems: string[];
+};
 /**
  * File content read from the repository at HEAD.
  */
@@ -3248,6 +5191,60 @@ export type RepoStatus = {
    * Repository display name (folder name)
    */
   repoName: string;
+  /**
+   * Merge, rebase, cherry-pick, revert or bisect in progress (if any)
+   */
+  operation: OperationState | null;
+  /**
+   * History is truncated (shallow clone) and can be deepened
+   */
+  isShallow: boolean;
+};
+/**
+ * The identity a repository commits with, and the profile it should use.
+ */
+export type RepositoryIdentity = {
+  /**
+   * Effective user.name
+   */
+  userName: string | null;
+  /**
+   * Effective user.email
+   */
+  userEmail: string | null;
+  /**
+   * Effective user.signingKey
+   */
+  signingKey: string | null;
+  /**
+   * Profile assigned to the repository or one of its parent directories
+   */
+  profile: IdentityProfile | null;
+  /**
+   * False if a profile is assigned and the effective identity differs from it
+   */
+  matches: boolean;
+};
+/**
+ * Result of a revert operation.
+ */
+export type RevertResult = {
+  /**
+   * Whether the revert commit was created
+   */
+  success: boolean;
+  /**
+   * OID of the revert commit (if created)
+   */
+  commitOid: string | null;
+  /**
+   * True if conflicts remain
+   */
+  hasConflicts: boolean;
+  /**
+   * List of conflicted file paths
+   */
+  conflictedFiles: string[];
 };
 /**
  * A scope suggestion from commit history.
@@ -3262,6 +5259,26 @@ export type ScopeSuggestion = {
    */
   usageCount: number;
 };
+/**
+ * Outcome of verifying a commit signature.
+ */
+export type SignatureStatus =
+  /**
+   * Valid signature from a trusted key or allowed signer
+   */
+  | "good"
+  /**
+   * Signature does not match, or the key is expired or revoked
+   */
+  | "bad"
+  /**
+   * Signed, but the key is not in the keyring / allowed signers
+   */
+  | "unknownKey"
+  /**
+   * No signature
+   */
+  | "unsigned";
 /**
  * Complete staging status showing staged, unstaged, and untracked files.
  */
@@ -3287,6 +5304,88 @@ export type StashEntry = {
    */
   oid: string;
 };
+/**
+ * A change of the commit a submodule points to.
+ */
+export type SubmoduleChange = {
+  oldOid: string | null;
+  newOid: string | null;
+  /**
+   * Commits in the new pointer that the old one lacks (when both are available locally)
+   */
+  commitsAhead: number | null;
+  /**
+   * Commits in the old pointer that the new one lacks
+   */
+  commitsBehind: number | null;
+  /**
+   * The submodule has uncommitted or untracked changes
+   */
+  dirty: boolean;
+  /**
+   * One-line description, e.g. "1a2b3c4 -> 5d6e7f8 (3 commits)"
+   */
+  summary: string;
+};
+/**
+ * A submodule of the open repository.
+ */
+export type SubmoduleInfo = {
+  name: string;
+  path: string;
+  url: string | null;
+  /**
+   * Branch tracked by `update --remote` (`submodule.<name>.branch`)
+   */
+  branch: string | null;
+  /**
+   * Commit recorded in HEAD
+   */
+  headOid: string | null;
+  /**
+   * Commit recorded in the index
+   */
+  indexOid: string | null;
+  /**
+   * Commit checked out in the submodule
+   */
+  workdirOid: string | null;
+  state: SubmoduleState;
+};
+/**
+ * Progress events for submodule updates.
+ */
+export type SubmoduleProgress =
+  | { event: "updating"; data: { path: string } }
+  | {
+      event: "transferring";
+      data: { path: string; current: number; total: number; bytes: number };
+    }
+  | { event: "updated"; data: { path: string } }
+  /**
+   * The update was cancelled through `cancel_operation`
+   */
+  | { event: "cancelled" };
+/**
+ * Where a submodule's checkout stands relative to the superproject.
+ */
+export type SubmoduleState =
+  /**
+   * Not initialized or not checked out
+   */
+  | "uninitialized"
+  /**
+   * Checked out at the recorded commit with no local changes
+   */
+  | "upToDate"
+  /**
+   * Checked out at a different commit than the one recorded in the index
+   */
+  | "modified"
+  /**
+   * At the recorded commit, but with uncommitted or untracked changes inside
+   */
+  | "dirty";
 /**
  * Progress events for remote sync operations.
  * Uses tagged enum serialization for frontend type safety.
@@ -3300,7 +5399,23 @@ export type SyncProgress =
       data: { current: number; total: number; bytes: number };
     }
   | { event: "resolving"; data: { current: number; total: number } }
+  | { event: "hookOutput"; data: { hook: string; line: string } }
+  /**
+   * Transfer progress of one remote while several are fetched at once
+   */
+  | {
+      event: "remoteTransferring";
+      data: { remote: string; current: number; total: number; bytes: number };
+    }
+  /**
+   * One remote finished while several are fetched at once
+   */
+  | { event: "remoteFinished"; data: { remote: string; success: boolean } }
   | { event: "finished"; data: { operation: string } }
+  /**
+   * The operation was cancelled through `cancel_operation`
+   */
+  | { event: "cancelled"; data: { operation: string } }
   | { event: "error"; data: { message: string } };
 /**
  * Result of a sync operation (push/pull/fetch).
@@ -3309,7 +5424,98 @@ export type SyncResult = {
   success: boolean;
   message: string;
   commitsTransferred: number;
+  /**
+   * Per-ref results reported by the remote (push only)
+   */
+  refUpdates: RefUpdateResult[];
+  /**
+   * Remote-tracking refs and tags deleted by pruning (fetch only)
+   */
+  prunedRefs: string[];
+};
+/**
+ * Two tags side by side, for release comparisons.
+ */
+export type TagComparison = {
+  from: TagInfo;
+  to: TagInfo;
+  /**
+   * Commits in `to` that are not in `from`
+   */
+  commitsAhead: number;
+  /**
+   * Commits in `from` that are not in `to`
+   */
+  commitsBehind: number;
+  /**
+   * Milliseconds from `from`'s creation to `to`'s
+   */
+  timeBetweenMs: number;
+  /**
+   * Whether the tagger differs
+   */
+  taggerChanged: boolean;
+  /**
+   * Line diff of the tag messages (empty when they are identical)
+   */
+  messageDiff: DiffLine[];
+};
+/**
+ * The nearest tag reachable from a commit (`git describe --tags`).
+ */
+export type TagDescription = {
+  tag: string;
+  /**
+   * Commits between the tag and the described commit
+   */
+  distance: number;
+  /**
+   * `git describe` output, e.g. "v1.2.0-3-gabc1234" or "v1.2.0"
+   */
+  description: string;
+};
+/**
+ * Which tags a fetch downloads.
+ */
+export type TagFollowing =
+  /**
+   * Tags pointing at fetched commits (git's default)
+   */
+  | "auto"
+  /**
+   * All tags (`--tags`)
+   */
+  | "all"
+  /**
+   * No tags (`--no-tags`)
+   */
+  | "none";
+/**
+ * Tags sharing a major or minor version, highest version first.
+ */
+export type TagGroup = {
+  /**
+   * "1.x", "1.2.x", or "Other" for tags that are not versions
+   */
+  label: string;
+  tags: TagInfo[];
+  /**
+   * How many of the tags are pre-releases
+   */
+  preReleaseCount: number;
 };
+/**
+ * How `list_tag_groups` buckets versions.
+ */
+export type TagGrouping =
+  /**
+   * One group per major version ("1.x")
+   */
+  | "major"
+  /**
+   * One group per minor version ("1.2.x")
+   */
+  | "minor";
 /**
  * Information about a git tag.
  */
@@ -3342,6 +5548,37 @@ export type TagInfo = {
    * Creation timestamp in milliseconds since epoch
    */
   createdAtMs: number;
+  /**
+   * Semantic version parsed from the name, if it is one (e.g. "v1.2.0-rc.1")
+   */
+  version: TagVersion | null;
+};
+/**
+ * Order of `list_tags`.
+ */
+export type TagSort =
+  /**
+   * Newest first by tagger or commit date
+   */
+  | "date"
+  /**
+   * Highest semantic version first; other tags follow, newest first
+   */
+  | "semver";
+/**
+ * A semantic version parsed from a tag name.
+ *
+ * An optional `v` prefix and a missing patch number ("v1.2") are accepted;
+ * build metadata after `+` is ignored.
+ */
+export type TagVersion = {
+  major: number;
+  minor: number;
+  patch: number;
+  /**
+   * Pre-release identifiers after `-` (e.g. "rc.1"), None for a release
+   */
+  preRelease: string | null;
 };
 /**
  * A suggested commit type based on file analysis.
@@ -3381,6 +5618,23 @@ export type UndoInfo = {
    */
   targetOid: string | null;
 };
+/**
+ * The undo/redo stack.
+ */
+export type UndoJournal = {
+  /**
+   * Entries, newest first
+   */
+  entries: JournalEntry[];
+  /**
+   * Whether there is an entry to undo
+   */
+  canUndo: boolean;
+  /**
+   * Whether there is an entry to redo
+   */
+  canRedo: boolean;
+};
 /**
  * Minimal user info for frontend display.
  */
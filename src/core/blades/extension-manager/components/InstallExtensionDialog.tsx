@@ -59,7 +59,7 @@ export function InstallExtensionDialog({
     if (!url.trim()) return;
     setStep("fetching");
     try {
-      const result = await commands.extensionFetchManifest(url.trim());
+      const result = await commands.extensionFetchManifest(url.trim(), null);
       if (result.status === "error") {
         setErrorMessage(result.error);
         setStep("error");
@@ -22,7 +22,7 @@ export function useCommitExecution(options?: UseCommitExecutionOptions) {
   const pushMutation = useMutation({
     mutationFn: () => {
       const channel = new Channel<SyncProgress>();
-      return commands.pushToRemote("origin", channel);
+      return commands.pushToRemote("origin", channel, null, null);
     },
     onSuccess: (result) => {
       if (result.status === "error") {
@@ -51,7 +51,7 @@ export function useCommitExecution(options?: UseCommitExecutionOptions) {
 
   const commitMutation = useMutation({
     mutationFn: ({ message, amend }: { message: string; amend: boolean }) =>
-      commands.createCommit(message, amend),
+      commands.createCommit(message, amend, null),
     onSuccess: (_data, { message: commitMessage }) => {
       queryClient.invalidateQueries({ queryKey: ["stagingStatus"] });
       queryClient.invalidateQueries({ queryKey: ["commitHistory"] });
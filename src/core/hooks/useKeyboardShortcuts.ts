@@ -51,7 +51,7 @@ export function useKeyboardShortcuts() {
   const pushMutation = useMutation({
     mutationFn: () => {
       const channel = new Channel<SyncProgress>();
-      return commands.pushToRemote("origin", channel);
+      return commands.pushToRemote("origin", channel, null, null);
     },
     onSuccess: () => {
       toast.success("Pushed to origin");
@@ -69,7 +69,7 @@ export function useKeyboardShortcuts() {
   const pullMutation = useMutation({
     mutationFn: () => {
       const channel = new Channel<SyncProgress>();
-      return commands.pullFromRemote("origin", channel);
+      return commands.pullFromRemote("origin", channel, null, null);
     },
     onSuccess: () => {
       toast.success("Pulled from origin");
@@ -88,7 +88,7 @@ export function useKeyboardShortcuts() {
   const fetchMutation = useMutation({
     mutationFn: () => {
       const channel = new Channel<SyncProgress>();
-      return commands.fetchFromRemote("origin", channel);
+      return commands.fetchFromRemote("origin", channel, null, null);
     },
     onSuccess: () => {
       toast.success("Fetched from origin");
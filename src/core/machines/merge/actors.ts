@@ -5,7 +5,7 @@ import { getErrorMessage } from "../../lib/errors";
 
 export const executeMerge = fromPromise<MergeResult, { sourceBranch: string }>(
   async ({ input }) => {
-    const result = await commands.mergeBranch(input.sourceBranch);
+    const result = await commands.mergeBranch(input.sourceBranch, null);
     if (result.status === "error") {
       throw new Error(getErrorMessage(result.error));
     }
@@ -98,7 +98,7 @@ export const createBranchSlice: StateCreator<
       undefined,
       "gitOps:branch/checkout",
     );
-    const result = await commands.checkoutBranch(name);
+    const result = await commands.checkoutBranch(name, null);
     if (result.status === "ok") {
       await get().loadBranches();
       gitHookBus.emitDid("checkout", { branchName: name });
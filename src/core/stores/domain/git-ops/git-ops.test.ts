@@ -202,7 +202,13 @@ describe("useGitOpsStore", () => {
         .saveStash("test stash", false);
 
       expect(result).toBe(true);
-      expect(mockCommands.stashSave).toHaveBeenCalledWith("test stash", false);
+      expect(mockCommands.stashSave).toHaveBeenCalledWith(
+        "test stash",
+        false,
+        null,
+        null,
+        null,
+      );
     });
 
     it("saveStash returns false and sets error on failure", async () => {
@@ -55,7 +55,13 @@ export const createStashSlice: StateCreator<
       undefined,
       "gitOps:stash/save",
     );
-    const result = await commands.stashSave(message, includeUntracked);
+    const result = await commands.stashSave(
+      message,
+      includeUntracked,
+      null,
+      null,
+      null,
+    );
     if (result.status === "ok") {
       await get().loadStashes();
       return true;
@@ -70,7 +76,7 @@ export const createStashSlice: StateCreator<
       undefined,
       "gitOps:stash/apply",
     );
-    const result = await commands.stashApply(index);
+    const result = await commands.stashApply(index, null);
     if (result.status === "ok") {
       set({ stashIsLoading: false }, undefined, "gitOps:stash/applyOk");
       return true;
@@ -85,7 +91,7 @@ export const createStashSlice: StateCreator<
       undefined,
       "gitOps:stash/pop",
     );
-    const result = await commands.stashPop(index);
+    const result = await commands.stashPop(index, null);
     if (result.status === "ok") {
       await get().loadStashes();
       return true;
@@ -27,7 +27,7 @@ export const createTagSlice: StateCreator<
 
   loadTags: async () => {
     set({ tagIsLoading: true, tagError: null }, undefined, "gitOps:tag/load");
-    const result = await commands.listTags();
+    const result = await commands.listTags(null);
     if (result.status === "ok") {
       set(
         { tagList: result.data, tagIsLoading: false },
@@ -31,6 +31,7 @@ describe("UI State store", () => {
         status: "modified" as const,
         additions: 1,
         deletions: 0,
+        submodule: null,
       };
       useUIStore.getState().selectFile(file, "unstaged");
 
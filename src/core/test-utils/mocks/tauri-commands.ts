@@ -5,6 +5,7 @@ import type {
   CommitDetails,
   CommitGraph,
   CommitInfo,
+  CommitSignature,
   CommitSummary,
   FileChange,
   FileChanged,
@@ -57,6 +58,8 @@ export function createRepoStatus(overrides?: Partial<RepoStatus>): RepoStatus {
     isDirty: false,
     repoPath: "/test/repo",
     repoName: "repo",
+    operation: null,
+    isShallow: false,
     ...overrides,
   };
 }
@@ -78,6 +81,7 @@ export function createFileChange(overrides?: Partial<FileChange>): FileChange {
     status: "modified",
     additions: 10,
     deletions: 5,
+    submodule: null,
     ...overrides,
   };
 }
@@ -91,6 +95,18 @@ export function createBranchInfo(overrides?: Partial<BranchInfo>): BranchInfo {
     isMerged: null,
     isRemote: false,
     remoteName: null,
+    description: null,
+    ...overrides,
+  };
+}
+
+export function createCommitSignature(
+  overrides?: Partial<CommitSignature>,
+): CommitSignature {
+  return {
+    status: "unsigned",
+    signer: null,
+    keyId: null,
     ...overrides,
   };
 }
@@ -105,6 +121,7 @@ export function createCommitSummary(
     authorName: "Test User",
     authorEmail: "test@example.com",
     timestampMs: Date.now(),
+    signature: createCommitSignature(),
     ...overrides,
   };
 }
@@ -117,6 +134,7 @@ export function createFileDiff(overrides?: Partial<FileDiff>): FileDiff {
     hunks: [],
     isBinary: false,
     language: "typescript",
+    submodule: null,
     ...overrides,
   };
 }
@@ -136,6 +154,7 @@ export function createCommitDetails(
     committerTimestampMs: Date.now(),
     parentOids: [],
     filesChanged: [],
+    signature: createCommitSignature(),
     ...overrides,
   };
 }
@@ -145,6 +164,7 @@ export function createCommitInfo(overrides?: Partial<CommitInfo>): CommitInfo {
     oid: "abc1234567890abcdef1234567890abcdef123456",
     shortOid: "abc1234",
     message: "feat: test commit",
+    identityWarning: null,
     ...overrides,
   };
 }
@@ -158,6 +178,7 @@ export function createTagInfo(overrides?: Partial<TagInfo>): TagInfo {
     tagger: "Test User",
     isAnnotated: true,
     createdAtMs: Date.now(),
+    version: null,
     ...overrides,
   };
 }
@@ -175,6 +196,10 @@ export function createRemoteInfo(overrides?: Partial<RemoteInfo>): RemoteInfo {
   return {
     name: "origin",
     url: "https://github.com/test/repo.git",
+    pushUrl: null,
+    fetchRefspecs: ["+refs/heads/*:refs/remotes/origin/*"],
+    githubOwner: "test",
+    githubRepo: "repo",
     ...overrides,
   };
 }
@@ -211,6 +236,7 @@ export function createFileChanged(
     status: "modified",
     additions: 10,
     deletions: 5,
+    submodule: null,
     ...overrides,
   };
 }
@@ -251,6 +277,8 @@ export function createMockCommands() {
         success: true,
         message: "Fetched",
         commitsTransferred: 0,
+        refUpdates: [],
+        prunedRefs: [],
       } satisfies SyncResult),
     ),
     pushToRemote: vi.fn().mockResolvedValue(
@@ -258,6 +286,8 @@ export function createMockCommands() {
         success: true,
         message: "Pushed",
         commitsTransferred: 0,
+        refUpdates: [],
+        prunedRefs: [],
       } satisfies SyncResult),
     ),
     pullFromRemote: vi.fn().mockResolvedValue(
@@ -265,6 +295,8 @@ export function createMockCommands() {
         success: true,
         message: "Pulled",
         commitsTransferred: 0,
+        refUpdates: [],
+        prunedRefs: [],
       } satisfies SyncResult),
     ),
     listBranches: vi.fn().mockResolvedValue(ok([])),
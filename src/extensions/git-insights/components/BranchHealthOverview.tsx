@@ -37,7 +37,7 @@ function formatRelativeDate(timestampMs: number): string {
 
 async function handleCheckout(name: string) {
   try {
-    const result = await commands.checkoutBranch(name);
+    const result = await commands.checkoutBranch(name, null);
     if (result.status === "error") {
       const errMsg =
         "message" in result.error
@@ -104,6 +104,7 @@ export function InitRepoForm({ onCancel, onComplete }: InitRepoFormProps) {
           await commands.createCommit(
             store.commitMessage || "Initial commit",
             false,
+            null,
           );
         }
       }
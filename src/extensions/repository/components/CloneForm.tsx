@@ -84,7 +84,13 @@ export function CloneForm({ onCancel }: CloneFormProps) {
         updateProgress(event);
       };
 
-      const result = await commands.cloneRepository(url, destination, channel);
+      const result = await commands.cloneRepository(
+        url,
+        destination,
+        channel,
+        null,
+        null,
+      );
       if (result.status === "error") {
         throw new Error(
           typeof result.error === "object" && "message" in result.error
@@ -36,7 +36,7 @@ export function SyncButtons() {
   const pushMutation = useMutation({
     mutationFn: async () => {
       const channel = createProgressChannel();
-      return commands.pushToRemote(defaultRemote, channel);
+      return commands.pushToRemote(defaultRemote, channel, null, null);
     },
     onSuccess: () => {
       toast.success(`Pushed to ${defaultRemote}`);
@@ -54,7 +54,7 @@ export function SyncButtons() {
   const pullMutation = useMutation({
     mutationFn: async () => {
       const channel = createProgressChannel();
-      return commands.pullFromRemote(defaultRemote, channel);
+      return commands.pullFromRemote(defaultRemote, channel, null, null);
     },
     onSuccess: () => {
       toast.success(`Pulled from ${defaultRemote}`);
@@ -74,7 +74,7 @@ export function SyncButtons() {
   const fetchMutation = useMutation({
     mutationFn: async () => {
       const channel = createProgressChannel();
-      return commands.fetchFromRemote(defaultRemote, channel);
+      return commands.fetchFromRemote(defaultRemote, channel, null, null);
     },
     onSuccess: () => {
       toast.success(`Fetched from ${defaultRemote}`);
@@ -36,7 +36,12 @@ export async function onActivate(api: ExtensionAPI): Promise<void> {
     action: async () => {
       try {
         const channel = new Channel<SyncProgress>();
-        const result = await tauriCommands.pushToRemote("origin", channel);
+        const result = await tauriCommands.pushToRemote(
+          "origin",
+          channel,
+          null,
+          null,
+        );
         if (result.status === "error") {
           toast.error(`Push failed: ${getErrorMessage(result.error)}`);
           return;
@@ -63,7 +68,12 @@ export async function onActivate(api: ExtensionAPI): Promise<void> {
     action: async () => {
       try {
         const channel = new Channel<SyncProgress>();
-        const result = await tauriCommands.pullFromRemote("origin", channel);
+        const result = await tauriCommands.pullFromRemote(
+          "origin",
+          channel,
+          null,
+          null,
+        );
         if (result.status === "error") {
           toast.error(`Pull failed: ${getErrorMessage(result.error)}`);
           return;
@@ -90,7 +100,12 @@ export async function onActivate(api: ExtensionAPI): Promise<void> {
     action: async () => {
       try {
         const channel = new Channel<SyncProgress>();
-        const result = await tauriCommands.fetchFromRemote("origin", channel);
+        const result = await tauriCommands.fetchFromRemote(
+          "origin",
+          channel,
+          null,
+          null,
+        );
         if (result.status === "error") {
           toast.error(`Fetch failed: ${getErrorMessage(result.error)}`);
           return;
@@ -153,7 +168,12 @@ export async function onActivate(api: ExtensionAPI): Promise<void> {
       pushLoading = true;
       try {
         const channel = new Channel<SyncProgress>();
-        const result = await tauriCommands.pushToRemote("origin", channel);
+        const result = await tauriCommands.pushToRemote(
+          "origin",
+          channel,
+          null,
+          null,
+        );
         if (result.status === "error") {
           toast.error(`Push failed: ${getErrorMessage(result.error)}`);
           return;
@@ -185,7 +205,12 @@ export async function onActivate(api: ExtensionAPI): Promise<void> {
       pullLoading = true;
       try {
         const channel = new Channel<SyncProgress>();
-        const result = await tauriCommands.pullFromRemote("origin", channel);
+        const result = await tauriCommands.pullFromRemote(
+          "origin",
+          channel,
+          null,
+          null,
+        );
         if (result.status === "error") {
           toast.error(`Pull failed: ${getErrorMessage(result.error)}`);
           return;
@@ -217,7 +242,12 @@ export async function onActivate(api: ExtensionAPI): Promise<void> {
       fetchLoading = true;
       try {
         const channel = new Channel<SyncProgress>();
-        const result = await tauriCommands.fetchFromRemote("origin", channel);
+        const result = await tauriCommands.fetchFromRemote(
+          "origin",
+          channel,
+          null,
+          null,
+        );
         if (result.status === "error") {
           toast.error(`Fetch failed: ${getErrorMessage(result.error)}`);
           return;
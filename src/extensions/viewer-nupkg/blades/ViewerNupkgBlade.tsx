@@ -12,6 +12,7 @@ export function ViewerNupkgBlade({ filePath }: ViewerNupkgBladeProps) {
     status: "modified",
     additions: null,
     deletions: null,
+    submodule: null,
   };
 
   return <NugetPackageViewer file={file} section={null} />;